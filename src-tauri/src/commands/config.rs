@@ -1,60 +1,54 @@
 use crate::models::{
-    AIConfigOverview, ChannelConfig, ConfiguredModel, ConfiguredProvider,
-    ModelConfig, ModelCostConfig, OfficialProvider, OpenClawConfig,
-    ProviderConfig, SuggestedModel,
+    AIConfigOverview, ChannelConfig, ChannelDescriptor, ChannelToggleResult, ChannelsOverview,
+    ConfigBackup, ConfigIssue, ConfigSnapshot, ConfiguredModel, ConfiguredProvider, EnvChange, EnvVarInfo, GatewayTokenInfo,
+    ModelConfig, ModelCostConfig, OfficialProvider, OpenClawConfig, SuggestedModel,
+    DEFAULT_CHANNEL_ACCOUNT,
+};
+use crate::utils::supervisor::GatewaySupervisor;
+use crate::utils::env_file::{self, EnvFile};
+use crate::utils::{
+    channel_registry, channel_schema, config_store, config_validator, file, gateway,
+    gateway_token, platform, plugins, secrets, settings,
 };
-use crate::utils::{file, platform, shell};
 use log::{debug, error, info, warn};
 use serde_json::{json, Value};
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::command;
+use tauri::{command, State};
 
-/// 获取 openclaw.json 配置
-fn load_openclaw_config() -> Result<Value, String> {
-    let config_path = platform::get_config_file_path();
-    
-    if !file::file_exists(&config_path) {
-        return Ok(json!({}));
-    }
-    
-    let content =
-        file::read_file(&config_path).map_err(|e| format!("读取配置文件失败: {}", e))?;
-    
-    serde_json::from_str(&content).map_err(|e| format!("解析配置文件失败: {}", e))
+/// 获取 openclaw.json 配置（类型化，未建模的字段原样保留）
+fn load_openclaw_config() -> Result<OpenClawConfig, String> {
+    config_store::load_typed_with_revision().map(|(config, _)| config)
 }
 
-/// 保存 openclaw.json 配置
-fn save_openclaw_config(config: &Value) -> Result<(), String> {
-    let config_path = platform::get_config_file_path();
-    
-    let content =
-        serde_json::to_string_pretty(config).map_err(|e| format!("序列化配置失败: {}", e))?;
-    
-    file::write_file(&config_path, &content).map_err(|e| format!("写入配置文件失败: {}", e))
+/// 保存 openclaw.json 配置（原子写入，并保留备份）
+/// 读取后文件被外部修改时拒绝保存
+fn save_openclaw_config(config: &OpenClawConfig, revision: &str) -> Result<(), String> {
+    config_store::save_typed(config, Some(revision))
 }
 
-/// 获取完整配置
+/// 获取完整配置及其版本
 #[command]
-pub async fn get_config() -> Result<Value, String> {
+pub async fn get_config() -> Result<ConfigSnapshot, String> {
     info!("[获取配置] 读取 openclaw.json 配置...");
-    let result = load_openclaw_config();
+    let result = config_store::load_with_revision();
     match &result {
         Ok(_) => info!("[获取配置] ✓ 配置读取成功"),
         Err(e) => error!("[获取配置] ✗ 配置读取失败: {}", e),
     }
-    result
+    result.map(|(config, revision)| ConfigSnapshot { config, revision })
 }
 
 /// 保存配置
 #[command]
-pub async fn save_config(config: Value) -> Result<String, String> {
+pub async fn save_config(config: Value, expected_revision: Option<String>) -> Result<String, String> {
     info!("[保存配置] 保存 openclaw.json 配置...");
     debug!(
         "[保存配置] 配置内容: {}",
         serde_json::to_string_pretty(&config).unwrap_or_default()
     );
-    match save_openclaw_config(&config) {
+    // 与读取相同的宽松解析：无法解析的节点原样写回，具体错误由 validate_config 报告
+    let typed = config_store::parse_typed(config)?;
+    match config_store::save_typed(&typed, expected_revision.as_deref()) {
         Ok(_) => {
             info!("[保存配置] ✓ 配置保存成功");
             Ok("配置已保存".to_string())
@@ -66,12 +60,59 @@ pub async fn save_config(config: Value) -> Result<String, String> {
     }
 }
 
+/// 获取 openclaw.json 当前版本（内容哈希），保存时作为 expected_revision 传回
+#[command]
+pub async fn get_config_revision() -> Result<String, String> {
+    Ok(config_store::revision())
+}
+
+/// 校验 openclaw.json，返回带 JSON 路径的问题列表（为空表示通过）
+#[command]
+pub async fn validate_config() -> Result<Vec<ConfigIssue>, String> {
+    info!("[校验配置] 校验 openclaw.json...");
+    let config = config_store::load()?;
+    let issues = config_validator::validate(&config);
+    info!("[校验配置] ✓ 发现 {} 个问题", issues.len());
+    for issue in &issues {
+        debug!("[校验配置] - [{}] {}: {}", issue.severity, issue.path, issue.message);
+    }
+    Ok(issues)
+}
+
+/// 列出 openclaw.json 的备份（最新的在前）
+#[command]
+pub async fn list_config_backups() -> Result<Vec<ConfigBackup>, String> {
+    Ok(config_store::list_backups())
+}
+
+/// 用指定备份恢复 openclaw.json
+#[command]
+pub async fn restore_config_backup(name: String) -> Result<String, String> {
+    info!("[配置备份] 恢复备份: {}", name);
+    match config_store::restore_backup(&name) {
+        Ok(_) => {
+            info!("[配置备份] ✓ 已恢复备份 {}", name);
+            Ok(format!("已恢复备份 {}，重启服务后生效", name))
+        }
+        Err(e) => {
+            error!("[配置备份] ✗ 恢复失败: {}", e);
+            Err(e)
+        }
+    }
+}
+
 /// 获取环境变量值
 #[command]
 pub async fn get_env_value(key: String) -> Result<Option<String>, String> {
     info!("[获取环境变量] 读取环境变量: {}", key);
     let env_path = platform::get_env_file_path();
-    let value = file::read_env_value(&env_path, &key);
+    // 密钥类变量优先从密钥库读取，兼容尚未迁移的 env 文件
+    let stored = if secrets::is_secret_env_key(&key) {
+        secrets::backend(&settings::active_profile()).get(&key)?
+    } else {
+        None
+    };
+    let value = stored.or_else(|| file::read_env_value(&env_path, &key));
     match &value {
         Some(v) => debug!(
             "[获取环境变量] {}={} (已脱敏)",
@@ -89,6 +130,17 @@ pub async fn save_env_value(key: String, value: String) -> Result<String, String
     info!("[保存环境变量] 保存环境变量: {}", key);
     let env_path = platform::get_env_file_path();
     debug!("[保存环境变量] 环境文件路径: {}", env_path);
+
+    // 密钥类变量保存到密钥库，并移除 env 文件中的明文
+    if secrets::is_secret_env_key(&key) {
+        secrets::backend(&settings::active_profile()).set(&key, &value)?;
+        if file::read_env_value(&env_path, &key).is_some() {
+            file::remove_env_value(&env_path, &key)
+                .map_err(|e| format!("移除 env 文件中的明文失败: {}", e))?;
+        }
+        info!("[保存环境变量] ✓ {} 已保存到密钥库", key);
+        return Ok("环境变量已保存".to_string());
+    }
     
     match file::set_env_value(&env_path, &key, &value) {
         Ok(_) => {
@@ -102,66 +154,269 @@ pub async fn save_env_value(key: String, value: String) -> Result<String, String
     }
 }
 
-// ============ Gateway Token 命令 ============
+/// 找出字符串中所有 `${NAME}` 引用的变量名
+fn referenced_env_names(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                names.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
 
-/// 生成随机 token
-fn generate_token() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    
-    // 使用时间戳和随机数生成 token
-    let random_part: u64 = (timestamp as u64) ^ 0x5DEECE66Du64;
-    format!("{:016x}{:016x}{:016x}", 
-        random_part, 
-        random_part.wrapping_mul(0x5DEECE66Du64),
-        timestamp as u64
-    )
+/// 根据 JSON 路径描述引用方
+fn describe_config_path(path: &[String]) -> String {
+    match path {
+        [models, providers, name, ..] if models == "models" && providers == "providers" => {
+            format!("Provider {}", name)
+        }
+        [channels, id, ..] if channels == "channels" => format!("渠道 {}", id),
+        [gateway, ..] if gateway == "gateway" => "Gateway".to_string(),
+        _ => path.join("."),
+    }
 }
 
-/// 获取或生成 Gateway Token
+/// 统计各环境变量的用途：openclaw.json 中的 `${NAME}` 引用、
+/// Provider 的默认 API Key 变量、渠道的 OPENCLAW_<渠道>_* 变量和 Gateway Token
+fn collect_env_usages(config: &OpenClawConfig) -> HashMap<String, Vec<String>> {
+    fn walk(value: &Value, path: &mut Vec<String>, usages: &mut HashMap<String, Vec<String>>) {
+        match value {
+            Value::String(text) => {
+                for name in referenced_env_names(text) {
+                    usages
+                        .entry(name.to_string())
+                        .or_default()
+                        .push(describe_config_path(path));
+                }
+            }
+            Value::Object(map) => {
+                for (key, child) in map {
+                    path.push(key.clone());
+                    walk(child, path, usages);
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    walk(child, path, usages);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut usages: HashMap<String, Vec<String>> = HashMap::new();
+    if let Ok(value) = serde_json::to_value(config) {
+        walk(&value, &mut Vec::new(), &mut usages);
+    }
+
+    for provider_name in config.models.providers.keys() {
+        usages
+            .entry(secrets::provider_secret_name(provider_name))
+            .or_default()
+            .push(format!("Provider {}", provider_name));
+    }
+    usages
+        .entry("OPENCLAW_GATEWAY_TOKEN".to_string())
+        .or_default()
+        .push("Gateway".to_string());
+
+    for list in usages.values_mut() {
+        list.sort();
+        list.dedup();
+    }
+    usages
+}
+
+/// 列出当前档案的所有环境变量（env 文件和密钥库），值已脱敏
 #[command]
-pub async fn get_or_create_gateway_token() -> Result<String, String> {
-    info!("[Gateway Token] 获取或创建 Gateway Token...");
-    
-    let mut config = load_openclaw_config()?;
-    
-    // 检查是否已有 token
-    if let Some(token) = config
-        .pointer("/gateway/auth/token")
-        .and_then(|v| v.as_str())
-    {
-        if !token.is_empty() {
-            info!("[Gateway Token] ✓ 使用现有 Token");
-            return Ok(token.to_string());
+pub async fn list_env_vars() -> Result<Vec<EnvVarInfo>, String> {
+    info!("[环境变量] 列出环境变量...");
+    let profile = settings::active_profile();
+
+    let env = EnvFile::load(&profile.env_file_path())
+        .map_err(|e| format!("读取 env 文件失败: {}", e))?;
+    let vault = secrets::backend(&profile).load_all().unwrap_or_else(|e| {
+        warn!("[环境变量] 读取密钥库失败: {}", e);
+        Default::default()
+    });
+    let config = load_openclaw_config().unwrap_or_default();
+    let usages = collect_env_usages(&config);
+
+    // 启动时密钥库中的值会覆盖 env 文件中的同名变量
+    let mut values: Vec<(String, String, &str)> = env
+        .vars()
+        .into_iter()
+        .filter(|(key, _)| !vault.contains_key(key))
+        .map(|(key, value)| (key, value, "env"))
+        .collect();
+    values.extend(vault.into_iter().map(|(key, value)| (key, value, "vault")));
+    values.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let vars: Vec<EnvVarInfo> = values
+        .into_iter()
+        .map(|(key, value, source)| {
+            let mut used_by = usages.get(&key).cloned().unwrap_or_default();
+            used_by.extend(
+                config
+                    .channels
+                    .keys()
+                    .filter(|id| key.starts_with(&format!("OPENCLAW_{}_", id.to_uppercase())))
+                    .map(|id| format!("渠道 {}", id)),
+            );
+            EnvVarInfo {
+                masked_value: secrets::mask(&value),
+                source: source.to_string(),
+                is_secret: secrets::is_secret_env_key(&key),
+                used_by,
+                key,
+            }
+        })
+        .collect();
+
+    info!("[环境变量] ✓ 共 {} 个环境变量", vars.len());
+    Ok(vars)
+}
+
+/// 删除环境变量（同时从 env 文件和密钥库中删除）
+#[command]
+pub async fn delete_env_var(key: String) -> Result<String, String> {
+    info!("[环境变量] 删除环境变量: {}", key);
+    let profile = settings::active_profile();
+    let env_path = profile.env_file_path();
+
+    let in_env = file::read_env_value(&env_path, &key).is_some();
+    if in_env {
+        file::remove_env_value(&env_path, &key)
+            .map_err(|e| format!("删除环境变量失败: {}", e))?;
+    }
+    let in_vault = secrets::backend(&profile).delete(&key)?;
+
+    if !in_env && !in_vault {
+        return Err(format!("环境变量不存在: {}", key));
+    }
+    info!("[环境变量] ✓ {} 已删除", key);
+    Ok(format!("环境变量 {} 已删除", key))
+}
+
+/// 批量修改环境变量：全部校验通过后一次性写入
+/// 密钥类变量写入密钥库（并移除 env 文件中的明文），其余写入 env 文件；
+/// env 文件写入失败时回滚密钥库的修改
+#[command]
+pub async fn apply_env_changes(changes: Vec<EnvChange>) -> Result<String, String> {
+    info!("[环境变量] 批量修改 {} 个环境变量", changes.len());
+
+    if let Some(invalid) = changes.iter().find(|c| !env_file::is_valid_key(&c.key)) {
+        return Err(format!("环境变量名无效: {}", invalid.key));
+    }
+
+    let profile = settings::active_profile();
+    let env_path = profile.env_file_path();
+    let backend = secrets::backend(&profile);
+
+    let mut env = EnvFile::load(&env_path).map_err(|e| format!("读取 env 文件失败: {}", e))?;
+    let previous_secrets = backend.load_all()?;
+    let mut vault_changes: Vec<(String, Option<String>)> = Vec::new();
+
+    for change in &changes {
+        match &change.value {
+            Some(value) if secrets::is_secret_env_key(&change.key) => {
+                env.remove(&change.key);
+                vault_changes.push((change.key.clone(), Some(value.clone())));
+            }
+            Some(value) => {
+                env.set(&change.key, value);
+            }
+            None => {
+                env.remove(&change.key);
+                if previous_secrets.contains_key(&change.key) {
+                    vault_changes.push((change.key.clone(), None));
+                }
+            }
         }
     }
-    
-    // 生成新 token
-    let new_token = generate_token();
-    info!("[Gateway Token] 生成新 Token: {}...", &new_token[..8]);
-    
-    // 确保路径存在
-    if config.get("gateway").is_none() {
-        config["gateway"] = json!({});
+
+    if !vault_changes.is_empty() {
+        backend.apply(&vault_changes)?;
     }
-    if config["gateway"].get("auth").is_none() {
-        config["gateway"]["auth"] = json!({});
+
+    if let Err(e) = env.save(&env_path) {
+        error!("[环境变量] ✗ 写入 env 文件失败，回滚密钥库: {}", e);
+        let rollback: Vec<(String, Option<String>)> = vault_changes
+            .iter()
+            .map(|(key, _)| (key.clone(), previous_secrets.get(key).cloned()))
+            .collect();
+        if let Err(rollback_err) = backend.apply(&rollback) {
+            error!("[环境变量] ✗ 回滚密钥库失败: {}", rollback_err);
+        }
+        return Err(format!("写入 env 文件失败: {}", e));
     }
-    
-    // 设置 token 和 mode
-    config["gateway"]["auth"]["token"] = json!(new_token);
-    config["gateway"]["auth"]["mode"] = json!("token");
-    config["gateway"]["mode"] = json!("local");
-    
-    // 保存配置
-    save_openclaw_config(&config)?;
-    
-    info!("[Gateway Token] ✓ Token 已保存到配置");
-    Ok(new_token)
+
+    info!("[环境变量] ✓ 已应用 {} 项修改", changes.len());
+    Ok(format!("已应用 {} 项修改", changes.len()))
+}
+
+// ============ Gateway Token 命令 ============
+
+/// 获取或生成 Gateway Token
+#[command]
+pub async fn get_or_create_gateway_token() -> Result<String, String> {
+    info!("[Gateway Token] 获取或创建 Gateway Token...");
+    let token = gateway_token::ensure(&settings::active_profile())?;
+    info!("[Gateway Token] ✓ Token 已就绪");
+    Ok(token)
+}
+
+/// 轮换 Gateway Token：写入新 token，Gateway 运行中则重启使其生效，返回新的 Dashboard URL
+#[command]
+pub async fn rotate_gateway_token(
+    supervisor: State<'_, GatewaySupervisor>,
+) -> Result<String, String> {
+    info!("[Gateway Token] 轮换 Gateway Token...");
+    gateway_token::rotate(&supervisor)?;
+    get_dashboard_url().await
+}
+
+/// 获取当前档案 Gateway Token 的签发与过期信息
+#[command]
+pub async fn get_gateway_token_info() -> Result<GatewayTokenInfo, String> {
+    Ok(gateway_token::info(&settings::active_profile()))
+}
+
+/// 设置当前档案 Gateway Token 的有效期（小时），None 表示不过期
+#[command]
+pub async fn set_gateway_token_ttl(hours: Option<u32>) -> Result<String, String> {
+    info!("[Gateway Token] 设置有效期: {:?} 小时", hours);
+
+    if hours == Some(0) {
+        return Err("Token 有效期必须大于 0 小时".to_string());
+    }
+
+    let active = settings::active_profile();
+    settings::update(|manager_settings| {
+        if let Some(profile) = manager_settings
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == active.name)
+        {
+            profile.token_ttl_hours = hours;
+        }
+        Ok(())
+    })?;
+
+    Ok(match hours {
+        Some(h) => format!("Token 有效期已设置为 {} 小时", h),
+        None => "Token 已设置为不过期".to_string(),
+    })
 }
 
 /// 获取 Dashboard URL（带 token）
@@ -170,12 +425,44 @@ pub async fn get_dashboard_url() -> Result<String, String> {
     info!("[Dashboard URL] 获取 Dashboard URL...");
     
     let token = get_or_create_gateway_token().await?;
-    let url = format!("http://localhost:18789?token={}", token);
+    let url = format!("http://localhost:{}?token={}", platform::get_gateway_port(), token);
     
     info!("[Dashboard URL] ✓ URL: {}...", &url[..50.min(url.len())]);
     Ok(url)
 }
 
+/// 获取当前生效的 Gateway 端口
+#[command]
+pub async fn get_gateway_port() -> Result<u16, String> {
+    Ok(platform::get_gateway_port())
+}
+
+/// 设置当前档案的 Gateway 端口覆盖（None 表示使用 openclaw.json 中的 gateway.port）
+#[command]
+pub async fn set_gateway_port(port: Option<u16>) -> Result<String, String> {
+    info!("[Gateway 端口] 设置端口覆盖: {:?}", port);
+    
+    if port == Some(0) {
+        return Err("端口无效: 0".to_string());
+    }
+    
+    let active = settings::active_profile();
+    settings::update(|manager_settings| {
+        if let Some(profile) = manager_settings
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == active.name)
+        {
+            profile.gateway_port = port;
+        }
+        Ok(())
+    })?;
+    
+    let effective = platform::get_gateway_port();
+    info!("[Gateway 端口] ✓ 当前生效端口: {}", effective);
+    Ok(format!("Gateway 端口已设置为 {}，重启服务后生效", effective))
+}
+
 // ============ AI 配置相关命令 ============
 
 /// 获取官方 Provider 列表（预设模板）
@@ -200,6 +487,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(200000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
                 SuggestedModel {
                     id: "claude-sonnet-4-5-20250929".to_string(),
@@ -208,6 +496,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(200000),
                     max_tokens: Some(8192),
                     recommended: false,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -227,6 +516,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(4096),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
                 SuggestedModel {
                     id: "gpt-4o-mini".to_string(),
@@ -235,6 +525,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(4096),
                     recommended: false,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -254,6 +545,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(200000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
                 SuggestedModel {
                     id: "moonshot-v1-128k".to_string(),
@@ -262,6 +554,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: false,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -281,6 +574,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
                 SuggestedModel {
                     id: "qwen-plus".to_string(),
@@ -289,6 +583,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: false,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -308,6 +603,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
                 SuggestedModel {
                     id: "deepseek-reasoner".to_string(),
@@ -316,6 +612,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: false,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -335,6 +632,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -354,6 +652,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(200000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -373,6 +672,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(128000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -392,6 +692,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(200000),
                     max_tokens: Some(8192),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -411,6 +712,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
                     context_window: Some(8192),
                     max_tokens: Some(4096),
                     recommended: true,
+                    capabilities: Vec::new(),
                 },
             ],
         },
@@ -431,106 +733,64 @@ pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
     let config_path = platform::get_config_file_path();
     info!("[AI 配置] 配置文件路径: {}", config_path);
 
-    let config = load_openclaw_config()?;
-    debug!("[AI 配置] 配置内容: {}", serde_json::to_string_pretty(&config).unwrap_or_default());
+    let (config, revision) = config_store::load_typed_with_revision()?;
 
     // 解析主模型
-    let primary_model = config
-        .pointer("/agents/defaults/model/primary")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    let primary_model = config.agents.defaults.model.primary.clone();
     info!("[AI 配置] 主模型: {:?}", primary_model);
+    let fallback_models = config.agents.defaults.model.fallbacks.clone();
 
     // 解析可用模型列表
-    let available_models: Vec<String> = config
-        .pointer("/agents/defaults/models")
-        .and_then(|v| v.as_object())
-        .map(|obj| obj.keys().cloned().collect())
-        .unwrap_or_default();
+    let available_models: Vec<String> = config.agents.defaults.models.keys().cloned().collect();
     info!("[AI 配置] 可用模型数: {}", available_models.len());
 
     // 解析已配置的 Provider
     let mut configured_providers: Vec<ConfiguredProvider> = Vec::new();
-
-    let providers_value = config.pointer("/models/providers");
-    info!("[AI 配置] providers 节点存在: {}", providers_value.is_some());
-
-    if let Some(providers) = providers_value.and_then(|v| v.as_object()) {
-        info!("[AI 配置] 找到 {} 个 Provider", providers.len());
-        
-        for (provider_name, provider_config) in providers {
-            info!("[AI 配置] 解析 Provider: {}", provider_name);
-            
-            let base_url = provider_config
-                .get("baseUrl")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-
-            let api_key = provider_config
-                .get("apiKey")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-
-            let api_key_masked = api_key.as_ref().map(|key| {
-                if key.len() > 8 {
-                    format!("{}...{}", &key[..4], &key[key.len() - 4..])
-                } else {
-                    "****".to_string()
+    info!("[AI 配置] 找到 {} 个 Provider", config.models.providers.len());
+
+    let profile = settings::active_profile();
+    for (provider_name, provider_config) in &config.models.providers {
+        info!("[AI 配置] 解析 Provider: {}", provider_name);
+
+        // 密钥引用从密钥库解析后再脱敏
+        let api_key = provider_config
+            .api_key
+            .as_deref()
+            .and_then(|key| secrets::resolve(&profile, key));
+        let api_key_masked = api_key.as_deref().map(secrets::mask);
+
+        // 解析模型列表
+        let models: Vec<ConfiguredModel> = provider_config
+            .models
+            .iter()
+            .filter(|m| !m.id.is_empty())
+            .map(|m| {
+                let full_id = format!("{}/{}", provider_name, m.id);
+                let is_primary = primary_model.as_ref() == Some(&full_id);
+
+                debug!("[AI 配置] 解析模型: {} (is_primary: {})", full_id, is_primary);
+
+                ConfiguredModel {
+                    full_id,
+                    id: m.id.clone(),
+                    name: if m.name.is_empty() { m.id.clone() } else { m.name.clone() },
+                    api_type: m.api.clone(),
+                    context_window: m.context_window,
+                    max_tokens: m.max_tokens,
+                    is_primary,
                 }
-            });
-
-            // 解析模型列表
-            let models_array = provider_config.get("models").and_then(|v| v.as_array());
-            info!("[AI 配置] Provider {} 的 models 数组: {:?}", provider_name, models_array.map(|a| a.len()));
-            
-            let models: Vec<ConfiguredModel> = models_array
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|m| {
-                            let id = m.get("id")?.as_str()?.to_string();
-                            let name = m
-                                .get("name")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or(&id)
-                                .to_string();
-                            let full_id = format!("{}/{}", provider_name, id);
-                            let is_primary = primary_model.as_ref() == Some(&full_id);
-
-                            info!("[AI 配置] 解析模型: {} (is_primary: {})", full_id, is_primary);
-
-                            Some(ConfiguredModel {
-                                full_id,
-                                id,
-                                name,
-                                api_type: m.get("api").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                                context_window: m
-                                    .get("contextWindow")
-                                    .and_then(|v| v.as_u64())
-                                    .map(|n| n as u32),
-                                max_tokens: m
-                                    .get("maxTokens")
-                                    .and_then(|v| v.as_u64())
-                                    .map(|n| n as u32),
-                                is_primary,
-                            })
-                        })
-                        .collect()
-                })
-                .unwrap_or_default();
+            })
+            .collect();
 
-            info!("[AI 配置] Provider {} 解析完成: {} 个模型", provider_name, models.len());
+        info!("[AI 配置] Provider {} 解析完成: {} 个模型", provider_name, models.len());
 
-            configured_providers.push(ConfiguredProvider {
-                name: provider_name.clone(),
-                base_url,
-                api_key_masked,
-                has_api_key: api_key.is_some(),
-                models,
-            });
-        }
-    } else {
-        info!("[AI 配置] 未找到 providers 配置或格式不正确");
+        configured_providers.push(ConfiguredProvider {
+            name: provider_name.clone(),
+            base_url: provider_config.base_url.clone(),
+            api_key_masked,
+            has_api_key: api_key.is_some(),
+            models,
+        });
     }
 
     info!(
@@ -542,8 +802,10 @@ pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
 
     Ok(AIConfigOverview {
         primary_model,
+        fallback_models,
         configured_providers,
         available_models,
+        revision,
     })
 }
 
@@ -555,6 +817,7 @@ pub async fn save_provider(
     api_key: Option<String>,
     api_type: String,
     models: Vec<ModelConfig>,
+    expected_revision: Option<String>,
 ) -> Result<String, String> {
     info!(
         "[保存 Provider] 保存 Provider: {} ({} 个模型)",
@@ -562,115 +825,79 @@ pub async fn save_provider(
         models.len()
     );
 
-    let mut config = load_openclaw_config()?;
+    let (mut config, revision) =
 
-    // 确保路径存在
-    if config.get("models").is_none() {
-        config["models"] = json!({});
-    }
-    if config["models"].get("providers").is_none() {
-        config["models"]["providers"] = json!({});
-    }
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
-    }
-    if config["agents"].get("defaults").is_none() {
-        config["agents"]["defaults"] = json!({});
-    }
-    if config["agents"]["defaults"].get("models").is_none() {
-        config["agents"]["defaults"]["models"] = json!({});
-    }
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
 
-    // 构建模型配置
-    let models_json: Vec<Value> = models
-        .iter()
-        .map(|m| {
-            let mut model_obj = json!({
-                "id": m.id,
-                "name": m.name,
-                "api": m.api.clone().unwrap_or(api_type.clone()),
-                "input": if m.input.is_empty() { vec!["text".to_string()] } else { m.input.clone() },
-            });
+    // 在原有 Provider 基础上更新，保留未建模的字段
+    let mut provider = config
+        .models
+        .providers
+        .remove(&provider_name)
+        .unwrap_or_default();
 
-            if let Some(cw) = m.context_window {
-                model_obj["contextWindow"] = json!(cw);
+    // 构建模型配置：补全默认值，并沿用同 id 旧模型中未建模的字段
+    let previous_models = std::mem::take(&mut provider.models);
+    provider.models = models
+        .into_iter()
+        .map(|mut m| {
+            if m.api.is_none() {
+                m.api = Some(api_type.clone());
             }
-            if let Some(mt) = m.max_tokens {
-                model_obj["maxTokens"] = json!(mt);
+            if m.input.is_empty() {
+                m.input = vec!["text".to_string()];
             }
-            if let Some(r) = m.reasoning {
-                model_obj["reasoning"] = json!(r);
+            if m.cost.is_none() {
+                m.cost = Some(ModelCostConfig::default());
             }
-            if let Some(cost) = &m.cost {
-                model_obj["cost"] = json!({
-                    "input": cost.input,
-                    "output": cost.output,
-                    "cacheRead": cost.cache_read,
-                    "cacheWrite": cost.cache_write,
-                });
-            } else {
-                model_obj["cost"] = json!({
-                    "input": 0,
-                    "output": 0,
-                    "cacheRead": 0,
-                    "cacheWrite": 0,
-                });
+            if m.extra.is_empty() {
+                if let Some(old) = previous_models.iter().find(|old| old.id == m.id) {
+                    m.extra = old.extra.clone();
+                }
             }
-
-            model_obj
+            m
         })
         .collect();
-
-    // 构建 Provider 配置
-    let mut provider_config = json!({
-        "baseUrl": base_url,
-        "models": models_json,
-    });
-
-    // 处理 API Key：如果传入了新的非空 key，使用新的；否则保留原有的
-    if let Some(key) = api_key {
-        if !key.is_empty() {
-            // 使用新传入的 API Key
-            provider_config["apiKey"] = json!(key);
-            info!("[保存 Provider] 使用新的 API Key");
-        } else {
-            // 空字符串表示不更改，尝试保留原有的 API Key
-            if let Some(existing_key) = config
-                .pointer(&format!("/models/providers/{}/apiKey", provider_name))
-                .and_then(|v| v.as_str())
-            {
-                provider_config["apiKey"] = json!(existing_key);
+    provider.base_url = base_url;
+
+    // 处理 API Key：如果传入了新的非空 key，存入密钥库并在配置中保存引用；否则保留原有的
+    match api_key.filter(|key| !key.is_empty()) {
+        Some(key) => {
+            let secret_name =
+                secrets::provider_secret_name_for(
+                    &config.models.providers,
+                    &provider_name,
+                    provider.api_key.as_deref(),
+                )?;
+            secrets::backend(&settings::active_profile()).set(&secret_name, &key)?;
+            provider.api_key = Some(secrets::reference(&secret_name));
+            info!("[保存 Provider] 使用新的 API Key（已保存到密钥库: {}）", secret_name);
+        }
+        None => {
+            if provider.api_key.is_some() {
                 info!("[保存 Provider] 保留原有的 API Key");
             }
         }
-    } else {
-        // None 表示不更改，尝试保留原有的 API Key
-        if let Some(existing_key) = config
-            .pointer(&format!("/models/providers/{}/apiKey", provider_name))
-            .and_then(|v| v.as_str())
-        {
-            provider_config["apiKey"] = json!(existing_key);
-            info!("[保存 Provider] 保留原有的 API Key");
-        }
     }
 
-    // 保存 Provider 配置
-    config["models"]["providers"][&provider_name] = provider_config;
-
-    // 将模型添加到 agents.defaults.models
-    for model in &models {
+    // 将模型添加到 agents.defaults.models（已有的条目保持不变）
+    for model in &provider.models {
         let full_id = format!("{}/{}", provider_name, model.id);
-        config["agents"]["defaults"]["models"][&full_id] = json!({});
+        config
+            .agents
+            .defaults
+            .models
+            .entry(full_id)
+            .or_insert_with(|| json!({}));
     }
 
+    // 保存 Provider 配置
+    config.models.providers.insert(provider_name.clone(), provider);
+
     // 更新元数据
-    let now = chrono::Utc::now().to_rfc3339();
-    if config.get("meta").is_none() {
-        config["meta"] = json!({});
-    }
-    config["meta"]["lastTouchedAt"] = json!(now);
+    config.meta.last_touched_at = Some(chrono::Utc::now().to_rfc3339());
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, &revision)?;
     info!("[保存 Provider] ✓ Provider {} 保存成功", provider_name);
 
     Ok(format!("Provider {} 已保存", provider_name))
@@ -678,46 +905,53 @@ pub async fn save_provider(
 
 /// 删除 Provider
 #[command]
-pub async fn delete_provider(provider_name: String) -> Result<String, String> {
+pub async fn delete_provider(
+    provider_name: String,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
     info!("[删除 Provider] 删除 Provider: {}", provider_name);
 
-    let mut config = load_openclaw_config()?;
+    let (mut config, revision) =
+
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
 
     // 删除 Provider 配置
-    if let Some(providers) = config
-        .pointer_mut("/models/providers")
-        .and_then(|v| v.as_object_mut())
-    {
-        providers.remove(&provider_name);
-    }
+    let removed = config.models.providers.remove(&provider_name);
 
     // 删除相关模型
-    if let Some(models) = config
-        .pointer_mut("/agents/defaults/models")
-        .and_then(|v| v.as_object_mut())
-    {
-        let keys_to_remove: Vec<String> = models
-            .keys()
-            .filter(|k| k.starts_with(&format!("{}/", provider_name)))
-            .cloned()
-            .collect();
-
-        for key in keys_to_remove {
-            models.remove(&key);
-        }
+    let prefix = format!("{}/", provider_name);
+    config
+        .agents
+        .defaults
+        .models
+        .retain(|key, _| !key.starts_with(&prefix));
+
+    // 如果主模型属于该 Provider，清除主模型；同时从备用模型中移除
+    let model = &mut config.agents.defaults.model;
+    if model.primary.as_deref().is_some_and(|p| p.starts_with(&prefix)) {
+        model.primary = None;
     }
-
-    // 如果主模型属于该 Provider，清除主模型
-    if let Some(primary) = config
-        .pointer("/agents/defaults/model/primary")
-        .and_then(|v| v.as_str())
-    {
-        if primary.starts_with(&format!("{}/", provider_name)) {
-            config["agents"]["defaults"]["model"]["primary"] = json!(null);
+    model.fallbacks.retain(|m| !m.starts_with(&prefix));
+
+    save_openclaw_config(&config, &revision)?;
+
+    // 清理不再被任何 Provider 引用的密钥
+    let secret_name = removed
+        .as_ref()
+        .and_then(|p| p.api_key.as_deref())
+        .and_then(secrets::parse_reference);
+    if let Some(name) = secret_name {
+        let still_used = config
+            .models
+            .providers
+            .values()
+            .any(|p| p.api_key.as_deref().and_then(secrets::parse_reference) == Some(name));
+        if !still_used {
+            if let Err(e) = secrets::backend(&settings::active_profile()).delete(name) {
+                warn!("[删除 Provider] 删除密钥 {} 失败: {}", name, e);
+            }
         }
     }
-
-    save_openclaw_config(&config)?;
     info!("[删除 Provider] ✓ Provider {} 已删除", provider_name);
 
     Ok(format!("Provider {} 已删除", provider_name))
@@ -725,53 +959,95 @@ pub async fn delete_provider(provider_name: String) -> Result<String, String> {
 
 /// 设置主模型
 #[command]
-pub async fn set_primary_model(model_id: String) -> Result<String, String> {
+pub async fn set_primary_model(
+    model_id: String,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
     info!("[设置主模型] 设置主模型: {}", model_id);
 
-    let mut config = load_openclaw_config()?;
+    let (mut config, revision) =
 
-    // 确保路径存在
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
-    }
-    if config["agents"].get("defaults").is_none() {
-        config["agents"]["defaults"] = json!({});
-    }
-    if config["agents"]["defaults"].get("model").is_none() {
-        config["agents"]["defaults"]["model"] = json!({});
-    }
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
 
-    // 设置主模型
-    config["agents"]["defaults"]["model"]["primary"] = json!(model_id);
+    // 设置主模型，并从备用模型中移除
+    let model = &mut config.agents.defaults.model;
+    model.primary = Some(model_id.clone());
+    model.fallbacks.retain(|m| *m != model_id);
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, &revision)?;
     info!("[设置主模型] ✓ 主模型已设置为: {}", model_id);
 
     Ok(format!("主模型已设置为 {}", model_id))
 }
 
+/// 设置备用模型（按顺序），传入空列表表示清除
+#[command]
+pub async fn set_fallback_models(
+    model_ids: Vec<String>,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
+    info!("[设置备用模型] 设置备用模型: {:?}", model_ids);
+
+    let (mut config, revision) =
+
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
+
+    let primary = config.agents.defaults.model.primary.as_deref();
+    let mut fallbacks: Vec<String> = Vec::new();
+    for model_id in model_ids.iter().map(|m| m.trim()) {
+        if !model_exists(&config, model_id) {
+            return Err(format!("模型 {} 不在 models.providers 中", model_id));
+        }
+        if primary == Some(model_id) {
+            return Err(format!("模型 {} 已是主模型，不能作为备用模型", model_id));
+        }
+        if fallbacks.iter().any(|m| m == model_id) {
+            return Err(format!("备用模型重复: {}", model_id));
+        }
+        fallbacks.push(model_id.to_string());
+    }
+
+    let count = fallbacks.len();
+    config.agents.defaults.model.fallbacks = fallbacks;
+
+    save_openclaw_config(&config, &revision)?;
+    info!("[设置备用模型] ✓ 已设置 {} 个备用模型", count);
+
+    Ok(format!("已设置 {} 个备用模型", count))
+}
+
+/// 模型（provider/model-id）是否在 models.providers 中
+pub fn model_exists(config: &OpenClawConfig, full_id: &str) -> bool {
+    full_id.split_once('/').is_some_and(|(provider, model)| {
+        config
+            .models
+            .providers
+            .get(provider)
+            .is_some_and(|p| p.models.iter().any(|m| m.id == model))
+    })
+}
+
 /// 添加模型到可用列表
 #[command]
-pub async fn add_available_model(model_id: String) -> Result<String, String> {
+pub async fn add_available_model(
+    model_id: String,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
     info!("[添加模型] 添加模型到可用列表: {}", model_id);
 
-    let mut config = load_openclaw_config()?;
+    let (mut config, revision) =
 
-    // 确保路径存在
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
-    }
-    if config["agents"].get("defaults").is_none() {
-        config["agents"]["defaults"] = json!({});
-    }
-    if config["agents"]["defaults"].get("models").is_none() {
-        config["agents"]["defaults"]["models"] = json!({});
-    }
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
 
-    // 添加模型
-    config["agents"]["defaults"]["models"][&model_id] = json!({});
+    // 添加模型（已存在时保留原有设置）
+    config
+        .agents
+        .defaults
+        .models
+        .entry(model_id.clone())
+        .or_insert_with(|| json!({}));
 
-    save_openclaw_config(&config)?;
+    save_openclaw_config(&config, &revision)?;
     info!("[添加模型] ✓ 模型 {} 已添加", model_id);
 
     Ok(format!("模型 {} 已添加", model_id))
@@ -779,19 +1055,19 @@ pub async fn add_available_model(model_id: String) -> Result<String, String> {
 
 /// 从可用列表移除模型
 #[command]
-pub async fn remove_available_model(model_id: String) -> Result<String, String> {
+pub async fn remove_available_model(
+    model_id: String,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
     info!("[移除模型] 从可用列表移除模型: {}", model_id);
 
-    let mut config = load_openclaw_config()?;
+    let (mut config, revision) =
 
-    if let Some(models) = config
-        .pointer_mut("/agents/defaults/models")
-        .and_then(|v| v.as_object_mut())
-    {
-        models.remove(&model_id);
-    }
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
 
-    save_openclaw_config(&config)?;
+    config.agents.defaults.models.remove(&model_id);
+
+    save_openclaw_config(&config, &revision)?;
     info!("[移除模型] ✓ 模型 {} 已移除", model_id);
 
     Ok(format!("模型 {} 已移除", model_id))
@@ -831,164 +1107,214 @@ pub async fn get_ai_providers() -> Result<Vec<crate::models::AIProviderOption>,
 
 // ============ 渠道配置 ============
 
+/// 获取所有渠道描述（内置 + 用户自定义）
+#[command]
+pub async fn get_channel_descriptors() -> Result<Vec<ChannelDescriptor>, String> {
+    Ok(channel_registry::all())
+}
+
+/// 按渠道 schema 校验渠道配置，返回字段级问题（path 为字段名）；只有 error 级别的问题会阻止保存
+#[command]
+pub async fn validate_channel_config(mut channel: ChannelConfig) -> Result<Vec<ConfigIssue>, String> {
+    let descriptor = channel_registry::require(&channel.channel_type)?;
+    descriptor.migrate_renamed_fields(&mut channel.config);
+    Ok(channel_schema::validate(&descriptor, &channel.config))
+}
+
+/// 账号配置节点：默认账号为 channels.<渠道> 本身，其他账号为 accounts.<账号>
+fn account_node<'a>(channel: &'a Value, account: &str) -> Option<&'a Value> {
+    if account == DEFAULT_CHANNEL_ACCOUNT {
+        Some(channel)
+    } else {
+        channel.get("accounts").and_then(|a| a.get(account))
+    }
+}
+
+/// 读取 enabled 字段（未设置时为 None）
+fn enabled_flag(node: Option<&Value>) -> Option<bool> {
+    node.and_then(|n| n.get("enabled")).and_then(|v| v.as_bool())
+}
+
+/// 读取账号配置（默认账号不含 accounts），返回 (enabled 字段, 配置字段)
+fn read_account_config(channel: &Value, account: &str) -> (Option<bool>, HashMap<String, Value>) {
+    let node = account_node(channel, account);
+    let Some(obj) = node.and_then(|n| n.as_object()) else {
+        return (None, HashMap::new());
+    };
+    let fields = obj
+        .iter()
+        .filter(|(k, _)| *k != "enabled" && *k != "accounts") // 排除 enabled 和子账号
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    (enabled_flag(node), fields)
+}
+
+/// 渠道下的其他账号名称（不含默认账号）
+fn named_accounts(channel: &Value) -> Vec<String> {
+    channel
+        .get("accounts")
+        .and_then(|a| a.as_object())
+        .map(|accounts| accounts.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
 /// 获取渠道配置 - 从 openclaw.json 和 env 文件读取
+/// 每个渠道返回默认账号，另外为 channels.<渠道>.accounts 中的每个账号各返回一项
 #[command]
-pub async fn get_channels_config() -> Result<Vec<ChannelConfig>, String> {
+pub async fn get_channels_config() -> Result<ChannelsOverview, String> {
     info!("[渠道配置] 获取渠道配置列表...");
     
-    let config = load_openclaw_config()?;
-    let channels_obj = config.get("channels").cloned().unwrap_or(json!({}));
+    let (config, revision) = config_store::load_typed_with_revision()?;
+    let channels_obj = config.channels;
     let env_path = platform::get_env_file_path();
     debug!("[渠道配置] 环境文件路径: {}", env_path);
     
     let mut channels = Vec::new();
     
-    // 支持的渠道类型列表及其测试字段
-    let channel_types = vec![
-        ("telegram", "telegram", vec!["userId"]),
-        ("discord", "discord", vec!["testChannelId"]),
-        ("slack", "slack", vec!["testChannelId"]),
-        ("feishu", "feishu", vec!["testChatId"]),
-        ("whatsapp", "whatsapp", vec![]),
-        ("imessage", "imessage", vec![]),
-        ("wechat", "wechat", vec![]),
-        ("dingtalk", "dingtalk", vec![]),
-    ];
-    
-    for (channel_id, channel_type, test_fields) in channel_types {
-        let channel_config = channels_obj.get(channel_id);
+    // 渠道列表及其测试字段来自渠道注册表
+    for descriptor in channel_registry::all() {
+        let channel_id = descriptor.id.as_str();
+        let channel_config = channels_obj.get(channel_id).cloned().unwrap_or(Value::Null);
         
-        let enabled = channel_config
-            .and_then(|c| c.get("enabled"))
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let mut accounts = vec![DEFAULT_CHANNEL_ACCOUNT.to_string()];
+        accounts.extend(named_accounts(&channel_config));
         
-        // 将渠道配置转换为 HashMap
-        let mut config_map: HashMap<String, Value> = if let Some(cfg) = channel_config {
-            if let Some(obj) = cfg.as_object() {
-                obj.iter()
-                    .filter(|(k, _)| *k != "enabled") // 排除 enabled 字段
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect()
-            } else {
-                HashMap::new()
+        for account in accounts {
+            let (enabled, mut config_map) = read_account_config(&channel_config, &account);
+            if descriptor.migrate_renamed_fields(&mut config_map) {
+                debug!("[渠道配置] {} 使用了旧字段名，保存时迁移", channel_registry::config_id(channel_id, &account));
             }
-        } else {
-            HashMap::new()
-        };
-        
-        // 从 env 文件读取测试字段
-        for field in test_fields {
-            let env_key = format!(
-                "OPENCLAW_{}_{}",
-                channel_id.to_uppercase(),
-                field.to_uppercase()
-            );
-            if let Some(value) = file::read_env_value(&env_path, &env_key) {
-                config_map.insert(field.to_string(), json!(value));
+            // 账号条目没有 enabled 时跟随 OpenClaw 的默认值（启用）；渠道停用时所有账号都停用
+            let is_default = account == DEFAULT_CHANNEL_ACCOUNT;
+            let disabled = enabled == Some(false) || enabled_flag(Some(&channel_config)) == Some(false);
+            let enabled = enabled.unwrap_or(!is_default);
+            
+            // 从 env 文件读取测试字段
+            for field in descriptor.test_only_fields() {
+                let env_key = descriptor.test_env_key(&account, field);
+                if let Some(value) = file::read_env_value(&env_path, &env_key) {
+                    config_map.insert(field.to_string(), json!(value));
+                }
             }
+            
+            // 判断是否已配置（有任何非空配置项）
+            let has_config = !config_map.is_empty() || enabled;
+            
+            channels.push(ChannelConfig {
+                id: channel_registry::config_id(channel_id, &account),
+                channel_type: channel_id.to_string(),
+                account,
+                enabled: has_config,
+                disabled,
+                config: config_map,
+            });
         }
-        
-        // 判断是否已配置（有任何非空配置项）
-        let has_config = !config_map.is_empty() || enabled;
-        
-        channels.push(ChannelConfig {
-            id: channel_id.to_string(),
-            channel_type: channel_type.to_string(),
-            enabled: has_config,
-            config: config_map,
-        });
     }
     
     info!("[渠道配置] ✓ 返回 {} 个渠道配置", channels.len());
     for ch in &channels {
         debug!("[渠道配置] - {}: enabled={}", ch.id, ch.enabled);
     }
-    Ok(channels)
+    Ok(ChannelsOverview { channels, revision })
 }
 
 /// 保存渠道配置 - 保存到 openclaw.json
+/// 默认账号写入 channels.<渠道>，其他账号写入 channels.<渠道>.accounts.<账号>
 #[command]
-pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, String> {
-    info!(
-        "[保存渠道配置] 保存渠道配置: {} ({})",
-        channel.id, channel.channel_type
-    );
+pub async fn save_channel_config(
+    mut channel: ChannelConfig,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
+    let label = channel_registry::account_label(&channel.channel_type, &channel.account);
+    info!("[保存渠道配置] 保存渠道配置: {}", label);
     
-    let mut config = load_openclaw_config()?;
-    let env_path = platform::get_env_file_path();
-    debug!("[保存渠道配置] 环境文件路径: {}", env_path);
+    let descriptor = channel_registry::require(&channel.channel_type)?;
+    channel_registry::check_account_name(&channel.account)?;
     
-    // 确保 channels 对象存在
-    if config.get("channels").is_none() {
-        config["channels"] = json!({});
-    }
+    // 旧字段名（如 Discord 的 botToken）迁移为新字段名
+    descriptor.migrate_renamed_fields(&mut channel.config);
     
-    // 确保 plugins 对象存在
-    if config.get("plugins").is_none() {
-        config["plugins"] = json!({
-            "allow": [],
-            "entries": {}
-        });
+    // 按渠道 schema 校验，字段拼写错误或格式不对时拒绝保存；未知字段只记录警告
+    let issues = channel_schema::validate(&descriptor, &channel.config);
+    for issue in issues.iter().filter(|issue| issue.severity != "error") {
+        warn!("[保存渠道配置] {}: {}", issue.path, issue.message);
     }
-    if config["plugins"].get("allow").is_none() {
-        config["plugins"]["allow"] = json!([]);
-    }
-    if config["plugins"].get("entries").is_none() {
-        config["plugins"]["entries"] = json!({});
+    if channel_schema::has_errors(&issues) {
+        let details: Vec<String> = issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .map(|issue| format!("{}: {}", issue.path, issue.message))
+            .collect();
+        warn!("[保存渠道配置] ✗ 配置校验未通过: {}", details.join("; "));
+        return Err(format!("{} 配置校验未通过: {}", descriptor.name, details.join("; ")));
     }
     
-    // 这些字段只用于测试，不保存到 openclaw.json，而是保存到 env 文件
-    let test_only_fields = vec!["userId", "testChatId", "testChannelId"];
+    let (mut config, revision) =
+    
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
+    let env_path = platform::get_env_file_path();
+    debug!("[保存渠道配置] 环境文件路径: {}", env_path);
     
-    // 构建渠道配置
-    let mut channel_obj = json!({
-        "enabled": true
+    // 这些字段只用于测试，不保存到 openclaw.json，而是保存到 env 文件（每个账号单独一组变量）
+    let test_only_fields: Vec<&str> = descriptor.test_only_fields().collect();
+    
+    // 构建账号配置；已停用的渠道或账号保存后保持停用
+    let existing = config.channels.remove(&descriptor.id).filter(|c| c.is_object());
+    let channel_enabled = enabled_flag(existing.as_ref()) != Some(false);
+    // 不同账号的测试字段变量名不能相同，否则会互相覆盖
+    if channel.account != DEFAULT_CHANNEL_ACCOUNT {
+        let accounts = existing.as_ref().map(named_accounts).unwrap_or_default();
+        if let Some(other) = descriptor.env_conflicting_account(&channel.account, &accounts) {
+            return Err(format!(
+                "账号名称 {} 与已有账号 {} 冲突（大小写、- 和 _ 视为相同），请换一个名称",
+                channel.account, other
+            ));
+        }
+    }
+    let existing_account = existing.as_ref().and_then(|c| account_node(c, &channel.account));
+    let account_enabled = enabled_flag(existing_account) != Some(false);
+    let mut account_obj = json!({
+        "enabled": account_enabled
     });
     
     // 添加渠道特定配置
     for (key, value) in &channel.config {
         if test_only_fields.contains(&key.as_str()) {
             // 保存到 env 文件
-            let env_key = format!(
-                "OPENCLAW_{}_{}",
-                channel.id.to_uppercase(),
-                key.to_uppercase()
-            );
+            let env_key = descriptor.test_env_key(&channel.account, key);
             if let Some(val_str) = value.as_str() {
                 let _ = file::set_env_value(&env_path, &env_key, val_str);
             }
         } else {
             // 保存到 openclaw.json
-            channel_obj[key] = value.clone();
+            account_obj[key] = value.clone();
         }
     }
     
-    // 更新 channels 配置
-    config["channels"][&channel.id] = channel_obj;
-    
-    // 更新 plugins.allow 数组 - 确保渠道在白名单中
-    if let Some(allow_arr) = config["plugins"]["allow"].as_array_mut() {
-        let channel_id_val = json!(&channel.id);
-        if !allow_arr.contains(&channel_id_val) {
-            allow_arr.push(channel_id_val);
+    // 更新 channels 配置，保留同一渠道下的其他账号
+    let channel_id = descriptor.id.clone();
+    let channel_obj = if channel.account == DEFAULT_CHANNEL_ACCOUNT {
+        if let Some(accounts) = existing.as_ref().and_then(|c| c.get("accounts")) {
+            account_obj["accounts"] = accounts.clone();
         }
-    }
+        account_obj
+    } else {
+        let mut channel_obj = existing.unwrap_or_else(|| json!({}));
+        channel_obj["enabled"] = json!(channel_enabled);
+        channel_obj["accounts"][channel.account.as_str()] = account_obj;
+        channel_obj
+    };
+    config.channels.insert(channel_id.clone(), channel_obj);
     
-    // 更新 plugins.entries - 确保插件已启用
-    config["plugins"]["entries"][&channel.id] = json!({
-        "enabled": true
-    });
+    // 更新 plugins.allow 和 plugins.entries - 插件启用状态跟随渠道
+    plugins::set_enabled(&mut config, &channel_id, channel_enabled);
     
     // 保存配置
     info!("[保存渠道配置] 写入配置文件...");
-    match save_openclaw_config(&config) {
+    match save_openclaw_config(&config, &revision) {
         Ok(_) => {
-            info!(
-                "[保存渠道配置] ✓ {} 配置保存成功",
-                channel.channel_type
-            );
-            Ok(format!("{} 配置已保存", channel.channel_type))
+            info!("[保存渠道配置] ✓ {} 配置保存成功", label);
+            Ok(format!("{} 配置已保存", label))
         }
         Err(e) => {
             error!("[保存渠道配置] ✗ 保存失败: {}", e);
@@ -997,47 +1323,64 @@ pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, Strin
     }
 }
 
-/// 清空渠道配置 - 从 openclaw.json 中删除指定渠道的配置
+/// 清空渠道配置 - 从 openclaw.json 中删除指定渠道账号的配置
+/// 未指定账号时清空默认账号；渠道下没有其他账号时连同插件条目一起删除
 #[command]
-pub async fn clear_channel_config(channel_id: String) -> Result<String, String> {
-    info!("[清空渠道配置] 清空渠道配置: {}", channel_id);
+pub async fn clear_channel_config(
+    channel_id: String,
+    account: Option<String>,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
+    let account = account.unwrap_or_else(|| DEFAULT_CHANNEL_ACCOUNT.to_string());
+    let descriptor = channel_registry::require(&channel_id)?;
+    let channel_id = descriptor.id.clone();
+    let label = channel_registry::account_label(&channel_id, &account);
+    info!("[清空渠道配置] 清空渠道配置: {}", label);
+    
+    let (mut config, revision) =
     
-    let mut config = load_openclaw_config()?;
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
     let env_path = platform::get_env_file_path();
     
-    // 从 channels 对象中删除该渠道
-    if let Some(channels) = config.get_mut("channels").and_then(|v| v.as_object_mut()) {
-        channels.remove(&channel_id);
-        info!("[清空渠道配置] 已从 channels 中删除: {}", channel_id);
+    let mut channel_obj = config.channels.remove(&channel_id).unwrap_or(Value::Null);
+    if let Some(obj) = channel_obj.as_object_mut() {
+        if account == DEFAULT_CHANNEL_ACCOUNT {
+            // 默认账号的字段直接写在渠道下，保留其他账号
+            obj.retain(|k, _| k == "enabled" || k == "accounts");
+        } else if let Some(accounts) = obj.get_mut("accounts").and_then(|a| a.as_object_mut()) {
+            accounts.remove(&account);
+            if accounts.is_empty() {
+                obj.remove("accounts");
+            }
+        }
     }
     
-    // 从 plugins.allow 数组中删除
-    if let Some(allow_arr) = config.pointer_mut("/plugins/allow").and_then(|v| v.as_array_mut()) {
-        allow_arr.retain(|v| v.as_str() != Some(&channel_id));
-        info!("[清空渠道配置] 已从 plugins.allow 中删除: {}", channel_id);
+    // 默认账号和其他账号都没有配置时，删除整个渠道
+    let has_default_fields = channel_obj
+        .as_object()
+        .is_some_and(|obj| obj.keys().any(|k| k != "enabled" && k != "accounts"));
+    if has_default_fields || !named_accounts(&channel_obj).is_empty() {
+        config.channels.insert(channel_id.clone(), channel_obj);
+        info!("[清空渠道配置] 已删除 {}，保留该渠道的其他账号", label);
+    } else {
+        // 从 channels 对象中删除该渠道
+        info!("[清空渠道配置] 已从 channels 中删除: {}", channel_id);
+        
+        // 从 plugins.allow 和 plugins.entries 中删除（保留安装记录）
+        plugins::remove_entry(&mut config, &channel_id);
+        info!("[清空渠道配置] 已从 plugins.allow 和 plugins.entries 中删除: {}", channel_id);
     }
     
-    // 从 plugins.entries 中删除
-    if let Some(entries) = config.pointer_mut("/plugins/entries").and_then(|v| v.as_object_mut()) {
-        entries.remove(&channel_id);
-        info!("[清空渠道配置] 已从 plugins.entries 中删除: {}", channel_id);
-    }
-    
-    // 清除相关的环境变量
-    let env_prefixes = vec![
-        format!("OPENCLAW_{}_USERID", channel_id.to_uppercase()),
-        format!("OPENCLAW_{}_TESTCHATID", channel_id.to_uppercase()),
-        format!("OPENCLAW_{}_TESTCHANNELID", channel_id.to_uppercase()),
-    ];
-    for env_key in env_prefixes {
-        let _ = file::remove_env_value(&env_path, &env_key);
+    // 清除该账号相关的环境变量
+    for field in descriptor.test_only_fields() {
+        let _ = file::remove_env_value(&env_path, &descriptor.test_env_key(&account, field));
     }
     
     // 保存配置
-    match save_openclaw_config(&config) {
+    match save_openclaw_config(&config, &revision) {
         Ok(_) => {
-            info!("[清空渠道配置] ✓ {} 配置已清空", channel_id);
-            Ok(format!("{} 配置已清空", channel_id))
+            info!("[清空渠道配置] ✓ {} 配置已清空", label);
+            Ok(format!("{} 配置已清空", label))
         }
         Err(e) => {
             error!("[清空渠道配置] ✗ 清空失败: {}", e);
@@ -1046,104 +1389,71 @@ pub async fn clear_channel_config(channel_id: String) -> Result<String, String>
     }
 }
 
-// ============ 飞书插件管理 ============
-
-/// 飞书插件状态
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FeishuPluginStatus {
-    pub installed: bool,
-    pub version: Option<String>,
-    pub plugin_name: Option<String>,
-}
-
-/// 检查飞书插件是否已安装
+/// 启用或停用渠道账号，保留凭据和测试变量
+/// 默认账号切换 channels.<渠道>.enabled 和 plugins.entries.<渠道>.enabled，其他账号切换 accounts.<账号>.enabled；
+/// restart_gateway 为 true 且 Gateway 正在运行时重启使其生效，否则通过 restart_required 提示前端
 #[command]
-pub async fn check_feishu_plugin() -> Result<FeishuPluginStatus, String> {
-    info!("[飞书插件] 检查飞书插件安装状态...");
+pub async fn set_channel_enabled(
+    supervisor: State<'_, GatewaySupervisor>,
+    channel_id: String,
+    enabled: bool,
+    account: Option<String>,
+    restart_gateway: Option<bool>,
+    expected_revision: Option<String>,
+) -> Result<ChannelToggleResult, String> {
+    let account = account.unwrap_or_else(|| DEFAULT_CHANNEL_ACCOUNT.to_string());
+    let channel_id = channel_registry::require(&channel_id)?.id;
+    let label = channel_registry::account_label(&channel_id, &account);
+    let action = if enabled { "启用" } else { "停用" };
+    info!("[渠道开关] {} {}", action, label);
     
-    // 执行 openclaw plugins list 命令
-    match shell::run_openclaw(&["plugins", "list"]) {
-        Ok(output) => {
-            debug!("[飞书插件] plugins list 输出: {}", output);
-            
-            // 查找包含 feishu 的行（不区分大小写）
-            let lines: Vec<&str> = output.lines().collect();
-            let feishu_line = lines.iter().find(|line| {
-                line.to_lowercase().contains("feishu")
-            });
-            
-            if let Some(line) = feishu_line {
-                info!("[飞书插件] ✓ 飞书插件已安装: {}", line);
-                
-                // 尝试解析版本号（通常格式为 "name@version" 或 "name version"）
-                let version = if line.contains('@') {
-                    line.split('@').last().map(|s| s.trim().to_string())
-                } else {
-                    // 尝试匹配版本号模式 (如 0.1.2)
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    parts.iter()
-                        .find(|p| p.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(false))
-                        .map(|s| s.to_string())
-                };
-                
-                Ok(FeishuPluginStatus {
-                    installed: true,
-                    version,
-                    plugin_name: Some(line.trim().to_string()),
-                })
-            } else {
-                info!("[飞书插件] ✗ 飞书插件未安装");
-                Ok(FeishuPluginStatus {
-                    installed: false,
-                    version: None,
-                    plugin_name: None,
-                })
-            }
-        }
-        Err(e) => {
-            warn!("[飞书插件] 检查插件列表失败: {}", e);
-            // 如果命令失败，假设插件未安装
-            Ok(FeishuPluginStatus {
-                installed: false,
-                version: None,
-                plugin_name: None,
-            })
+    let (mut config, revision) =
+    
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
+    let channel_obj = config
+        .channels
+        .get_mut(&channel_id)
+        .filter(|c| c.is_object())
+        .ok_or_else(|| format!("{} 尚未配置", channel_id))?;
+    
+    if account == DEFAULT_CHANNEL_ACCOUNT {
+        channel_obj["enabled"] = json!(enabled);
+    } else {
+        let account_obj = channel_obj
+            .get_mut("accounts")
+            .and_then(|a| a.get_mut(&account))
+            .filter(|a| a.is_object())
+            .ok_or_else(|| format!("{} 尚未配置", label))?;
+        account_obj["enabled"] = json!(enabled);
+        // 启用账号时渠道本身也必须启用
+        if enabled {
+            channel_obj["enabled"] = json!(true);
         }
     }
-}
-
-/// 安装飞书插件
-#[command]
-pub async fn install_feishu_plugin() -> Result<String, String> {
-    info!("[飞书插件] 开始安装飞书插件...");
     
-    // 先检查是否已安装
-    let status = check_feishu_plugin().await?;
-    if status.installed {
-        info!("[飞书插件] 飞书插件已安装，跳过");
-        return Ok(format!("飞书插件已安装: {}", status.plugin_name.unwrap_or_default()));
+    // 插件条目跟随渠道（停用单个账号时不影响插件）
+    if account == DEFAULT_CHANNEL_ACCOUNT || enabled {
+        plugins::set_enabled(&mut config, &channel_id, enabled);
     }
     
-    // 安装飞书插件
-    // 注意：使用 @m1heng-clawd/feishu 包名
-    info!("[飞书插件] 执行 openclaw plugins install @m1heng-clawd/feishu ...");
-    match shell::run_openclaw(&["plugins", "install", "@m1heng-clawd/feishu"]) {
-        Ok(output) => {
-            info!("[飞书插件] 安装输出: {}", output);
-            
-            // 验证安装结果
-            let verify_status = check_feishu_plugin().await?;
-            if verify_status.installed {
-                info!("[飞书插件] ✓ 飞书插件安装成功");
-                Ok(format!("飞书插件安装成功: {}", verify_status.plugin_name.unwrap_or_default()))
-            } else {
-                warn!("[飞书插件] 安装命令执行成功但插件未找到");
-                Err("安装命令执行成功但插件未找到，请检查 openclaw 版本".to_string())
-            }
-        }
-        Err(e) => {
-            error!("[飞书插件] ✗ 安装失败: {}", e);
-            Err(format!("安装飞书插件失败: {}\n\n请手动执行: openclaw plugins install @m1heng-clawd/feishu", e))
-        }
+    if let Err(e) = save_openclaw_config(&config, &revision) {
+        error!("[渠道开关] ✗ 保存失败: {}", e);
+        return Err(e);
     }
+    info!("[渠道开关] ✓ 已{} {}", action, label);
+    
+    let running = gateway::is_running();
+    let restarted = running && restart_gateway.unwrap_or(false);
+    if restarted {
+        info!("[渠道开关] 重启 Gateway 以应用修改...");
+        gateway::restart(&supervisor)?;
+    }
+    
+    Ok(ChannelToggleResult {
+        channel: channel_id,
+        account,
+        enabled,
+        restarted,
+        restart_required: running && !restarted,
+    })
 }
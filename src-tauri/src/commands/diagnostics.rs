@@ -1,85 +1,11 @@
-use crate::models::{AITestResult, ChannelTestResult, DiagnosticResult, SystemInfo};
-use crate::utils::{platform, shell};
+use crate::commands::provider;
+use crate::models::{
+    AITestResult, ChannelTestResult, DiagnosticResult, SystemInfo, DEFAULT_CHANNEL_ACCOUNT,
+};
+use crate::utils::{channel_registry, config_store, platform, plugins, shell};
+use serde_json::json;
 use tauri::command;
-use log::{info, warn, error, debug};
-
-/// 去除 ANSI 转义序列（颜色代码等）
-fn strip_ansi_codes(input: &str) -> String {
-    // 匹配 ANSI 转义序列: ESC[ ... m 或 ESC[ ... 其他控制字符
-    let mut result = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
-    
-    while let Some(c) = chars.next() {
-        if c == '\x1b' {
-            // 跳过 ESC[...m 序列
-            if chars.peek() == Some(&'[') {
-                chars.next(); // 跳过 '['
-                // 跳过直到遇到字母
-                while let Some(&next) = chars.peek() {
-                    chars.next();
-                    if next.is_ascii_alphabetic() {
-                        break;
-                    }
-                }
-            }
-        } else {
-            result.push(c);
-        }
-    }
-    result
-}
-
-/// 从混合输出中提取 JSON 内容
-fn extract_json_from_output(output: &str) -> Option<String> {
-    // 先去除 ANSI 颜色代码
-    let clean_output = strip_ansi_codes(output);
-    
-    // 按行查找 JSON 开始位置
-    let lines: Vec<&str> = clean_output.lines().collect();
-    let mut json_start_line = None;
-    let mut json_end_line = None;
-    
-    // 找到 JSON 开始行：
-    // - 以 { 开头（JSON 对象）
-    // - 或以 [" 或 [数字 开头（真正的 JSON 数组，不是 [plugins] 这样的文本）
-    for (i, line) in lines.iter().enumerate() {
-        let trimmed = line.trim();
-        if trimmed.starts_with('{') {
-            json_start_line = Some(i);
-            break;
-        }
-        // 检查是否是真正的 JSON 数组（以 [" 或 [数字 或 [{ 开头）
-        if trimmed.starts_with('[') && trimmed.len() > 1 {
-            let second_char = trimmed.chars().nth(1).unwrap_or(' ');
-            if second_char == '"' || second_char == '{' || second_char == '[' || second_char.is_ascii_digit() {
-                json_start_line = Some(i);
-                break;
-            }
-        }
-    }
-    
-    // 找到 JSON 结束行（以 } 或 ] 结尾的行，从后往前找）
-    for (i, line) in lines.iter().enumerate().rev() {
-        let trimmed = line.trim();
-        if trimmed == "}" || trimmed == "}," || trimmed.ends_with('}') {
-            json_end_line = Some(i);
-            break;
-        }
-        if trimmed == "]" || trimmed == "]," {
-            json_end_line = Some(i);
-            break;
-        }
-    }
-    
-    match (json_start_line, json_end_line) {
-        (Some(start), Some(end)) if start <= end => {
-            let json_lines: Vec<&str> = lines[start..=end].to_vec();
-            let json_str = json_lines.join("\n");
-            Some(json_str)
-        }
-        _ => None,
-    }
-}
+use log::{info, warn, error};
 
 /// 运行诊断
 #[command]
@@ -172,119 +98,128 @@ pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
 }
 
 /// 测试 AI 连接
+/// 直接向主模型所属的 Provider 发送最小请求，不再通过 openclaw agent 的输出判断
 #[command]
 pub async fn test_ai_connection() -> Result<AITestResult, String> {
     info!("[AI测试] 开始测试 AI 连接...");
-    
-    // 获取当前配置的 provider
-    let start = std::time::Instant::now();
-    
-    // 使用 openclaw 命令测试连接
-    info!("[AI测试] 执行: openclaw agent --local --to +1234567890 --message 回复 OK");
-    let result = shell::run_openclaw(&["agent", "--local", "--to", "+1234567890", "--message", "回复 OK"]);
-    
-    let latency = start.elapsed().as_millis() as u64;
-    info!("[AI测试] 命令执行完成, 耗时: {}ms", latency);
-    
-    match result {
-        Ok(output) => {
-            debug!("[AI测试] 原始输出: {}", output);
-            // 过滤掉警告信息
-            let filtered: String = output
-                .lines()
-                .filter(|l: &&str| !l.contains("ExperimentalWarning"))
-                .collect::<Vec<&str>>()
-                .join("\n");
-            
-            let success = !filtered.to_lowercase().contains("error")
-                && !filtered.contains("401")
-                && !filtered.contains("403");
-            
-            if success {
-                info!("[AI测试] ✓ AI 连接测试成功");
-            } else {
-                warn!("[AI测试] ✗ AI 连接测试失败: {}", filtered);
-            }
-            
-            Ok(AITestResult {
-                success,
-                provider: "current".to_string(),
-                model: "default".to_string(),
-                response: if success { Some(filtered.clone()) } else { None },
-                error: if success { None } else { Some(filtered) },
-                latency_ms: Some(latency),
-            })
-        }
-        Err(e) => Ok(AITestResult {
+
+    let (config, _) = config_store::load_typed_with_revision()?;
+    let Some(primary) = config.agents.defaults.model.primary.clone() else {
+        warn!("[AI测试] ✗ 未设置主模型");
+        return Ok(AITestResult {
             success: false,
-            provider: "current".to_string(),
-            model: "default".to_string(),
+            provider: String::new(),
+            model: String::new(),
             response: None,
-            error: Some(e),
-            latency_ms: Some(latency),
-        }),
+            error: Some("未设置主模型，请先在 AI 配置中选择主模型".to_string()),
+            latency_ms: None,
+        });
+    };
+
+    // 主模型格式为 provider/model，模型 ID 本身可能包含 /
+    let Some((provider_name, model_id)) = primary.split_once('/') else {
+        return Err(format!("主模型格式无效: {}", primary));
+    };
+    info!("[AI测试] 主模型: {}", primary);
+
+    let result = provider::test_configured_model(&config, provider_name, model_id).await?;
+    if result.success {
+        info!("[AI测试] ✓ AI 连接测试成功, 耗时: {}ms", result.total_ms);
+    } else {
+        warn!("[AI测试] ✗ AI 连接测试失败: {:?}", result.error);
     }
+
+    Ok(AITestResult {
+        success: result.success,
+        provider: result.provider,
+        model: result.model,
+        response: result.response,
+        error: result.error,
+        latency_ms: Some(result.total_ms),
+    })
 }
 
-/// 获取渠道测试目标
-fn get_channel_test_target(channel_type: &str) -> Option<String> {
-    let env_path = platform::get_env_file_path();
-    
-    // 根据渠道类型获取测试目标的环境变量
-    let env_key = match channel_type.to_lowercase().as_str() {
-        "telegram" => "OPENCLAW_TELEGRAM_USERID",
-        "discord" => "OPENCLAW_DISCORD_TESTCHANNELID",
-        "slack" => "OPENCLAW_SLACK_TESTCHANNELID",
-        "feishu" => "OPENCLAW_FEISHU_TESTCHATID",
-        // WhatsApp 是扫码登录，不需要测试目标发送消息
-        "whatsapp" => return None,
-        // iMessage 也不需要测试目标
-        "imessage" => return None,
-        _ => return None,
-    };
-    
-    crate::utils::file::read_env_value(&env_path, env_key)
+/// 获取渠道账号的测试目标（渠道描述中 test_target_field 对应的 env 变量）
+fn get_channel_test_target(channel_type: &str, account: &str) -> Option<String> {
+    let env_key = channel_registry::get(channel_type)?.test_target_env_key(account)?;
+    crate::utils::file::read_env_value(&platform::get_env_file_path(), &env_key)
 }
 
-/// 检查渠道是否需要发送测试消息
+/// 检查渠道是否需要发送测试消息（有测试目标字段的渠道发送测试消息，其他只检查状态）
 fn channel_needs_send_test(channel_type: &str) -> bool {
-    match channel_type.to_lowercase().as_str() {
-        // 这些渠道需要发送测试消息来验证
-        "telegram" | "discord" | "slack" | "feishu" => true,
-        // WhatsApp 和 iMessage 只检查状态，不发送测试消息
-        "whatsapp" | "imessage" => false,
-        _ => false,
-    }
+    channel_registry::get(channel_type).is_some_and(|c| c.test_target_field.is_some())
+}
+
+/// `openclaw channels status` 输出中一个渠道账号的状态
+struct ChannelAccountStatus {
+    /// 渠道显示名称，如 Telegram
+    channel: String,
+    /// 账号名称，如 default
+    account: String,
+    enabled: bool,
+    configured: bool,
+    linked: bool,
+    /// 冒号后面的状态描述
+    status: String,
 }
 
-/// 从文本输出解析渠道状态
+/// 从文本输出解析所有渠道账号的状态
 /// 格式: "- Telegram default: enabled, configured, mode:polling, token:config"
-fn parse_channel_status_text(output: &str, channel_type: &str) -> Option<(bool, bool, bool, String)> {
+fn parse_channel_status_lines(output: &str) -> Vec<ChannelAccountStatus> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (head, status) = line.trim().strip_prefix("- ")?.split_once(':')?;
+            // 旧版本输出没有账号名称，视为默认账号
+            let (channel, account) = head
+                .trim()
+                .rsplit_once(' ')
+                .unwrap_or((head.trim(), DEFAULT_CHANNEL_ACCOUNT));
+            let status = status.trim();
+            Some(ChannelAccountStatus {
+                channel: channel.trim().to_string(),
+                account: account.to_string(),
+                enabled: status.contains("enabled"),
+                configured: status.contains("configured") && !status.contains("not configured"),
+                linked: status.contains("linked"),
+                status: status.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 从文本输出解析指定渠道账号的状态
+fn parse_channel_status_text(
+    output: &str,
+    channel_type: &str,
+    account: &str,
+) -> Option<(bool, bool, bool, String)> {
     let channel_lower = channel_type.to_lowercase();
-    
-    for line in output.lines() {
-        let line = line.trim();
-        // 匹配 "- Telegram default: ..." 格式
-        if line.starts_with("- ") && line.to_lowercase().contains(&channel_lower) {
-            // 解析状态
-            let enabled = line.contains("enabled");
-            let configured = line.contains("configured") && !line.contains("not configured");
-            let linked = line.contains("linked");
-            
-            // 提取状态描述（冒号后面的部分）
-            let status_part = line.split(':').skip(1).collect::<Vec<&str>>().join(":");
-            let status_msg = status_part.trim().to_string();
-            
-            return Some((enabled, configured, linked, status_msg));
-        }
-    }
-    None
+    let lines: Vec<ChannelAccountStatus> = parse_channel_status_lines(output)
+        .into_iter()
+        .filter(|s| s.channel.to_lowercase().contains(&channel_lower))
+        .collect();
+    
+    let matched = lines
+        .iter()
+        .position(|s| s.account.eq_ignore_ascii_case(account))
+        // 默认账号兼容账号名称不是 default 的单账号输出
+        .or_else(|| (account == DEFAULT_CHANNEL_ACCOUNT && lines.len() == 1).then_some(0))?;
+    let line = &lines[matched];
+    Some((line.enabled, line.configured, line.linked, line.status.clone()))
 }
 
 /// 测试渠道连接（检查状态并发送测试消息）
+/// 未指定账号时测试默认账号
 #[command]
-pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, String> {
-    info!("[渠道测试] 测试渠道: {}", channel_type);
+pub async fn test_channel(
+    channel_type: String,
+    account: Option<String>,
+) -> Result<ChannelTestResult, String> {
+    let account = account.unwrap_or_else(|| DEFAULT_CHANNEL_ACCOUNT.to_string());
+    let channel_type = channel_registry::require(&channel_type)?.id;
+    let label = channel_registry::account_label(&channel_type, &account);
+    info!("[渠道测试] 测试渠道: {}", label);
     let channel_lower = channel_type.to_lowercase();
     
     // 使用 openclaw channels status 检查渠道状态（不加 --json，因为可能不支持）
@@ -300,12 +235,12 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
             info!("[渠道测试] status 命令执行成功");
             
             // 尝试从文本输出解析状态
-            if let Some((enabled, configured, linked, status_msg)) = parse_channel_status_text(output, &channel_type) {
+            if let Some((enabled, configured, linked, status_msg)) = parse_channel_status_text(output, &channel_type, &account) {
                 debug_info = format!("enabled={}, configured={}, linked={}", enabled, configured, linked);
-                info!("[渠道测试] {} 状态: {}", channel_type, debug_info);
+                info!("[渠道测试] {} 状态: {}", label, debug_info);
                 
                 if !configured {
-                    info!("[渠道测试] {} 未配置", channel_type);
+                    info!("[渠道测试] {} 未配置", label);
                     return Ok(ChannelTestResult {
                         success: false,
                         channel: channel_type.clone(),
@@ -325,10 +260,17 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
                 };
             } else {
                 // 尝试 JSON 解析（作为备选）
-                if let Some(json_str) = extract_json_from_output(output) {
+                if let Some(json_str) = shell::extract_json_from_output(output) {
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&json_str) {
                         if let Some(channels) = json.get("channels").and_then(|c| c.as_object()) {
-                            if let Some(ch) = channels.get(&channel_lower) {
+                            let entry = channels.get(&channel_lower).and_then(|ch| {
+                                if account == DEFAULT_CHANNEL_ACCOUNT {
+                                    Some(ch)
+                                } else {
+                                    ch.get("accounts").and_then(|a| a.get(&account))
+                                }
+                            });
+                            if let Some(ch) = entry {
                                 let configured = ch.get("configured").and_then(|v| v.as_bool()).unwrap_or(false);
                                 let linked = ch.get("linked").and_then(|v| v.as_bool()).unwrap_or(false);
                                 channel_ok = configured;
@@ -339,7 +281,7 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
                 }
                 
                 if !channel_ok {
-                    debug_info = format!("无法解析 {} 的状态", channel_type);
+                    debug_info = format!("无法解析 {} 的状态", label);
                     info!("[渠道测试] {}", debug_info);
                 }
             }
@@ -352,7 +294,7 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
     
     // 如果渠道状态不 OK，直接返回失败
     if !channel_ok {
-        info!("[渠道测试] {} 状态检查失败，不发送测试消息", channel_type);
+        info!("[渠道测试] {} 状态检查失败，不发送测试消息", label);
         let error_msg = if debug_info.is_empty() {
             "渠道未运行或未配置".to_string()
         } else {
@@ -366,22 +308,22 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
         });
     }
     
-    info!("[渠道测试] {} 状态正常 ({})", channel_type, status_message);
+    info!("[渠道测试] {} 状态正常 ({})", label, status_message);
     
     // 对于 WhatsApp 和 iMessage，只返回状态检查结果，不发送测试消息
     if !channel_needs_send_test(&channel_type) {
-        info!("[渠道测试] {} 不需要发送测试消息（状态检查即可）", channel_type);
+        info!("[渠道测试] {} 不需要发送测试消息（状态检查即可）", label);
         return Ok(ChannelTestResult {
             success: true,
             channel: channel_type.clone(),
-            message: format!("{} 状态正常 ({})", channel_type, status_message),
+            message: format!("{} 状态正常 ({})", label, status_message),
             error: None,
         });
     }
     
     // 尝试发送测试消息
     info!("[渠道测试] 步骤2: 获取测试目标...");
-    let test_target = get_channel_test_target(&channel_type);
+    let test_target = get_channel_test_target(&channel_type, &account);
     
     if let Some(target) = test_target {
         info!("[渠道测试] 步骤3: 发送测试消息到 {}...", target);
@@ -390,20 +332,14 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
         
         // 使用 openclaw message send 发送测试消息
         info!("[渠道测试] 执行: openclaw message send --channel {} --target {} ...", channel_lower, target);
-        let send_result = shell::run_openclaw(&[
-            "message", "send",
-            "--channel", &channel_lower,
-            "--target", &target,
-            "--message", &message,
-            "--json"
-        ]);
+        let send_result = shell::run_openclaw(&message_send_args(&channel_lower, &account, &target, &message));
         
         match send_result {
             Ok(output) => {
                 info!("[渠道测试] 发送命令输出长度: {}", output.len());
                 
                 // 检查发送是否成功
-                let send_ok = if let Some(json_str) = extract_json_from_output(&output) {
+                let send_ok = if let Some(json_str) = shell::extract_json_from_output(&output) {
                     info!("[渠道测试] 提取到 JSON: {}", json_str);
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&json_str) {
                         // 检查各种成功标志
@@ -432,72 +368,78 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
                 };
                 
                 if send_ok {
-                    info!("[渠道测试] ✓ {} 测试消息发送成功", channel_type);
+                    info!("[渠道测试] ✓ {} 测试消息发送成功", label);
                     Ok(ChannelTestResult {
                         success: true,
                         channel: channel_type.clone(),
-                        message: format!("{} 测试消息已发送 ({})", channel_type, status_message),
+                        message: format!("{} 测试消息已发送 ({})", label, status_message),
                         error: None,
                     })
                 } else {
-                    info!("[渠道测试] ✗ {} 测试消息发送失败", channel_type);
+                    info!("[渠道测试] ✗ {} 测试消息发送失败", label);
                     Ok(ChannelTestResult {
                         success: false,
                         channel: channel_type.clone(),
-                        message: format!("{} 消息发送失败", channel_type),
+                        message: format!("{} 消息发送失败", label),
                         error: Some(output),
                     })
                 }
             }
             Err(e) => {
-                info!("[渠道测试] ✗ {} 发送命令执行失败: {}", channel_type, e);
+                info!("[渠道测试] ✗ {} 发送命令执行失败: {}", label, e);
                 Ok(ChannelTestResult {
                     success: false,
                     channel: channel_type.clone(),
-                    message: format!("{} 消息发送失败", channel_type),
+                    message: format!("{} 消息发送失败", label),
                     error: Some(e),
                 })
             }
         }
     } else {
         // 没有配置测试目标，返回状态但提示需要配置测试目标
-        let hint = match channel_lower.as_str() {
-            "telegram" => "请配置 OPENCLAW_TELEGRAM_USERID",
-            "discord" => "请配置 OPENCLAW_DISCORD_TESTCHANNELID",
-            "slack" => "请配置 OPENCLAW_SLACK_TESTCHANNELID",
-            "feishu" => "请配置 OPENCLAW_FEISHU_TESTCHATID",
-            _ => "请配置测试目标",
+        let hint = match channel_registry::get(&channel_type).and_then(|c| c.test_target_env_key(&account)) {
+            Some(env_key) => format!("请配置 {}", env_key),
+            None => "请配置测试目标".to_string(),
         };
         
-        info!("[渠道测试] {} 未配置测试目标，跳过发送消息 ({})", channel_type, hint);
+        info!("[渠道测试] {} 未配置测试目标，跳过发送消息 ({})", label, hint);
         Ok(ChannelTestResult {
             success: true,
             channel: channel_type.clone(),
-            message: format!("{} 状态正常 ({}) - {}", channel_type, status_message, hint),
+            message: format!("{} 状态正常 ({}) - {}", label, status_message, hint),
             error: None,
         })
     }
 }
 
-/// 发送测试消息到渠道
+/// `openclaw message send` 的参数，非默认账号时指定 --account
+fn message_send_args<'a>(channel: &'a str, account: &'a str, target: &'a str, message: &'a str) -> Vec<&'a str> {
+    let mut args = vec!["message", "send", "--channel", channel];
+    if account != DEFAULT_CHANNEL_ACCOUNT {
+        args.extend(["--account", account]);
+    }
+    args.extend(["--target", target, "--message", message, "--json"]);
+    args
+}
+
+/// 发送测试消息到渠道（未指定账号时使用默认账号）
 #[command]
-pub async fn send_test_message(channel_type: String, target: String) -> Result<ChannelTestResult, String> {
+pub async fn send_test_message(
+    channel_type: String,
+    target: String,
+    account: Option<String>,
+) -> Result<ChannelTestResult, String> {
+    let account = account.unwrap_or_else(|| DEFAULT_CHANNEL_ACCOUNT.to_string());
     let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
     let message = format!("🤖 OpenClaw 测试消息\n\n✅ 连接成功！\n⏰ {}", timestamp);
     
     // 使用 openclaw message send 命令发送测试消息
-    let send_result = shell::run_openclaw(&[
-        "message", "send",
-        "--channel", &channel_type,
-        "--target", &target,
-        "--message", &message,
-        "--json"
-    ]);
+    let send_result = shell::run_openclaw(&message_send_args(&channel_type, &account, &target, &message));
     
     match send_result {
         Ok(output) => {
             // 尝试从混合输出中提取并解析 JSON 结果
-            let success = if let Some(json_str) = extract_json_from_output(&output) {
+            let success = if let Some(json_str) = shell::extract_json_from_output(&output) {
                 if let Ok(json) = serde_json::from_str::<serde_json::Value>(&json_str) {
                     json.get("success").and_then(|v| v.as_bool()).unwrap_or(false)
                         || json.get("ok").and_then(|v| v.as_bool()).unwrap_or(false)
@@ -565,169 +507,144 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
     })
 }
 
-/// 启动渠道登录（如 WhatsApp 扫码）
+/// 启动渠道登录（如 WhatsApp 扫码），仅支持渠道描述中声明了 login 的渠道
 #[command]
 pub async fn start_channel_login(channel_type: String) -> Result<String, String> {
     info!("[渠道登录] 开始渠道登录流程: {}", channel_type);
     
-    match channel_type.as_str() {
-        "whatsapp" => {
-            info!("[渠道登录] WhatsApp 登录流程...");
-            // 先在后台启用插件
-            info!("[渠道登录] 启用 whatsapp 插件...");
-            let _ = shell::run_openclaw(&["plugins", "enable", "whatsapp"]);
-            
-            #[cfg(target_os = "macos")]
-            {
-                let env_path = platform::get_env_file_path();
-                // 创建一个临时脚本文件
-                // 流程：1. 启用插件 2. 重启 Gateway 3. 登录
-                let script_content = format!(
-                    r#"#!/bin/bash
+    let descriptor = channel_registry::require(&channel_type)?;
+    if descriptor.login.is_none() {
+        return Err(format!("不支持 {} 的登录向导", channel_type));
+    }
+    let channel = descriptor.id.as_str();
+    let name = descriptor.name.as_str();
+    
+    info!("[渠道登录] {} 登录流程...", name);
+    // 先启用插件，并确保 channels.<渠道> 存在（不设置 enabled，扫码登录的渠道不支持这个键）
+    info!("[渠道登录] 启用 {} 插件...", channel);
+    let (mut config, revision) = config_store::load_typed_with_revision()?;
+    plugins::set_enabled(&mut config, channel, true);
+    config
+        .channels
+        .entry(channel.to_string())
+        .or_insert_with(|| json!({ "dmPolicy": "pairing", "groupPolicy": "allowlist" }));
+    config_store::save_typed(&config, Some(&revision))?;
+    
+    #[cfg(target_os = "macos")]
+    {
+        let env_path = platform::get_env_file_path();
+        // 创建一个临时脚本文件
+        // 流程：1. 重启 Gateway 使插件生效 2. 登录
+        let script_content = format!(
+            r#"#!/bin/bash
 source {} 2>/dev/null
+{}
+# 脚本中包含密钥，加载后立即删除
+rm -f "$0"
 clear
 echo "╔════════════════════════════════════════════════════════╗"
-echo "║           📱 WhatsApp 登录向导                          ║"
+echo "║           📱 {name} 登录向导                          ║"
 echo "╚════════════════════════════════════════════════════════╝"
 echo ""
 
-echo "步骤 1/3: 启用 WhatsApp 插件..."
-openclaw plugins enable whatsapp 2>/dev/null || true
-
-# 确保 whatsapp 在 plugins.allow 数组中
-python3 << 'PYEOF'
-import json
-import os
-
-config_path = os.path.expanduser("~/.openclaw/openclaw.json")
-plugin_id = "whatsapp"
-
-try:
-    with open(config_path, 'r') as f:
-        config = json.load(f)
-    
-    # 设置 plugins.allow 和 plugins.entries
-    if 'plugins' not in config:
-        config['plugins'] = {{'allow': [], 'entries': {{}}}}
-    if 'allow' not in config['plugins']:
-        config['plugins']['allow'] = []
-    if 'entries' not in config['plugins']:
-        config['plugins']['entries'] = {{}}
-    
-    if plugin_id not in config['plugins']['allow']:
-        config['plugins']['allow'].append(plugin_id)
-    
-    config['plugins']['entries'][plugin_id] = {{'enabled': True}}
-    
-    # 确保 channels.whatsapp 存在（但不设置 enabled，WhatsApp 不支持这个键）
-    if 'channels' not in config:
-        config['channels'] = {{}}
-    if plugin_id not in config['channels']:
-        config['channels'][plugin_id] = {{'dmPolicy': 'pairing', 'groupPolicy': 'allowlist'}}
-    
-    with open(config_path, 'w') as f:
-        json.dump(config, f, indent=2, ensure_ascii=False)
-    print("配置已更新")
-except Exception as e:
-    print(f"Warning: {{e}}")
-PYEOF
-
-echo "✅ 插件已启用"
-echo ""
-
-echo "步骤 2/3: 重启 Gateway 使插件生效..."
+echo "步骤 1/2: 重启 Gateway 使插件生效..."
 # 使用 openclaw 命令停止和启动 gateway
 openclaw gateway stop 2>/dev/null || true
 sleep 2
 # 启动 gateway 服务
-openclaw gateway start 2>/dev/null || openclaw gateway --port 18789 &
+openclaw gateway start 2>/dev/null || openclaw gateway --port {} &
 sleep 3
 echo "✅ Gateway 已重启"
 echo ""
 
-echo "步骤 3/3: 启动 WhatsApp 登录..."
-echo "请使用 WhatsApp 手机 App 扫描下方二维码"
+echo "步骤 2/2: 启动 {name} 登录..."
+echo "请使用 {name} 手机 App 扫描下方二维码"
 echo ""
-openclaw channels login --channel whatsapp --verbose
+openclaw channels login --channel {channel} --verbose
 echo ""
 echo "════════════════════════════════════════════════════════"
 echo "登录完成！"
 echo ""
 read -p "按回车键关闭此窗口..."
 "#,
-                    env_path
-                );
-                
-                let script_path = "/tmp/openclaw_whatsapp_login.command";
-                std::fs::write(script_path, script_content)
-                    .map_err(|e| format!("创建脚本失败: {}", e))?;
-                
-                // 设置可执行权限
-                std::process::Command::new("chmod")
-                    .args(["+x", script_path])
-                    .output()
-                    .map_err(|e| format!("设置权限失败: {}", e))?;
-                
-                // 使用 open 命令打开 .command 文件（会自动在新终端窗口中执行）
-                std::process::Command::new("open")
-                    .arg(script_path)
-                    .spawn()
-                    .map_err(|e| format!("启动终端失败: {}", e))?;
-            }
-            
-            #[cfg(target_os = "linux")]
-            {
-                let env_path = platform::get_env_file_path();
-                // 创建脚本
-                let script_content = format!(
-                    r#"#!/bin/bash
+            shell::shell_quote(&env_path),
+            shell::profile_shell_exports(),
+            platform::get_gateway_port()
+        );
+        
+        let script_path = format!("/tmp/openclaw_{}_login.command", channel);
+        // 脚本中包含密钥，仅当前用户可读写
+        crate::utils::file::write_private_file_atomic(&script_path, &script_content)
+            .map_err(|e| format!("创建脚本失败: {}", e))?;
+        
+        // 设置可执行权限
+        std::process::Command::new("chmod")
+            .args(["700", script_path.as_str()])
+            .output()
+            .map_err(|e| format!("设置权限失败: {}", e))?;
+        
+        // 使用 open 命令打开 .command 文件（会自动在新终端窗口中执行）
+        std::process::Command::new("open")
+            .arg(&script_path)
+            .spawn()
+            .map_err(|e| format!("启动终端失败: {}", e))?;
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        let env_path = platform::get_env_file_path();
+        // 创建脚本
+        let script_content = format!(
+            r#"#!/bin/bash
 source {} 2>/dev/null
+{}
+# 脚本中包含密钥，加载后立即删除
+rm -f "$0"
 clear
-echo "📱 WhatsApp 登录向导"
+echo "📱 {name} 登录向导"
 echo ""
-openclaw channels login --channel whatsapp --verbose
+openclaw channels login --channel {channel} --verbose
 echo ""
 read -p "按回车键关闭..."
 "#,
-                    env_path
-                );
-                
-                let script_path = "/tmp/openclaw_whatsapp_login.sh";
-                std::fs::write(script_path, &script_content)
-                    .map_err(|e| format!("创建脚本失败: {}", e))?;
-                
-                std::process::Command::new("chmod")
-                    .args(["+x", script_path])
-                    .output()
-                    .map_err(|e| format!("设置权限失败: {}", e))?;
-                
-                // 尝试不同的终端模拟器
-                let terminals = ["gnome-terminal", "xfce4-terminal", "konsole", "xterm"];
-                let mut launched = false;
-                
-                for term in terminals {
-                    let result = std::process::Command::new(term)
-                        .args(["--", script_path])
-                        .spawn();
-                    
-                    if result.is_ok() {
-                        launched = true;
-                        break;
-                    }
-                }
-                
-                if !launched {
-                    return Err("无法启动终端，请手动运行: openclaw channels login --channel whatsapp".to_string());
-                }
-            }
+            shell::shell_quote(&env_path),
+            shell::profile_shell_exports()
+        );
+        
+        let script_path = format!("/tmp/openclaw_{}_login.sh", channel);
+        // 脚本中包含密钥，仅当前用户可读写
+        crate::utils::file::write_private_file_atomic(&script_path, &script_content)
+            .map_err(|e| format!("创建脚本失败: {}", e))?;
+        
+        std::process::Command::new("chmod")
+            .args(["700", script_path.as_str()])
+            .output()
+            .map_err(|e| format!("设置权限失败: {}", e))?;
+        
+        // 尝试不同的终端模拟器
+        let terminals = ["gnome-terminal", "xfce4-terminal", "konsole", "xterm"];
+        let mut launched = false;
+        
+        for term in terminals {
+            let result = std::process::Command::new(term)
+                .args(["--", script_path.as_str()])
+                .spawn();
             
-            #[cfg(target_os = "windows")]
-            {
-                return Err("Windows 暂不支持自动启动终端，请手动运行: openclaw channels login --channel whatsapp".to_string());
+            if result.is_ok() {
+                launched = true;
+                break;
             }
-            
-            Ok("已在新终端窗口中启动 WhatsApp 登录，请查看弹出的终端窗口并扫描二维码".to_string())
         }
-        _ => Err(format!("不支持 {} 的登录向导", channel_type)),
+        
+        if !launched {
+            return Err(format!("无法启动终端，请手动运行: openclaw channels login --channel {}", channel));
+        }
     }
+    
+    #[cfg(target_os = "windows")]
+    {
+        return Err(format!("Windows 暂不支持自动启动终端，请手动运行: openclaw channels login --channel {}", channel));
+    }
+    
+    Ok(format!("已在新终端窗口中启动 {} 登录，请查看弹出的终端窗口并扫描二维码", name))
 }
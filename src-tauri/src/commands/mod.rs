@@ -1,5 +1,10 @@
 pub mod config;
 pub mod diagnostics;
 pub mod installer;
+pub mod plugin;
 pub mod process;
+pub mod profile;
+pub mod provider;
+pub mod secrets;
 pub mod service;
+pub mod usage;
@@ -0,0 +1,266 @@
+use crate::models::{OpenClawConfig, PluginInfo};
+use crate::utils::plugins::{self, ListedPlugin};
+use crate::utils::{config_store, shell};
+use log::{error, info, warn};
+use std::collections::BTreeSet;
+use tauri::command;
+
+/// 获取已安装的插件，命令失败时返回空列表（只使用配置中的信息）
+fn listed_or_empty() -> Vec<ListedPlugin> {
+    plugins::list_installed().unwrap_or_else(|e| {
+        warn!("[插件] 获取插件列表失败: {}", e);
+        Vec::new()
+    })
+}
+
+/// 获取已安装的插件，命令失败时返回错误（需要据此判断是否已安装时使用）
+fn listed_or_error() -> Result<Vec<ListedPlugin>, String> {
+    plugins::list_installed().map_err(|e| {
+        error!("[插件] ✗ 获取插件列表失败: {}", e);
+        format!("获取插件列表失败: {}", e)
+    })
+}
+
+fn find_listed<'a>(listed: &'a [ListedPlugin], plugin_id: &str) -> Option<&'a ListedPlugin> {
+    listed.iter().find(|p| p.id.eq_ignore_ascii_case(plugin_id))
+}
+
+/// 按安装来源查找插件：列表中 source 或 name 与 spec 相同，或安装记录中的 spec 相同
+/// 用于重新安装或升级已安装的 npm 包、本地路径插件
+fn find_by_spec<'a>(
+    listed: &'a [ListedPlugin],
+    config: &OpenClawConfig,
+    spec: &str,
+) -> Option<&'a ListedPlugin> {
+    listed
+        .iter()
+        .find(|p| p.source.as_deref() == Some(spec) || p.name.as_deref() == Some(spec))
+        .or_else(|| {
+            config
+                .plugins
+                .installs
+                .iter()
+                .filter(|(_, record)| record.get("spec").and_then(|v| v.as_str()) == Some(spec))
+                .find_map(|(id, _)| find_listed(listed, id))
+        })
+}
+
+/// 列出插件：已安装的插件、plugins 配置中的插件，以及插件目录中尚未安装的插件
+#[command]
+pub async fn list_plugins() -> Result<Vec<PluginInfo>, String> {
+    info!("[插件] 获取插件列表...");
+    let listed = listed_or_empty();
+    let (config, _) = config_store::load_typed_with_revision()?;
+    let catalogue = plugins::catalogue();
+
+    let mut ids: BTreeSet<String> = listed.iter().map(|p| p.id.clone()).collect();
+    ids.extend(config.plugins.entries.keys().cloned());
+    ids.extend(config.plugins.installs.keys().cloned());
+    ids.extend(catalogue.iter().map(|c| c.id.clone()));
+
+    let result: Vec<PluginInfo> = ids
+        .into_iter()
+        .map(|id| {
+            let listed = find_listed(&listed, &id);
+            let install = config.plugins.installs.get(&id);
+            let install_text = |key: &str| {
+                install
+                    .and_then(|r| r.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            };
+            let entry_enabled = config
+                .plugins
+                .entries
+                .get(&id)
+                .and_then(|e| e.get("enabled"))
+                .and_then(|v| v.as_bool());
+            let catalogue_entry = catalogue.iter().find(|c| c.id == id);
+
+            PluginInfo {
+                name: listed
+                    .and_then(|p| p.name.clone())
+                    .or_else(|| catalogue_entry.map(|c| c.name.clone()))
+                    .unwrap_or_else(|| id.clone()),
+                version: listed
+                    .and_then(|p| p.version.clone())
+                    .or_else(|| install_text("version")),
+                source: listed
+                    .and_then(|p| p.source.clone())
+                    .or_else(|| install_text("spec")),
+                installed: listed.is_some() || install.is_some(),
+                enabled: entry_enabled
+                    .or_else(|| listed.and_then(|p| p.enabled))
+                    .unwrap_or(false),
+                allowed: config.plugins.allow.contains(&id),
+                package: catalogue_entry
+                    .map(|c| c.package.clone())
+                    .or_else(|| install_text("spec")),
+                channel: catalogue_entry.and_then(|c| c.channel.clone()),
+                id,
+            }
+        })
+        .collect();
+
+    info!("[插件] ✓ 共 {} 个插件", result.len());
+    Ok(result)
+}
+
+/// 安装插件：spec 可以是插件目录中的 ID，也可以是 npm 包名或本地路径
+/// 安装后启用插件并记录安装来源
+#[command]
+pub async fn install_plugin(spec: String, expected_revision: Option<String>) -> Result<String, String> {
+    let catalogue_entry = plugins::find_in_catalogue(&spec);
+    let package = catalogue_entry
+        .as_ref()
+        .map(|c| c.package.clone())
+        .unwrap_or_else(|| spec.clone());
+    info!("[插件] 安装插件: {}", package);
+
+    // 执行命令前检查前端持有的版本；openclaw 命令本身可能修改配置，命令执行后重新读取
+    let (current, _) = config_store::load_typed_for_update(expected_revision.as_deref())?;
+
+    // 无法获取插件列表时不能判断是否已安装或安装是否成功
+    let before = listed_or_error()?;
+    let already = catalogue_entry
+        .as_ref()
+        .and_then(|c| find_listed(&before, &c.id))
+        .cloned();
+
+    let listed = match already {
+        Some(listed) => {
+            info!("[插件] 插件 {} 已安装，跳过", listed.id);
+            listed
+        }
+        None => {
+            info!("[插件] 执行 openclaw plugins install {} ...", package);
+            if let Err(e) = shell::run_openclaw(&["plugins", "install", &package]) {
+                error!("[插件] ✗ 安装失败: {}", e);
+                return Err(format!(
+                    "安装插件失败: {}\n\n请手动执行: openclaw plugins install {}",
+                    e, package
+                ));
+            }
+
+            // 目录中的插件按 ID 查找，其他插件取安装后新出现的插件，
+            // 重新安装或升级时按安装来源查找
+            let after = listed_or_error()?;
+            let found = match &catalogue_entry {
+                Some(entry) => find_listed(&after, &entry.id).cloned(),
+                None => after
+                    .iter()
+                    .find(|p| find_listed(&before, &p.id).is_none())
+                    .or_else(|| find_by_spec(&after, &current, &package))
+                    .cloned(),
+            };
+            found.ok_or_else(|| {
+                warn!("[插件] 安装命令执行成功但插件未找到");
+                "安装命令执行成功但插件未找到，请检查 openclaw 版本".to_string()
+            })?
+        }
+    };
+
+    let (mut config, revision) = config_store::load_typed_with_revision()?;
+    plugins::set_enabled(&mut config, &listed.id, true);
+    plugins::record_install(&mut config, &listed.id, &package, listed.version.as_deref());
+    config_store::save_typed(&config, Some(&revision))?;
+
+    let label = match &listed.version {
+        Some(version) => format!("{}@{}", listed.id, version),
+        None => listed.id.clone(),
+    };
+    info!("[插件] ✓ 插件已安装并启用: {}", label);
+    Ok(format!("插件已安装: {}，重启 Gateway 后生效", label))
+}
+
+/// 卸载插件，并从 plugins.allow、plugins.entries 和 plugins.installs 中移除
+/// 仍有渠道配置依赖该插件时拒绝卸载
+#[command]
+pub async fn uninstall_plugin(
+    plugin_id: String,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
+    info!("[插件] 卸载插件: {}", plugin_id);
+
+    // 执行命令前检查前端持有的版本；openclaw 命令本身可能修改配置，命令执行后重新读取
+    let (current, _) = config_store::load_typed_for_update(expected_revision.as_deref())?;
+    if let Some(channel) = plugins::find_in_catalogue(&plugin_id).and_then(|c| c.channel) {
+        if current.channels.contains_key(&channel) {
+            return Err(format!("渠道 {} 仍在使用该插件，请先清空渠道配置", channel));
+        }
+    }
+
+    // 无法获取插件列表时不能判断是否需要执行卸载命令
+    let installed = find_listed(&listed_or_error()?, &plugin_id).is_some();
+    if installed {
+        info!("[插件] 执行 openclaw plugins uninstall {} ...", plugin_id);
+        if let Err(e) = shell::run_openclaw(&["plugins", "uninstall", &plugin_id]) {
+            error!("[插件] ✗ 卸载失败: {}", e);
+            return Err(format!(
+                "卸载插件失败: {}\n\n请手动执行: openclaw plugins uninstall {}",
+                e, plugin_id
+            ));
+        }
+    }
+
+    let (mut config, revision) = config_store::load_typed_with_revision()?;
+    plugins::remove(&mut config, &plugin_id);
+    config_store::save_typed(&config, Some(&revision))?;
+
+    info!("[插件] ✓ 插件已卸载: {}", plugin_id);
+    Ok(format!("插件已卸载: {}，重启 Gateway 后生效", plugin_id))
+}
+
+/// 启用或停用插件（修改 plugins.entries 和 plugins.allow），重启 Gateway 后生效
+#[command]
+pub async fn set_plugin_enabled(
+    plugin_id: String,
+    enabled: bool,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
+    let action = if enabled { "启用" } else { "停用" };
+    info!("[插件] {}插件: {}", action, plugin_id);
+
+    let (mut config, revision) =
+        config_store::load_typed_for_update(expected_revision.as_deref())?;
+    plugins::set_enabled(&mut config, &plugin_id, enabled);
+    config_store::save_typed(&config, Some(&revision))?;
+
+    info!("[插件] ✓ 已{}插件: {}", action, plugin_id);
+    Ok(format!("已{}插件 {}，重启 Gateway 后生效", action, plugin_id))
+}
+
+/// 更新插件到最新版本，并更新安装记录中的版本
+#[command]
+pub async fn update_plugin(
+    plugin_id: String,
+    expected_revision: Option<String>,
+) -> Result<String, String> {
+    info!("[插件] 更新插件: {}", plugin_id);
+
+    // 执行命令前检查前端持有的版本；openclaw 命令本身可能修改配置，命令执行后重新读取
+    config_store::load_typed_for_update(expected_revision.as_deref())?;
+
+    if let Err(e) = shell::run_openclaw(&["plugins", "update", &plugin_id]) {
+        error!("[插件] ✗ 更新失败: {}", e);
+        return Err(format!(
+            "更新插件失败: {}\n\n请手动执行: openclaw plugins update {}",
+            e, plugin_id
+        ));
+    }
+
+    let version = find_listed(&listed_or_empty(), &plugin_id).and_then(|p| p.version.clone());
+    if let Some(version) = &version {
+        let (mut config, revision) = config_store::load_typed_with_revision()?;
+        if plugins::set_installed_version(&mut config, &plugin_id, version) {
+            config_store::save_typed(&config, Some(&revision))?;
+        }
+    }
+
+    let label = match version {
+        Some(version) => format!("{}@{}", plugin_id, version),
+        None => plugin_id,
+    };
+    info!("[插件] ✓ 插件已更新: {}", label);
+    Ok(format!("插件已更新: {}，重启 Gateway 后生效", label))
+}
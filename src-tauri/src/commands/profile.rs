@@ -0,0 +1,108 @@
+use crate::models::{Profile, ProfilesOverview, DEFAULT_PROFILE_NAME};
+use crate::utils::settings;
+use log::info;
+use tauri::command;
+
+/// 校验档案名称：仅允许字母、数字、下划线和连字符
+fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("档案名称不能为空".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "档案名称无效: {}（仅允许字母、数字、下划线和连字符）",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// 获取所有配置档案及当前激活的档案
+#[command]
+pub async fn get_profiles() -> Result<ProfilesOverview, String> {
+    let manager_settings = settings::load();
+    Ok(ProfilesOverview {
+        active: settings::active_profile().name,
+        profiles: manager_settings.profiles,
+    })
+}
+
+/// 切换当前激活的配置档案
+#[command]
+pub async fn set_active_profile(name: String) -> Result<String, String> {
+    info!("[配置档案] 切换到档案: {}", name);
+
+    settings::update(|manager_settings| {
+        if !manager_settings.profiles.iter().any(|p| p.name == name) {
+            return Err(format!("档案不存在: {}", name));
+        }
+        manager_settings.active_profile = Some(name.clone());
+        Ok(())
+    })?;
+
+    info!("[配置档案] ✓ 当前档案: {}", name);
+    Ok(format!("已切换到档案 {}", name))
+}
+
+/// 新增或更新配置档案
+#[command]
+pub async fn save_profile(profile: Profile) -> Result<String, String> {
+    info!("[配置档案] 保存档案: {}", profile.name);
+
+    validate_profile_name(&profile.name)?;
+    if profile.config_dir.trim().is_empty() {
+        return Err("配置目录不能为空".to_string());
+    }
+    if profile.gateway_port == Some(0) {
+        return Err("端口无效: 0".to_string());
+    }
+    if profile.token_ttl_hours == Some(0) {
+        return Err("Token 有效期必须大于 0 小时".to_string());
+    }
+
+    settings::update(|manager_settings| {
+        match manager_settings
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == profile.name)
+        {
+            Some(existing) => *existing = profile.clone(),
+            None => manager_settings.profiles.push(profile.clone()),
+        }
+        Ok(())
+    })?;
+
+    info!("[配置档案] ✓ 档案 {} 已保存", profile.name);
+    Ok(format!("档案 {} 已保存", profile.name))
+}
+
+/// 删除配置档案（不能删除默认档案和当前激活的档案）
+#[command]
+pub async fn delete_profile(name: String) -> Result<String, String> {
+    info!("[配置档案] 删除档案: {}", name);
+
+    if name == DEFAULT_PROFILE_NAME {
+        return Err("不能删除默认档案".to_string());
+    }
+    if settings::active_profile().name == name {
+        return Err("不能删除当前激活的档案，请先切换到其他档案".to_string());
+    }
+
+    settings::update(|manager_settings| {
+        let before = manager_settings.profiles.len();
+        manager_settings.profiles.retain(|p| p.name != name);
+        if manager_settings.profiles.len() == before {
+            return Err(format!("档案不存在: {}", name));
+        }
+        manager_settings.gateway_token_issued_at.remove(&name);
+        manager_settings.budgets.remove(&name);
+        manager_settings.budget_alerts_sent.remove(&name);
+        Ok(())
+    })?;
+
+    info!("[配置档案] ✓ 档案 {} 已删除", name);
+    Ok(format!("档案 {} 已删除", name))
+}
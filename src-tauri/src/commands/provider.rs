@@ -0,0 +1,188 @@
+use crate::models::{ModelTestProgress, ModelTestResult, OpenClawConfig, SuggestedModel};
+use crate::utils::provider_api::{self, ProviderEndpoint};
+use crate::utils::{config_store, settings};
+use log::{error, info, warn};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 批量测试进度事件，每完成一个模型推送一次 `ModelTestProgress`
+pub const MODEL_TEST_PROGRESS_EVENT: &str = "model-test-progress";
+
+/// 模型列表请求超时
+const DISCOVER_TIMEOUT: Duration = Duration::from_secs(20);
+/// 模型连通性测试超时
+const TEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// 批量测试默认并发数
+const DEFAULT_TEST_CONCURRENCY: usize = 4;
+/// 批量测试最大并发数
+const MAX_TEST_CONCURRENCY: usize = 16;
+
+/// 通过已配置的 baseUrl 和 API Key，从 Provider 的模型列表接口实时获取可用模型
+#[command]
+pub async fn discover_models(provider: String) -> Result<Vec<SuggestedModel>, String> {
+    info!("[模型发现] 获取 Provider {} 的模型列表...", provider);
+
+    let (config, _) = config_store::load_typed_with_revision()?;
+    let provider_config = config
+        .models
+        .providers
+        .get(&provider)
+        .ok_or_else(|| format!("Provider 不存在: {}", provider))?;
+    let endpoint =
+        ProviderEndpoint::from_config(&provider, provider_config, &settings::active_profile())?;
+    info!("[模型发现] 协议: {:?}, 地址: {}", endpoint.protocol, endpoint.base_url);
+
+    let client = provider_api::http_client(DISCOVER_TIMEOUT)?;
+    match provider_api::list_models(&client, &endpoint).await {
+        Ok(models) => {
+            info!("[模型发现] ✓ Provider {} 共 {} 个模型", provider, models.len());
+            Ok(models)
+        }
+        Err(e) => {
+            error!("[模型发现] ✗ 获取失败: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// 直接向 Provider 发送最小请求，测试已配置的模型是否可用（不经过 OpenClaw Agent）
+#[command]
+pub async fn test_provider_model(provider: String, model: String) -> Result<ModelTestResult, String> {
+    let (config, _) = config_store::load_typed_with_revision()?;
+    test_configured_model(&config, &provider, &model).await
+}
+
+/// 测试 openclaw.json 中的 Provider/模型，模型的 `api` 决定请求协议
+pub async fn test_configured_model(
+    config: &OpenClawConfig,
+    provider: &str,
+    model: &str,
+) -> Result<ModelTestResult, String> {
+    info!("[模型测试] 测试 {}/{}...", provider, model);
+
+    let provider_config = config
+        .models
+        .providers
+        .get(provider)
+        .ok_or_else(|| format!("Provider 不存在: {}", provider))?;
+    let api_type = provider_config
+        .models
+        .iter()
+        .find(|m| m.id == model)
+        .and_then(|m| m.api.as_deref());
+    let endpoint =
+        ProviderEndpoint::from_config(provider, provider_config, &settings::active_profile())?;
+
+    let client = provider_api::http_client(TEST_TIMEOUT)?;
+    let result = provider_api::test_model(&client, &endpoint, provider, model, api_type).await;
+    if result.success {
+        info!(
+            "[模型测试] ✓ {}/{} 可用 (HTTP {:?}, 首 token {:?}ms, 总耗时 {}ms)",
+            provider, model, result.status, result.first_token_ms, result.total_ms
+        );
+    } else {
+        warn!(
+            "[模型测试] ✗ {}/{} 不可用 ({:?}): {}",
+            provider,
+            model,
+            result.error_class,
+            result.error.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(result)
+}
+
+/// 并发测试所有已配置的模型，返回按 provider/model 排序的结果
+/// 包括 `agents.defaults.models` 中的可用模型和 `models.providers` 下的全部模型
+#[command]
+pub async fn test_all_models(
+    app: AppHandle,
+    concurrency: Option<usize>,
+) -> Result<Vec<ModelTestResult>, String> {
+    let (config, _) = config_store::load_typed_with_revision()?;
+
+    let mut targets: BTreeSet<(String, String)> = BTreeSet::new();
+    for (provider, provider_config) in &config.models.providers {
+        for model in provider_config.models.iter().filter(|m| !m.id.is_empty()) {
+            targets.insert((provider.clone(), model.id.clone()));
+        }
+    }
+    let mut results = Vec::new();
+    for full_id in config.agents.defaults.models.keys() {
+        match full_id.split_once('/') {
+            Some((provider, model)) => {
+                targets.insert((provider.to_string(), model.to_string()));
+            }
+            None => results.push(unavailable(full_id, "", format!("模型 ID 格式无效: {}", full_id))),
+        }
+    }
+
+    let total = targets.len() + results.len();
+    let concurrency = concurrency
+        .unwrap_or(DEFAULT_TEST_CONCURRENCY)
+        .clamp(1, MAX_TEST_CONCURRENCY);
+    info!("[模型测试] 批量测试 {} 个模型，并发数 {}", total, concurrency);
+
+    let config = Arc::new(config);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut tasks = JoinSet::new();
+    for (provider, model) in targets {
+        let config = Arc::clone(&config);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            match test_configured_model(&config, &provider, &model).await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!("[模型测试] ✗ {}/{}: {}", provider, model, e);
+                    unavailable(&provider, &model, e)
+                }
+            }
+        });
+    }
+
+    for (completed, result) in results.iter().enumerate() {
+        emit_progress(&app, completed + 1, total, result);
+    }
+    while let Some(joined) = tasks.join_next().await {
+        let result = joined.map_err(|e| format!("模型测试任务异常: {}", e))?;
+        emit_progress(&app, results.len() + 1, total, &result);
+        results.push(result);
+    }
+
+    results.sort_by(|a, b| (&a.provider, &a.model).cmp(&(&b.provider, &b.model)));
+    let passed = results.iter().filter(|r| r.success).count();
+    info!("[模型测试] 批量测试完成: {}/{} 可用", passed, total);
+    Ok(results)
+}
+
+/// 无法发起测试（配置缺失等）时的结果
+fn unavailable(provider: &str, model: &str, error: String) -> ModelTestResult {
+    ModelTestResult {
+        success: false,
+        provider: provider.to_string(),
+        model: model.to_string(),
+        api: String::new(),
+        status: None,
+        error_class: None,
+        error: Some(error),
+        response: None,
+        first_token_ms: None,
+        total_ms: 0,
+    }
+}
+
+fn emit_progress(app: &AppHandle, completed: usize, total: usize, result: &ModelTestResult) {
+    let progress = ModelTestProgress {
+        completed,
+        total,
+        result: result.clone(),
+    };
+    if let Err(e) = app.emit(MODEL_TEST_PROGRESS_EVENT, &progress) {
+        warn!("[模型测试] 推送进度失败: {}", e);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::models::{SecretsMigration, SecretsStatus};
+use crate::utils::{config_store, file, secrets, settings, shell};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use tauri::command;
+
+/// 获取密钥库状态（仅返回密钥名称，不返回密钥值）
+#[command]
+pub async fn get_secrets_status() -> Result<SecretsStatus, String> {
+    let backend = secrets::backend(&settings::active_profile());
+    Ok(SecretsStatus {
+        backend: backend.name().to_string(),
+        key_source: backend.key_source(),
+        location: backend.location(),
+        names: backend.load_all()?.into_keys().collect(),
+    })
+}
+
+/// 保存密钥
+#[command]
+pub async fn set_secret(name: String, value: String) -> Result<String, String> {
+    secrets::validate_name(&name)?;
+    if value.is_empty() {
+        return Err("密钥值不能为空".to_string());
+    }
+    secrets::backend(&settings::active_profile()).set(&name, &value)?;
+    info!("[密钥库] ✓ 已保存密钥: {}", name);
+    Ok(format!("密钥 {} 已保存", name))
+}
+
+/// 删除密钥
+#[command]
+pub async fn delete_secret(name: String) -> Result<String, String> {
+    if !secrets::backend(&settings::active_profile()).delete(&name)? {
+        return Err(format!("密钥不存在: {}", name));
+    }
+    info!("[密钥库] ✓ 已删除密钥: {}", name);
+    Ok(format!("密钥 {} 已删除", name))
+}
+
+/// 将 openclaw.json 中的明文 API Key 和 env 文件中的密钥类变量迁移到密钥库
+#[command]
+pub async fn migrate_secrets() -> Result<SecretsMigration, String> {
+    info!("[密钥库] 开始迁移明文密钥...");
+    let profile = settings::active_profile();
+    let backend = secrets::backend(&profile);
+    let mut migration = SecretsMigration {
+        migrated: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    // openclaw.json 中的 Provider API Key
+    let (mut config, revision) = config_store::load_typed_with_revision()?;
+    let mut config_changed = false;
+    let secret_names: BTreeMap<String, Result<String, String>> = config
+        .models
+        .providers
+        .keys()
+        .map(|name| {
+            let secret_name =
+                secrets::provider_secret_name_for(&config.models.providers, name, None);
+            (name.clone(), secret_name)
+        })
+        .collect();
+    // 本次已写入密钥库的值，env 文件中的同名变量不能覆盖
+    let mut written: BTreeMap<String, String> = BTreeMap::new();
+    for (provider_name, provider) in config.models.providers.iter_mut() {
+        let Some(key) = provider.api_key.as_deref() else {
+            continue;
+        };
+        if key.is_empty() || secrets::parse_reference(key).is_some() {
+            continue;
+        }
+        let secret_name = match &secret_names[provider_name] {
+            Ok(name) => name.clone(),
+            Err(e) => {
+                migration
+                    .errors
+                    .push(format!("models.providers.{}.apiKey: {}", provider_name, e));
+                continue;
+            }
+        };
+        match backend.set(&secret_name, key) {
+            Ok(()) => {
+                written.insert(secret_name.clone(), key.to_string());
+                provider.api_key = Some(secrets::reference(&secret_name));
+                config_changed = true;
+                migration.migrated.push(secret_name);
+            }
+            Err(e) => migration
+                .errors
+                .push(format!("models.providers.{}.apiKey: {}", provider_name, e)),
+        }
+    }
+    if config_changed {
+        config_store::save_typed(&config, Some(&revision))?;
+    }
+
+    // env 文件中的密钥类变量
+    let env_path = profile.env_file_path();
+    let env_vars = shell::load_openclaw_env_vars(&env_path);
+    for (key, value) in env_vars {
+        if !secrets::is_secret_env_key(&key) || secrets::validate_name(&key).is_err() {
+            continue;
+        }
+        if let Some(existing) = written.get(&key) {
+            if *existing != value {
+                migration.errors.push(format!(
+                    "{}: 与 Provider 的 API Key 同名但值不同，未迁移，请手动处理 env 文件中的该变量",
+                    key
+                ));
+                continue;
+            }
+        }
+        let result = backend
+            .set(&key, &value)
+            .and_then(|_| file::remove_env_value(&env_path, &key).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => migration.migrated.push(key),
+            Err(e) => migration.errors.push(format!("{}: {}", key, e)),
+        }
+    }
+
+    for error in &migration.errors {
+        warn!("[密钥库] 迁移失败: {}", error);
+    }
+    info!("[密钥库] ✓ 已迁移 {} 个密钥", migration.migrated.len());
+    Ok(migration)
+}
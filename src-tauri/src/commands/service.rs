@@ -1,293 +1,121 @@
-use crate::models::ServiceStatus;
-use crate::utils::shell;
-use tauri::command;
-use std::process::Command;
+use crate::models::{GatewayExitRecord, GatewayLogEntry, LogQuery, ServiceStatus};
+use crate::utils::log_parser::{self, LogFilter};
+use crate::utils::{gateway, log_follower, platform, process_stats};
+use crate::utils::supervisor::GatewaySupervisor;
+use tauri::{command, State};
+use std::path::PathBuf;
 use log::{info, debug};
 
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
-
-/// Windows CREATE_NO_WINDOW 标志，用于隐藏控制台窗口
-#[cfg(windows)]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
-
-const SERVICE_PORT: u16 = 18789;
-
-/// 检测端口是否有服务在监听，返回 PID
-/// 简单直接：端口被占用 = 服务运行中
-fn check_port_listening(port: u16) -> Option<u32> {
-    #[cfg(unix)]
-    {
-        let output = Command::new("lsof")
-            .args(["-ti", &format!(":{}", port)])
-            .output()
-            .ok()?;
-        
-        if output.status.success() {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .next()
-                .and_then(|line| line.trim().parse::<u32>().ok())
-        } else {
-            None
-        }
-    }
-    
-    #[cfg(windows)]
-    {
-        let mut cmd = Command::new("netstat");
-        cmd.args(["-ano"]);
-        cmd.creation_flags(CREATE_NO_WINDOW);
-        
-        let output = cmd.output().ok()?;
-        
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                if line.contains(&format!(":{}", port)) && line.contains("LISTENING") {
-                    if let Some(pid_str) = line.split_whitespace().last() {
-                        if let Ok(pid) = pid_str.parse::<u32>() {
-                            return Some(pid);
-                        }
-                    }
-                }
-            }
-        }
-        None
-    }
-}
-
-/// 获取服务状态（简单版：直接检查端口占用）
+/// 获取服务状态（检查端口占用，并采集监听进程的资源占用）
 #[command]
 pub async fn get_service_status() -> Result<ServiceStatus, String> {
     // 简单直接：检查端口是否被占用
-    let pid = check_port_listening(SERVICE_PORT);
+    let port = platform::get_gateway_port();
+    let pid = gateway::listening_pid(port);
     let running = pid.is_some();
     
+    // 采集运行时长、内存和 CPU
+    let stats = pid.map(process_stats::sample).unwrap_or_default();
+    
     Ok(ServiceStatus {
         running,
         pid,
-        port: SERVICE_PORT,
-        uptime_seconds: None,
-        memory_mb: None,
-        cpu_percent: None,
+        port,
+        uptime_seconds: stats.uptime_seconds,
+        memory_mb: stats.memory_mb.map(|mb| (mb * 10.0).round() / 10.0),
+        cpu_percent: stats.cpu_percent,
     })
 }
 
-/// 启动服务
+/// 获取 gateway 进程退出记录（最新的在前）
 #[command]
-pub async fn start_service() -> Result<String, String> {
-    info!("[服务] 启动服务...");
-    
-    // 检查是否已经运行
-    let status = get_service_status().await?;
-    if status.running {
-        info!("[服务] 服务已在运行中");
-        return Err("服务已在运行中".to_string());
-    }
-    
-    // 检查 openclaw 命令是否存在
-    let openclaw_path = shell::get_openclaw_path();
-    if openclaw_path.is_none() {
-        info!("[服务] 找不到 openclaw 命令");
-        return Err("找不到 openclaw 命令，请先通过 npm install -g openclaw 安装".to_string());
-    }
-    info!("[服务] openclaw 路径: {:?}", openclaw_path);
-    
-    // 直接后台启动 gateway（不等待 doctor，避免阻塞）
-    info!("[服务] 后台启动 gateway...");
-    shell::spawn_openclaw_gateway()
-        .map_err(|e| format!("启动服务失败: {}", e))?;
-    
-    // 轮询等待端口开始监听（最多 15 秒）
-    info!("[服务] 等待端口 {} 开始监听...", SERVICE_PORT);
-    for i in 1..=15 {
-        std::thread::sleep(std::time::Duration::from_secs(1));
-        if let Some(pid) = check_port_listening(SERVICE_PORT) {
-            info!("[服务] ✓ 启动成功 ({}秒), PID: {}", i, pid);
-            return Ok(format!("服务已启动，PID: {}", pid));
-        }
-        if i % 3 == 0 {
-            debug!("[服务] 等待中... ({}秒)", i);
-        }
-    }
-    
-    info!("[服务] 等待超时，端口仍未监听");
-    Err("服务启动超时（15秒），请检查 openclaw 日志".to_string())
+pub async fn get_gateway_exit_history(
+    supervisor: State<'_, GatewaySupervisor>,
+) -> Result<Vec<GatewayExitRecord>, String> {
+    Ok(supervisor.exit_history())
 }
 
-/// 获取监听指定端口的所有 PID
-fn get_pids_on_port(port: u16) -> Vec<u32> {
-    #[cfg(unix)]
-    {
-        let output = Command::new("lsof")
-            .args(["-ti", &format!(":{}", port)])
-            .output();
-        
-        match output {
-            Ok(out) if out.status.success() => {
-                String::from_utf8_lossy(&out.stdout)
-                    .lines()
-                    .filter_map(|line| line.trim().parse::<u32>().ok())
-                    .collect()
-            }
-            _ => vec![],
-        }
-    }
-    
-    #[cfg(windows)]
-    {
-        let mut cmd = Command::new("netstat");
-        cmd.args(["-ano"]);
-        cmd.creation_flags(CREATE_NO_WINDOW);
-        
-        match cmd.output() {
-            Ok(out) if out.status.success() => {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-                stdout.lines()
-                    .filter(|line| line.contains(&format!(":{}", port)) && line.contains("LISTENING"))
-                    .filter_map(|line| line.split_whitespace().last())
-                    .filter_map(|pid_str| pid_str.parse::<u32>().ok())
-                    .collect()
-            }
-            _ => vec![],
-        }
-    }
-}
-
-/// 通过 PID 杀死进程
-fn kill_process(pid: u32, force: bool) -> bool {
-    info!("[服务] 杀死进程 PID: {}, force: {}", pid, force);
-    
-    #[cfg(unix)]
-    {
-        let signal = if force { "-9" } else { "-TERM" };
-        Command::new("kill")
-            .args([signal, &pid.to_string()])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
-    }
-    
-    #[cfg(windows)]
-    {
-        let mut cmd = Command::new("taskkill");
-        if force {
-            cmd.args(["/F", "/PID", &pid.to_string()]);
-        } else {
-            cmd.args(["/PID", &pid.to_string()]);
-        }
-        cmd.creation_flags(CREATE_NO_WINDOW);
-        cmd.output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
-    }
+/// 启动服务
+#[command]
+pub async fn start_service(supervisor: State<'_, GatewaySupervisor>) -> Result<String, String> {
+    gateway::start(&supervisor)
 }
 
 /// 停止服务（通过杀死监听端口的进程）
 #[command]
-pub async fn stop_service() -> Result<String, String> {
-    info!("[服务] 停止服务...");
-    
-    let pids = get_pids_on_port(SERVICE_PORT);
-    if pids.is_empty() {
-        info!("[服务] 端口 {} 无进程监听，服务未运行", SERVICE_PORT);
-        return Ok("服务未在运行".to_string());
-    }
-    
-    info!("[服务] 发现 {} 个进程监听端口 {}: {:?}", pids.len(), SERVICE_PORT, pids);
-    
-    // 第一步：优雅终止 (SIGTERM)
-    for &pid in &pids {
-        kill_process(pid, false);
-    }
-    std::thread::sleep(std::time::Duration::from_secs(2));
-    
-    // 检查是否已停止
-    let remaining = get_pids_on_port(SERVICE_PORT);
-    if remaining.is_empty() {
-        info!("[服务] ✓ 已停止");
-        return Ok("服务已停止".to_string());
-    }
-    
-    // 第二步：强制终止 (SIGKILL)
-    info!("[服务] 仍有 {} 个进程存活，强制终止...", remaining.len());
-    for &pid in &remaining {
-        kill_process(pid, true);
-    }
-    std::thread::sleep(std::time::Duration::from_secs(1));
-    
-    let still_running = get_pids_on_port(SERVICE_PORT);
-    if still_running.is_empty() {
-        info!("[服务] ✓ 已强制停止");
-        Ok("服务已停止".to_string())
-    } else {
-        Err(format!("无法停止服务，仍有进程: {:?}", still_running))
-    }
+pub async fn stop_service(supervisor: State<'_, GatewaySupervisor>) -> Result<String, String> {
+    gateway::stop(&supervisor)
 }
 
 /// 重启服务
 #[command]
-pub async fn restart_service() -> Result<String, String> {
-    info!("[服务] 重启服务...");
-    
-    // 先停止
-    let _ = stop_service().await;
-    std::thread::sleep(std::time::Duration::from_secs(1));
-    
-    // 再启动
-    start_service().await
+pub async fn restart_service(supervisor: State<'_, GatewaySupervisor>) -> Result<String, String> {
+    gateway::restart(&supervisor)
 }
 
-/// 获取日志（直接读取日志文件，比 RPC 更可靠）
-#[command]
-pub async fn get_logs(lines: Option<u32>) -> Result<Vec<String>, String> {
-    let n = lines.unwrap_or(100);
-    
-    let config_dir = crate::utils::platform::get_config_dir();
+
+/// 查询日志时每个文件最多读取的行数
+const QUERY_SCAN_LINES: usize = 5000;
+/// 查询日志默认返回的条数
+const QUERY_DEFAULT_LIMIT: u32 = 500;
+
+/// 读取已知日志文件的最后 N 行并解析，按时间戳合并排序
+fn read_log_entries(lines_per_file: usize) -> Vec<GatewayLogEntry> {
+    let config_dir = PathBuf::from(platform::get_config_dir());
+    let log_dir = PathBuf::from(platform::get_log_dir());
     
     // 尝试多个已知的日志文件位置
-    let log_files = vec![
-        format!("{}/logs/gateway.log", config_dir),
-        format!("{}/logs/gateway.err.log", config_dir),
-        format!("{}/stderr.log", config_dir),
-        format!("{}/stdout.log", config_dir),
+    let log_files = [
+        log_dir.join("gateway.log"),
+        log_dir.join("gateway.err.log"),
+        config_dir.join("stderr.log"),
+        config_dir.join("stdout.log"),
     ];
     
-    let mut all_lines: Vec<String> = Vec::new();
-    
+    let mut entries: Vec<GatewayLogEntry> = Vec::new();
     for log_file in &log_files {
-        if !std::path::Path::new(log_file).exists() {
+        if !log_file.exists() {
             continue;
         }
-        
-        // 使用 tail 高效读取最后 N 行
-        match Command::new("tail")
-            .args(["-n", &n.to_string(), log_file])
-            .output()
-        {
-            Ok(output) if output.status.success() => {
-                let content = String::from_utf8_lossy(&output.stdout);
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    if !trimmed.is_empty() {
-                        all_lines.push(trimmed.to_string());
-                    }
-                }
-            }
-            _ => continue,
-        }
-    }
-    
-    // 尝试按时间戳排序（日志格式通常以 ISO 时间戳开头）
-    all_lines.sort();
-    
-    // 去重并保留最后 N 行
-    all_lines.dedup();
-    let total = all_lines.len();
-    if total > n as usize {
-        all_lines = all_lines.split_off(total - n as usize);
-    }
+        let source = log_file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // 从文件末尾按块读取最后 N 行
+        let lines = log_follower::read_last_lines(log_file, lines_per_file);
+        entries.extend(log_parser::parse_lines(&lines, &source));
+    }
+    
+    log_parser::sort_entries(&mut entries);
+    // 同一内容可能同时写入多个文件，去掉相邻的重复行
+    entries.dedup_by(|a, b| a.raw == b.raw);
+    entries
+}
+
+/// 获取日志（直接读取日志文件，比 RPC 更可靠）
+/// 之后的新日志由日志跟踪线程通过 `gateway-log` 事件推送
+#[command]
+pub async fn get_logs(lines: Option<u32>) -> Result<Vec<String>, String> {
+    let n = lines.unwrap_or(100) as usize;
     
-    Ok(all_lines)
+    let entries = read_log_entries(n);
+    let skip = entries.len().saturating_sub(n);
+    Ok(entries.into_iter().skip(skip).map(|e| e.raw).collect())
+}
+
+/// 按级别、时间范围、渠道和关键字查询结构化日志（返回最新的 limit 条，按时间升序）
+#[command]
+pub async fn query_logs(query: LogQuery) -> Result<Vec<GatewayLogEntry>, String> {
+    info!("[日志查询] 查询条件: {:?}", query);
+    let filter = LogFilter::new(&query)?;
+    let limit = query.limit.unwrap_or(QUERY_DEFAULT_LIMIT) as usize;
+    
+    let matched: Vec<GatewayLogEntry> = read_log_entries(QUERY_SCAN_LINES)
+        .into_iter()
+        .filter(|e| filter.matches(e))
+        .collect();
+    
+    let skip = matched.len().saturating_sub(limit);
+    debug!("[日志查询] 匹配 {} 条，返回 {} 条", matched.len(), matched.len() - skip);
+    Ok(matched.into_iter().skip(skip).collect())
 }
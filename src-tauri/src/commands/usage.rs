@@ -0,0 +1,87 @@
+use crate::commands::config::model_exists;
+use crate::models::{BudgetAction, BudgetSettings, BudgetStatus, UsagePeriod, UsageReport};
+use crate::utils::{budget, config_store, settings, usage};
+use log::info;
+use tauri::command;
+
+/// 最多统计的周期数
+const MAX_PERIODS: u32 = 366;
+
+/// 获取用量与费用报告
+/// 从 Gateway 会话记录中读取 token 用量，按模型配置的价格计算费用，
+/// 返回最近 `count` 个周期（默认 30 天 / 12 周 / 12 个月）按 Provider、模型和渠道的汇总
+#[command]
+pub async fn get_usage_report(
+    period: UsagePeriod,
+    count: Option<u32>,
+) -> Result<UsageReport, String> {
+    let count = count
+        .unwrap_or(match period {
+            UsagePeriod::Day => 30,
+            UsagePeriod::Week | UsagePeriod::Month => 12,
+        })
+        .clamp(1, MAX_PERIODS);
+    info!("[用量统计] 生成用量报告: {:?} x {}", period, count);
+
+    let (config, _) = config_store::load_typed_with_revision()?;
+    let bounds = usage::period_bounds(period, count, chrono::Local::now().date_naive());
+    let since = usage::local_midnight(bounds[0]);
+
+    let (records, scanned) = usage::read_records(&settings::active_profile(), since);
+    let report = usage::build_report(&config, &records, period, &bounds, scanned);
+
+    info!(
+        "[用量统计] ✓ {} 次调用，{} 个会话，费用合计 {:.4}",
+        report.totals.requests, report.sessions_scanned, report.totals.cost
+    );
+    Ok(report)
+}
+
+/// 获取当前档案的预算设置
+#[command]
+pub async fn get_budget_settings() -> Result<BudgetSettings, String> {
+    Ok(budget::settings_for(&settings::active_profile()))
+}
+
+/// 保存当前档案的预算设置
+#[command]
+pub async fn save_budget_settings(mut budget: BudgetSettings) -> Result<String, String> {
+    let profile = settings::active_profile();
+    info!("[预算] 保存档案 {} 的预算设置", profile.name);
+
+    let limits = budget.global_limit.iter().chain(budget.provider_limits.values());
+    if limits.into_iter().any(|limit| !limit.is_finite() || *limit <= 0.0) {
+        return Err("预算金额必须大于 0".to_string());
+    }
+    if budget.alert_thresholds.iter().any(|t| *t == 0 || *t > 1000) {
+        return Err("提醒阈值必须在 1% 到 1000% 之间".to_string());
+    }
+    budget.alert_thresholds.sort_unstable();
+    budget.alert_thresholds.dedup();
+
+    if budget.action == BudgetAction::SwitchModel {
+        let model = budget
+            .cheaper_model
+            .as_deref()
+            .ok_or("超出预算时切换模型需要指定要切换到的模型")?;
+        let (config, _) = config_store::load_typed_for(&profile)?;
+        if !model_exists(&config, model) {
+            return Err(format!("模型 {} 不在 models.providers 中", model));
+        }
+    }
+
+    settings::update(|manager_settings| {
+        manager_settings.budgets.insert(profile.name.clone(), budget);
+        Ok(())
+    })?;
+
+    info!("[预算] ✓ 预算设置已保存");
+    Ok("预算设置已保存".to_string())
+}
+
+/// 获取当前周期的预算使用情况
+#[command]
+pub async fn get_budget_status() -> Result<BudgetStatus, String> {
+    let profile = settings::active_profile();
+    budget::status(&profile, &budget::settings_for(&profile))
+}
@@ -8,7 +8,11 @@ mod commands;
 mod models;
 mod utils;
 
-use commands::{config, diagnostics, installer, process, service};
+use commands::{
+    config, diagnostics, installer, plugin, process, profile, provider, secrets, service, usage,
+};
+use utils::{budget, config_watcher, gateway_token, log_follower};
+use utils::supervisor::GatewaySupervisor;
 
 fn main() {
     // 初始化日志 - 默认显示 info 级别日志
@@ -23,6 +27,17 @@ fn main() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_notification::init())
+        .manage(GatewaySupervisor::new())
+        .setup(|app| {
+            // 跟踪 gateway 日志并实时推送给前端
+            log_follower::spawn(app.handle().clone());
+            config_watcher::spawn(app.handle().clone());
+            // Token 到期自动轮换
+            gateway_token::spawn(app.handle().clone());
+            // 费用预算检查
+            budget::spawn(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // 服务管理
             service::get_service_status,
@@ -30,6 +45,8 @@ fn main() {
             service::stop_service,
             service::restart_service,
             service::get_logs,
+            service::query_logs,
+            service::get_gateway_exit_history,
             // 进程管理
             process::check_openclaw_installed,
             process::get_openclaw_version,
@@ -37,26 +54,63 @@ fn main() {
             // 配置管理
             config::get_config,
             config::save_config,
+            config::validate_config,
+            config::get_config_revision,
+            config::list_config_backups,
+            config::restore_config_backup,
             config::get_env_value,
             config::save_env_value,
+            config::list_env_vars,
+            config::delete_env_var,
+            config::apply_env_changes,
             config::get_ai_providers,
+            config::get_channel_descriptors,
+            config::validate_channel_config,
             config::get_channels_config,
             config::save_channel_config,
             config::clear_channel_config,
+            config::set_channel_enabled,
             // Gateway Token
             config::get_or_create_gateway_token,
             config::get_dashboard_url,
+            config::rotate_gateway_token,
+            config::get_gateway_token_info,
+            config::set_gateway_token_ttl,
+            config::get_gateway_port,
+            config::set_gateway_port,
+            // 配置档案
+            profile::get_profiles,
+            profile::set_active_profile,
+            profile::save_profile,
+            profile::delete_profile,
+            // 密钥库
+            secrets::get_secrets_status,
+            secrets::set_secret,
+            secrets::delete_secret,
+            secrets::migrate_secrets,
             // AI 配置管理
             config::get_official_providers,
             config::get_ai_config,
             config::save_provider,
             config::delete_provider,
             config::set_primary_model,
+            config::set_fallback_models,
             config::add_available_model,
             config::remove_available_model,
-            // 飞书插件管理
-            config::check_feishu_plugin,
-            config::install_feishu_plugin,
+            provider::discover_models,
+            provider::test_provider_model,
+            provider::test_all_models,
+            // 用量统计
+            usage::get_usage_report,
+            usage::get_budget_settings,
+            usage::save_budget_settings,
+            usage::get_budget_status,
+            // 插件管理
+            plugin::list_plugins,
+            plugin::install_plugin,
+            plugin::uninstall_plugin,
+            plugin::set_plugin_enabled,
+            plugin::update_plugin,
             // 诊断测试
             diagnostics::run_doctor,
             diagnostics::test_ai_connection,
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// 默认账号：配置直接写在 channels.<渠道> 下；其他账号写在 channels.<渠道>.accounts.<账号> 下
+pub const DEFAULT_CHANNEL_ACCOUNT: &str = "default";
+
+/// 渠道描述：渠道的配置字段、测试方式、登录方式和插件包
+/// 内置描述随应用打包（resources/channels.json），用户描述放在管理器目录的 channels/ 下
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelDescriptor {
+    /// 渠道 ID（openclaw.json 中 channels 下的键）
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 配置字段
+    #[serde(default)]
+    pub fields: Vec<ChannelFieldDescriptor>,
+    /// 发送测试消息的目标字段，为空表示只检查状态、不发送测试消息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_target_field: Option<String>,
+    /// 登录方式，如 qr 表示需要在终端中执行 `openclaw channels login` 扫码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    /// 需要额外安装的 OpenClaw 插件包
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_package: Option<String>,
+    /// 改名的字段：旧字段名 → 新字段名，读取和保存时自动迁移
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub renamed_fields: BTreeMap<String, String>,
+    /// 渠道配置的 JSON Schema（包括仅用于测试的字段），保存前按它校验
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+    /// 是否来自用户描述文件
+    #[serde(default)]
+    pub custom: bool,
+}
+
+impl ChannelDescriptor {
+    /// 仅用于测试、保存在 env 文件中的字段
+    pub fn test_only_fields(&self) -> impl Iterator<Item = &str> {
+        self.fields
+            .iter()
+            .filter(|f| f.test_only)
+            .map(|f| f.key.as_str())
+    }
+
+    /// 把旧字段名迁移为新字段名；新旧字段同时存在时以新字段为准。返回是否有改动
+    pub fn migrate_renamed_fields(&self, config: &mut HashMap<String, Value>) -> bool {
+        let mut changed = false;
+        for (old, new) in &self.renamed_fields {
+            if let Some(value) = config.remove(old) {
+                config.entry(new.clone()).or_insert(value);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// 测试字段在 env 文件中的变量名：
+    /// 默认账号为 OPENCLAW_<渠道>_<字段>，其他账号为 OPENCLAW_<渠道>_<账号>_<字段>
+    pub fn test_env_key(&self, account: &str, field: &str) -> String {
+        let prefix = if account == DEFAULT_CHANNEL_ACCOUNT {
+            format!("OPENCLAW_{}", env_segment(&self.id))
+        } else {
+            format!("OPENCLAW_{}_{}", env_segment(&self.id), env_segment(account))
+        };
+        format!("{}_{}", prefix, env_segment(field))
+    }
+
+    /// 已有账号中 env 变量名与 account 相同的账号（如 a-b 与 a_b、Foo 与 foo），没有冲突时返回 None
+    pub fn env_conflicting_account<'a>(&self, account: &str, accounts: &'a [String]) -> Option<&'a str> {
+        let segment = env_segment(account);
+        accounts
+            .iter()
+            .find(|other| other.as_str() != account && env_segment(other) == segment)
+            .map(|other| other.as_str())
+    }
+
+    /// 测试目标在 env 文件中的变量名
+    pub fn test_target_env_key(&self, account: &str) -> Option<String> {
+        self.test_target_field
+            .as_deref()
+            .map(|field| self.test_env_key(account, field))
+    }
+}
+
+/// 转为 env 变量名片段：大写，非字母数字替换为 _
+fn env_segment(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+/// 渠道配置字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelFieldDescriptor {
+    /// 字段名
+    pub key: String,
+    /// 显示名称
+    pub label: String,
+    /// 输入类型：text / password / select
+    #[serde(rename = "type", default = "default_field_type")]
+    pub field_type: String,
+    /// 是否为密钥（界面中默认隐藏）
+    #[serde(default)]
+    pub secret: bool,
+    /// 是否必填
+    #[serde(default)]
+    pub required: bool,
+    /// 仅用于测试，保存在 env 文件（OPENCLAW_<渠道>_<字段>）而不是 openclaw.json
+    #[serde(default)]
+    pub test_only: bool,
+    /// 输入提示
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// select 类型的选项
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<ChannelFieldOption>,
+}
+
+fn default_field_type() -> String {
+    "text".to_string()
+}
+
+/// select 字段的选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelFieldOption {
+    pub value: String,
+    pub label: String,
+}
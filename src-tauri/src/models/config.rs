@@ -1,171 +1,237 @@
+use super::DEFAULT_CHANNEL_ACCOUNT;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// 字段为默认值时不写入 openclaw.json，避免保存时凭空多出空节点
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
 
 /// OpenClaw 完整配置 - 对应 openclaw.json 结构
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// 各层的 `extra` 保存模型未声明的字段，读写时原样保留
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct OpenClawConfig {
     /// Agent 配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub agents: AgentsConfig,
     /// 模型配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub models: ModelsConfig,
     /// 网关配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub gateway: GatewayConfig,
     /// 渠道配置
-    #[serde(default)]
-    pub channels: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channels: BTreeMap<String, Value>,
     /// 插件配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub plugins: PluginsConfig,
     /// 元数据
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub meta: MetaConfig,
+    /// 其他未建模的字段
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    /// 读取时类型不匹配、无法解析的节点（路径 → 原始值），保存时原样写回
+    #[serde(skip)]
+    pub unparsed: Vec<(Vec<String>, Value)>,
 }
 
 /// Agent 配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentsConfig {
     /// 默认配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub defaults: AgentDefaults,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Agent 默认配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentDefaults {
     /// 模型配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub model: AgentModelConfig,
     /// 可用模型列表 (provider/model -> {})
-    #[serde(default)]
-    pub models: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, Value>,
     /// 压缩配置
-    #[serde(default)]
-    pub compaction: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compaction: Option<Value>,
     /// 上下文裁剪
-    #[serde(rename = "contextPruning", default)]
-    pub context_pruning: Option<serde_json::Value>,
+    #[serde(rename = "contextPruning", default, skip_serializing_if = "Option::is_none")]
+    pub context_pruning: Option<Value>,
     /// 心跳配置
-    #[serde(default)]
-    pub heartbeat: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<Value>,
     /// 最大并发数
-    #[serde(rename = "maxConcurrent", default)]
+    #[serde(rename = "maxConcurrent", default, skip_serializing_if = "Option::is_none")]
     pub max_concurrent: Option<u32>,
     /// 子代理配置
-    #[serde(default)]
-    pub subagents: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagents: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Agent 模型配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentModelConfig {
     /// 主模型 (格式: provider/model-id)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub primary: Option<String>,
+    /// 备用模型，主模型不可用时按顺序尝试 (格式: provider/model-id)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 模型配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ModelsConfig {
     /// Provider 配置映射
-    #[serde(default)]
-    pub providers: HashMap<String, ProviderConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, ProviderConfig>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Provider 配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ProviderConfig {
     /// API 地址
-    #[serde(rename = "baseUrl")]
+    #[serde(rename = "baseUrl", default, skip_serializing_if = "String::is_empty")]
     pub base_url: String,
     /// API Key
-    #[serde(rename = "apiKey")]
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
     /// 模型列表
     #[serde(default)]
     pub models: Vec<ModelConfig>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 模型配置详情
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 前端提交时使用 snake_case 字段名，通过 alias 兼容
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ModelConfig {
     /// 模型 ID
+    #[serde(default)]
     pub id: String,
     /// 显示名称
+    #[serde(default)]
     pub name: String,
     /// API 类型 (anthropic-messages / openai-completions)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub api: Option<String>,
     /// 支持的输入类型
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub input: Vec<String>,
     /// 上下文窗口大小
-    #[serde(rename = "contextWindow", default)]
+    #[serde(
+        rename = "contextWindow",
+        alias = "context_window",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub context_window: Option<u32>,
     /// 最大输出 Token
-    #[serde(rename = "maxTokens", default)]
+    #[serde(
+        rename = "maxTokens",
+        alias = "max_tokens",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub max_tokens: Option<u32>,
     /// 是否支持推理模式
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<bool>,
     /// 成本配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cost: Option<ModelCostConfig>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 模型成本配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ModelCostConfig {
     #[serde(default)]
     pub input: f64,
     #[serde(default)]
     pub output: f64,
-    #[serde(rename = "cacheRead", default)]
+    #[serde(rename = "cacheRead", alias = "cache_read", default)]
     pub cache_read: f64,
-    #[serde(rename = "cacheWrite", default)]
+    #[serde(rename = "cacheWrite", alias = "cache_write", default)]
     pub cache_write: f64,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 网关配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct GatewayConfig {
     /// 模式：local 或 cloud
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
+    /// 监听端口
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
     /// 认证配置
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auth: Option<GatewayAuthConfig>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 网关认证配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct GatewayAuthConfig {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 插件配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct PluginsConfig {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allow: Vec<String>,
-    #[serde(default)]
-    pub entries: HashMap<String, serde_json::Value>,
-    #[serde(default)]
-    pub installs: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub entries: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub installs: BTreeMap<String, Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// 元数据配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct MetaConfig {
-    #[serde(rename = "lastTouchedAt", default)]
+    #[serde(rename = "lastTouchedAt", default, skip_serializing_if = "Option::is_none")]
     pub last_touched_at: Option<String>,
-    #[serde(rename = "lastTouchedVersion", default)]
+    #[serde(rename = "lastTouchedVersion", default, skip_serializing_if = "Option::is_none")]
     pub last_touched_version: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// 配置校验问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigIssue {
+    /// 出问题的 JSON 路径，如 models.providers.openai.baseUrl
+    pub path: String,
+    /// 问题描述
+    pub message: String,
+    /// 严重程度：error / warning
+    pub severity: String,
 }
 
 // ============ 前端展示用数据结构 ============
@@ -206,6 +272,9 @@ pub struct SuggestedModel {
     pub max_tokens: Option<u32>,
     /// 是否推荐
     pub recommended: bool,
+    /// 能力（如 vision、tools、reasoning），来自 Provider 的模型列表接口
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
 }
 
 /// 已配置的 Provider（从配置文件读取）
@@ -247,10 +316,23 @@ pub struct ConfiguredModel {
 pub struct AIConfigOverview {
     /// 主模型
     pub primary_model: Option<String>,
+    /// 备用模型（按尝试顺序）
+    pub fallback_models: Vec<String>,
     /// 已配置的 Provider 列表
     pub configured_providers: Vec<ConfiguredProvider>,
     /// 可用模型列表
     pub available_models: Vec<String>,
+    /// 读取时的配置版本，修改时作为 expected_revision 传回
+    pub revision: String,
+}
+
+/// 完整配置及其版本（返回给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    /// openclaw.json 内容
+    pub config: Value,
+    /// 读取时的配置版本，保存时作为 expected_revision 传回
+    pub revision: String,
 }
 
 // ============ 旧数据结构保持兼容 ============
@@ -288,16 +370,63 @@ pub struct AIModelOption {
 /// 渠道配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelConfig {
-    /// 渠道 ID
+    /// 渠道 ID：默认账号为渠道类型，其他账号为 <渠道类型>:<账号>
     pub id: String,
     /// 渠道类型
     pub channel_type: String,
+    /// 账号名称（default 表示默认账号）
+    #[serde(default = "default_channel_account")]
+    pub account: String,
     /// 是否启用
     pub enabled: bool,
+    /// 是否已停用（配置保留，可通过 set_channel_enabled 重新启用）
+    #[serde(default)]
+    pub disabled: bool,
     /// 配置详情
     pub config: HashMap<String, serde_json::Value>,
 }
 
+fn default_channel_account() -> String {
+    DEFAULT_CHANNEL_ACCOUNT.to_string()
+}
+
+/// 渠道配置列表及其版本（返回给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelsOverview {
+    /// 所有渠道账号的配置
+    pub channels: Vec<ChannelConfig>,
+    /// 读取时的配置版本，修改时作为 expected_revision 传回
+    pub revision: String,
+}
+
+/// 启用/停用渠道的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelToggleResult {
+    /// 渠道类型
+    pub channel: String,
+    /// 账号名称
+    pub account: String,
+    /// 切换后是否启用
+    pub enabled: bool,
+    /// 是否已重启 Gateway
+    pub restarted: bool,
+    /// Gateway 正在运行但未重启，需要重启后才能生效
+    pub restart_required: bool,
+}
+
+/// openclaw.json 备份
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBackup {
+    /// 备份文件名
+    pub name: String,
+    /// 备份文件完整路径
+    pub path: String,
+    /// 备份时间（RFC 3339）
+    pub created_at: String,
+    /// 文件大小（字节）
+    pub size_bytes: u64,
+}
+
 /// 环境变量配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnvConfig {
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// 环境变量条目（值已脱敏）
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvVarInfo {
+    /// 变量名
+    pub key: String,
+    /// 脱敏后的值
+    pub masked_value: String,
+    /// 存储位置：env（env 文件）或 vault（密钥库）
+    pub source: String,
+    /// 是否为密钥类变量
+    pub is_secret: bool,
+    /// 使用该变量的 Provider / 渠道等说明
+    pub used_by: Vec<String>,
+}
+
+/// 环境变量批量修改中的一项
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnvChange {
+    /// 变量名
+    pub key: String,
+    /// 新值，为空表示删除
+    pub value: Option<String>,
+}
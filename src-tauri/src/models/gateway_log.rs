@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// 解析后的一条 gateway 日志
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayLogEntry {
+    /// 时间戳（RFC 3339），无法识别时为空
+    pub timestamp: Option<String>,
+    /// 日志级别（trace / debug / info / warn / error / fatal）
+    pub level: Option<String>,
+    /// 子系统或渠道标签，如 telegram、gateway/ws
+    pub subsystem: Option<String>,
+    /// 日志正文
+    pub message: String,
+    /// 来源文件名
+    pub source: String,
+    /// 去除 ANSI 颜色代码后的原始行
+    pub raw: String,
+}
+
+/// 日志查询条件（均为可选）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogQuery {
+    /// 最低日志级别，如 warn 会返回 warn、error 和 fatal
+    pub level: Option<String>,
+    /// 起始时间（RFC 3339，含）
+    pub since: Option<String>,
+    /// 结束时间（RFC 3339，含）
+    pub until: Option<String>,
+    /// 子系统/渠道，不区分大小写的包含匹配
+    pub channel: Option<String>,
+    /// 正文搜索关键字，不区分大小写
+    pub search: Option<String>,
+    /// 最多返回的条数（默认 500，取最新的）
+    pub limit: Option<u32>,
+}
@@ -1,5 +1,19 @@
+pub mod channel;
 pub mod config;
+pub mod env;
+pub mod gateway_log;
+pub mod plugin;
+pub mod secrets;
+pub mod settings;
 pub mod status;
+pub mod usage;
 
+pub use channel::*;
 pub use config::*;
+pub use env::*;
+pub use gateway_log::*;
+pub use plugin::*;
+pub use secrets::*;
+pub use settings::*;
 pub use status::*;
+pub use usage::*;
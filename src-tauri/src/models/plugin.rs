@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// OpenClaw 插件（返回给前端）
+/// 合并了 `openclaw plugins list` 的输出、openclaw.json 中的 plugins 配置和插件目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// 插件 ID（plugins.entries 中的键）
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 已安装的版本
+    pub version: Option<String>,
+    /// 来源：bundled / global / workspace，或 npm 包名、本地路径
+    pub source: Option<String>,
+    /// 是否已安装（出现在插件列表或 plugins.installs 中）
+    pub installed: bool,
+    /// 是否启用（以 plugins.entries 为准，没有条目时取插件列表中的状态）
+    pub enabled: bool,
+    /// 是否在 plugins.allow 白名单中
+    pub allowed: bool,
+    /// 插件目录中对应的安装包，未安装时可用它安装
+    pub package: Option<String>,
+    /// 依赖该插件的渠道
+    pub channel: Option<String>,
+}
+
+/// 插件目录条目：可以一键安装的插件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCatalogEntry {
+    /// 插件 ID
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 安装包（npm 包名或本地路径）
+    pub package: String,
+    /// 依赖该插件的渠道
+    pub channel: Option<String>,
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+/// 密钥库状态（不包含任何密钥值）
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretsStatus {
+    /// 存储后端名称
+    pub backend: String,
+    /// 加密密钥来源：machine-key 或 passphrase
+    pub key_source: String,
+    /// 存储位置
+    pub location: String,
+    /// 已保存的密钥名称
+    pub names: Vec<String>,
+}
+
+/// 明文密钥迁移结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretsMigration {
+    /// 已迁移到密钥库的密钥名称
+    pub migrated: Vec<String>,
+    /// 迁移失败的条目及原因
+    pub errors: Vec<String>,
+}
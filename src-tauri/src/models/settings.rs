@@ -0,0 +1,98 @@
+use super::BudgetSettings;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 默认配置档案名称
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// 管理器自身的设置（与 openclaw.json 分开保存）
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ManagerSettings {
+    /// 旧版的全局 Gateway 端口覆盖，读取时迁移到默认档案
+    #[serde(rename = "gatewayPort", default, skip_serializing_if = "Option::is_none")]
+    pub gateway_port: Option<u16>,
+    /// 当前激活的配置档案名称
+    #[serde(rename = "activeProfile", default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    /// 配置档案列表
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    /// 各档案当前 Gateway Token 的签发时间（RFC 3339），用于计算过期
+    #[serde(rename = "gatewayTokenIssuedAt", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub gateway_token_issued_at: BTreeMap<String, String>,
+    /// 各档案的费用预算
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub budgets: BTreeMap<String, BudgetSettings>,
+    /// 各档案已发送的预算提醒（周期开始日期|范围|阈值），避免同一周期重复提醒
+    #[serde(rename = "budgetAlertsSent", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub budget_alerts_sent: BTreeMap<String, Vec<String>>,
+}
+
+/// OpenClaw 配置档案：一套独立的配置目录、env 文件、日志目录和端口
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Profile {
+    /// 档案名称
+    pub name: String,
+    /// 配置目录（openclaw.json 所在目录）
+    #[serde(rename = "configDir")]
+    pub config_dir: String,
+    /// env 文件路径（为空时使用 <configDir>/env）
+    #[serde(rename = "envFile", default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+    /// 日志目录（为空时使用 <configDir>/logs）
+    #[serde(rename = "logDir", default, skip_serializing_if = "Option::is_none")]
+    pub log_dir: Option<String>,
+    /// Gateway 端口覆盖（为空时使用 openclaw.json 中的 gateway.port）
+    #[serde(rename = "gatewayPort", default, skip_serializing_if = "Option::is_none")]
+    pub gateway_port: Option<u16>,
+    /// Gateway Token 有效期（小时），到期后自动轮换；为空表示不过期
+    #[serde(rename = "tokenTtlHours", default, skip_serializing_if = "Option::is_none")]
+    pub token_ttl_hours: Option<u32>,
+}
+
+impl Profile {
+    /// openclaw.json 路径
+    pub fn config_file_path(&self) -> String {
+        Path::new(&self.config_dir).join("openclaw.json").display().to_string()
+    }
+
+    /// env 文件路径
+    pub fn env_file_path(&self) -> String {
+        self.env_file
+            .clone()
+            .unwrap_or_else(|| Path::new(&self.config_dir).join("env").display().to_string())
+    }
+
+    /// 日志目录
+    pub fn log_dir_path(&self) -> String {
+        self.log_dir
+            .clone()
+            .unwrap_or_else(|| Path::new(&self.config_dir).join("logs").display().to_string())
+    }
+
+    /// 加密密钥库文件路径
+    pub fn vault_file_path(&self) -> String {
+        Path::new(&self.config_dir).join("secrets.vault").display().to_string()
+    }
+}
+
+/// 配置档案概览（返回给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilesOverview {
+    /// 当前激活的档案名称
+    pub active: String,
+    /// 所有档案
+    pub profiles: Vec<Profile>,
+}
+
+/// Gateway Token 的签发与过期信息（不包含 token 本身）
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayTokenInfo {
+    /// 签发时间
+    pub issued_at: Option<String>,
+    /// 有效期（小时）
+    pub ttl_hours: Option<u32>,
+    /// 过期时间
+    pub expires_at: Option<String>,
+}
@@ -22,7 +22,7 @@ impl Default for ServiceStatus {
         Self {
             running: false,
             pid: None,
-            port: 18789,
+            port: crate::utils::platform::get_gateway_port(),
             uptime_seconds: None,
             memory_mb: None,
             cpu_percent: None,
@@ -30,6 +30,25 @@ impl Default for ServiceStatus {
     }
 }
 
+/// Gateway 进程退出记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayExitRecord {
+    /// 所属配置档案
+    pub profile: String,
+    /// 进程 ID
+    pub pid: u32,
+    /// 退出码（被信号终止时为空）
+    pub code: Option<i32>,
+    /// 终止信号（仅 Unix）
+    pub signal: Option<i32>,
+    /// 退出时间（RFC 3339）
+    pub exited_at: String,
+    /// 本次运行时长（秒）
+    pub uptime_seconds: u64,
+    /// 是否已安排自动重启
+    pub restart_scheduled: bool,
+}
+
 /// 系统信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
@@ -79,6 +98,66 @@ pub struct AITestResult {
     pub latency_ms: Option<u64>,
 }
 
+/// Provider 连通性测试的错误分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderErrorClass {
+    /// API Key 无效或无权限（401/403）
+    Auth,
+    /// 额度或余额不足
+    Quota,
+    /// 请求频率超限
+    RateLimited,
+    /// 模型不存在或无权访问该模型
+    ModelNotFound,
+    /// 请求参数或接口地址有误
+    InvalidRequest,
+    /// 服务端错误或过载（5xx）
+    Server,
+    /// 请求超时
+    Timeout,
+    /// 无法连接
+    Network,
+    /// 无法识别的错误
+    Unknown,
+}
+
+/// 单个模型的连通性测试结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelTestResult {
+    /// 是否成功
+    pub success: bool,
+    /// Provider 名称
+    pub provider: String,
+    /// 模型 ID
+    pub model: String,
+    /// 实际使用的接口类型（anthropic-messages / openai-completions）
+    pub api: String,
+    /// HTTP 状态码（未收到响应时为空）
+    pub status: Option<u16>,
+    /// 错误分类
+    pub error_class: Option<ProviderErrorClass>,
+    /// 错误信息
+    pub error: Option<String>,
+    /// 模型回复内容
+    pub response: Option<String>,
+    /// 首个 token 延迟（毫秒）
+    pub first_token_ms: Option<u64>,
+    /// 总耗时（毫秒）
+    pub total_ms: u64,
+}
+
+/// 批量模型测试进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelTestProgress {
+    /// 已完成数量
+    pub completed: usize,
+    /// 总数
+    pub total: usize,
+    /// 刚完成的测试结果
+    pub result: ModelTestResult,
+}
+
 /// 渠道测试结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelTestResult {
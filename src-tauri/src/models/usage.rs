@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 用量统计周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UsagePeriod {
+    /// 按天（本地时间 0 点起）
+    Day,
+    /// 按周（周一起）
+    Week,
+    /// 按自然月
+    #[default]
+    Month,
+}
+
+/// token 用量与费用合计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageTotals {
+    /// 模型调用次数
+    pub requests: u64,
+    /// 输入 token
+    pub input_tokens: u64,
+    /// 输出 token
+    pub output_tokens: u64,
+    /// 缓存读取 token
+    pub cache_read_tokens: u64,
+    /// 缓存写入 token
+    pub cache_write_tokens: u64,
+    /// 费用（与模型 cost 配置的币种一致，通常为美元）
+    pub cost: f64,
+}
+
+/// 某个维度（Provider、模型或渠道）下的用量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageBreakdown {
+    /// Provider 名称、provider/model 或渠道名称
+    pub key: String,
+    #[serde(flatten)]
+    pub usage: UsageTotals,
+}
+
+/// 单个统计周期的用量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsagePeriodReport {
+    /// 周期开始日期（YYYY-MM-DD，含）
+    pub start: String,
+    /// 周期结束日期（YYYY-MM-DD，不含）
+    pub end: String,
+    /// 周期合计
+    pub totals: UsageTotals,
+    /// 按 Provider 汇总（按费用降序）
+    pub by_provider: Vec<UsageBreakdown>,
+    /// 按模型汇总（按费用降序）
+    pub by_model: Vec<UsageBreakdown>,
+    /// 按渠道汇总（按费用降序）
+    pub by_channel: Vec<UsageBreakdown>,
+}
+
+/// 用量报告（返回给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// 统计周期
+    pub period: UsagePeriod,
+    /// 各周期的用量，从早到晚排列，最后一个为当前周期
+    pub periods: Vec<UsagePeriodReport>,
+    /// 所有周期的合计
+    pub totals: UsageTotals,
+    /// 读取的会话文件数
+    pub sessions_scanned: usize,
+    /// 没有配置价格、会话中也没有记录费用的模型（费用按 0 计）
+    pub unpriced_models: Vec<String>,
+}
+
+/// 超出预算时执行的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// 仅发送通知
+    #[default]
+    Notify,
+    /// 将主模型切换为更便宜的模型
+    SwitchModel,
+    /// 停止 Gateway
+    StopGateway,
+}
+
+/// 费用预算设置（按配置档案保存在管理器设置中）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BudgetSettings {
+    /// 预算周期
+    #[serde(default)]
+    pub period: UsagePeriod,
+    /// 全局预算（所有 Provider 合计），为空表示不限制
+    #[serde(rename = "globalLimit", default, skip_serializing_if = "Option::is_none")]
+    pub global_limit: Option<f64>,
+    /// 各 Provider 的预算
+    #[serde(rename = "providerLimits", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub provider_limits: BTreeMap<String, f64>,
+    /// 提醒阈值（预算的百分比）
+    #[serde(rename = "alertThresholds", default = "default_alert_thresholds")]
+    pub alert_thresholds: Vec<u32>,
+    /// 超出预算时执行的动作
+    #[serde(default)]
+    pub action: BudgetAction,
+    /// 动作为 switch_model 时切换到的模型 (provider/model-id)
+    #[serde(rename = "cheaperModel", default, skip_serializing_if = "Option::is_none")]
+    pub cheaper_model: Option<String>,
+}
+
+fn default_alert_thresholds() -> Vec<u32> {
+    vec![80, 100]
+}
+
+impl Default for BudgetSettings {
+    fn default() -> Self {
+        Self {
+            period: UsagePeriod::default(),
+            global_limit: None,
+            provider_limits: BTreeMap::new(),
+            alert_thresholds: default_alert_thresholds(),
+            action: BudgetAction::default(),
+            cheaper_model: None,
+        }
+    }
+}
+
+/// 单项预算的使用情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    /// global 或 Provider 名称
+    pub scope: String,
+    /// 预算
+    pub limit: f64,
+    /// 本周期已花费
+    pub spent: f64,
+    /// 已用百分比
+    pub percent: f64,
+}
+
+/// 当前周期的预算使用情况（返回给前端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    /// 预算周期
+    pub period: UsagePeriod,
+    /// 本周期开始日期（YYYY-MM-DD）
+    pub period_start: String,
+    /// 各项预算，全局预算在前
+    pub budgets: Vec<BudgetUsage>,
+}
@@ -0,0 +1,232 @@
+use crate::models::{BudgetAction, BudgetSettings, BudgetStatus, BudgetUsage, Profile};
+use crate::utils::supervisor::GatewaySupervisor;
+use crate::utils::{config_store, gateway, settings, usage};
+use log::{info, warn};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// 预算检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(300);
+/// 全局预算的范围名称
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// 获取档案的预算设置（未设置时为默认值）
+pub fn settings_for(profile: &Profile) -> BudgetSettings {
+    settings::load()
+        .budgets
+        .get(&profile.name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// 计算当前周期各项预算的使用情况
+pub fn status(profile: &Profile, budget: &BudgetSettings) -> Result<BudgetStatus, String> {
+    let (config, _) = config_store::load_typed_for(profile)?;
+    let bounds = usage::period_bounds(budget.period, 1, chrono::Local::now().date_naive());
+    let (records, scanned) = usage::read_records(profile, usage::local_midnight(bounds[0]));
+    let report = usage::build_report(&config, &records, budget.period, &bounds, scanned);
+    let current = &report.periods[0];
+
+    let usage_of = |scope: &str, limit: f64, spent: f64| BudgetUsage {
+        scope: scope.to_string(),
+        limit,
+        spent,
+        percent: if limit > 0.0 { spent / limit * 100.0 } else { 0.0 },
+    };
+
+    let mut budgets = Vec::new();
+    if let Some(limit) = budget.global_limit {
+        budgets.push(usage_of(GLOBAL_SCOPE, limit, current.totals.cost));
+    }
+    for (provider, limit) in &budget.provider_limits {
+        let spent = current
+            .by_provider
+            .iter()
+            .find(|b| b.key == *provider)
+            .map(|b| b.usage.cost)
+            .unwrap_or(0.0);
+        budgets.push(usage_of(provider, *limit, spent));
+    }
+
+    Ok(BudgetStatus {
+        period: budget.period,
+        period_start: current.start.clone(),
+        budgets,
+    })
+}
+
+/// 启动后台线程，定期检查当前档案的预算，超过阈值时发送桌面通知并执行设置的动作
+pub fn spawn(app: AppHandle) {
+    thread::Builder::new()
+        .name("budget-check".to_string())
+        .spawn(move || loop {
+            check(&app);
+            thread::sleep(CHECK_INTERVAL);
+        })
+        .expect("无法启动预算检查线程");
+}
+
+fn check(app: &AppHandle) {
+    let profile = settings::active_profile();
+    let budget = settings_for(&profile);
+    if budget.global_limit.is_none() && budget.provider_limits.is_empty() {
+        return;
+    }
+
+    let status = match status(&profile, &budget) {
+        Ok(status) => status,
+        Err(e) => {
+            warn!("[预算] 计算用量失败: {}", e);
+            return;
+        }
+    };
+
+    // 只保留本周期的提醒记录；这里只用于判断，新增的记录最后在设置锁内合并写回
+    let prefix = format!("{}|", status.period_start);
+    let mut sent: Vec<String> = settings::load()
+        .budget_alerts_sent
+        .get(&profile.name)
+        .cloned()
+        .unwrap_or_default();
+    let before = sent.len();
+    sent.retain(|key| key.starts_with(&prefix));
+    let mut changed = sent.len() != before;
+    let mut added: Vec<String> = Vec::new();
+
+    for item in &status.budgets {
+        // 同一次检查跨过多个阈值时只提醒最高的一个
+        let crossed: Vec<u32> = budget
+            .alert_thresholds
+            .iter()
+            .copied()
+            .filter(|t| item.percent >= *t as f64)
+            .filter(|t| !sent.contains(&alert_key(&status.period_start, &item.scope, *t)))
+            .collect();
+        if let Some(threshold) = crossed.iter().max() {
+            notify(
+                app,
+                "OpenClaw 费用提醒",
+                &format!(
+                    "{} 本周期已花费 {:.2}，达到预算 {:.2} 的 {}%",
+                    scope_label(&item.scope),
+                    item.spent,
+                    item.limit,
+                    threshold
+                ),
+            );
+        }
+        for threshold in crossed {
+            let key = alert_key(&status.period_start, &item.scope, threshold);
+            sent.push(key.clone());
+            added.push(key);
+            changed = true;
+        }
+
+        let exceeded_key = format!("{}exceeded|{}", prefix, item.scope);
+        if item.percent >= 100.0 && !sent.contains(&exceeded_key) {
+            warn!("[预算] ✗ {} 已超出预算: {:.4} / {:.4}", item.scope, item.spent, item.limit);
+            apply_action(app, &profile, &budget, &item.scope);
+            sent.push(exceeded_key.clone());
+            added.push(exceeded_key);
+            changed = true;
+        }
+    }
+
+    if changed {
+        // 在锁内重新读取并合并；档案已被删除时不写回，避免恢复已删除档案的记录
+        let result = settings::update(|manager_settings| {
+            if !manager_settings.profiles.iter().any(|p| p.name == profile.name) {
+                return Err(format!("档案 {} 已删除", profile.name));
+            }
+            let entry = manager_settings
+                .budget_alerts_sent
+                .entry(profile.name.clone())
+                .or_default();
+            entry.retain(|key| key.starts_with(&prefix));
+            for key in added {
+                if !entry.contains(&key) {
+                    entry.push(key);
+                }
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            warn!("[预算] 保存提醒记录失败: {}", e);
+        }
+    }
+}
+
+fn alert_key(period_start: &str, scope: &str, threshold: u32) -> String {
+    format!("{}|{}|{}", period_start, scope, threshold)
+}
+
+fn scope_label(scope: &str) -> String {
+    if scope == GLOBAL_SCOPE {
+        "全部 Provider".to_string()
+    } else {
+        format!("Provider {}", scope)
+    }
+}
+
+fn notify(app: &AppHandle, title: &str, body: &str) {
+    info!("[预算] 发送通知: {}", body);
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        warn!("[预算] 发送通知失败: {}", e);
+    }
+}
+
+/// 执行超出预算后的动作
+fn apply_action(app: &AppHandle, profile: &Profile, budget: &BudgetSettings, scope: &str) {
+    match budget.action {
+        BudgetAction::Notify => {}
+        BudgetAction::SwitchModel => match switch_to_cheaper_model(profile, budget, scope) {
+            Ok(Some(model)) => notify(
+                app,
+                "OpenClaw 已切换模型",
+                &format!("{} 超出预算，主模型已切换为 {}", scope_label(scope), model),
+            ),
+            Ok(None) => {}
+            Err(e) => warn!("[预算] 切换主模型失败: {}", e),
+        },
+        BudgetAction::StopGateway => {
+            match gateway::stop(&app.state::<GatewaySupervisor>()) {
+                Ok(_) => notify(
+                    app,
+                    "OpenClaw 已停止",
+                    &format!("{} 超出预算，Gateway 已停止", scope_label(scope)),
+                ),
+                Err(e) => warn!("[预算] 停止 Gateway 失败: {}", e),
+            }
+        }
+    }
+}
+
+/// 将主模型切换为设置的便宜模型，返回切换后的模型；无需切换时返回 None
+/// Provider 预算超出时，仅当主模型属于该 Provider 才切换
+fn switch_to_cheaper_model(
+    profile: &Profile,
+    budget: &BudgetSettings,
+    scope: &str,
+) -> Result<Option<String>, String> {
+    let Some(cheaper) = budget.cheaper_model.clone() else {
+        return Err("未设置要切换到的模型".to_string());
+    };
+
+    let (mut config, revision) = config_store::load_typed_for(profile)?;
+    let primary = config.agents.defaults.model.primary.clone().unwrap_or_default();
+    if primary == cheaper {
+        return Ok(None);
+    }
+    if scope != GLOBAL_SCOPE && !primary.starts_with(&format!("{}/", scope)) {
+        return Ok(None);
+    }
+
+    let model = &mut config.agents.defaults.model;
+    model.primary = Some(cheaper.clone());
+    model.fallbacks.retain(|m| *m != cheaper);
+    config_store::save_typed_for(profile, &config, Some(&revision))?;
+    info!("[预算] ✓ 主模型已从 {} 切换为 {}", primary, cheaper);
+    Ok(Some(cheaper))
+}
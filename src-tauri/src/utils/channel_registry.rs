@@ -0,0 +1,136 @@
+use crate::models::{ChannelDescriptor, DEFAULT_CHANNEL_ACCOUNT};
+use crate::utils::platform;
+use log::warn;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// 内置渠道描述
+const BUILTIN_CHANNELS: &str = include_str!("../../resources/channels.json");
+
+/// 用户渠道描述目录：<管理器目录>/channels，每个 .json 文件包含一个描述或描述数组
+pub fn user_dir() -> String {
+    Path::new(&platform::get_manager_dir())
+        .join("channels")
+        .display()
+        .to_string()
+}
+
+/// 所有渠道描述：内置描述在前，用户描述追加在后；ID 相同的用户描述覆盖内置描述
+pub fn all() -> Vec<ChannelDescriptor> {
+    let mut channels: Vec<ChannelDescriptor> =
+        serde_json::from_str(BUILTIN_CHANNELS).expect("内置渠道描述格式错误");
+
+    for mut descriptor in load_user_descriptors() {
+        descriptor.custom = true;
+        match channels.iter_mut().find(|c| c.id == descriptor.id) {
+            Some(existing) => *existing = descriptor,
+            None => channels.push(descriptor),
+        }
+    }
+    channels
+}
+
+/// 按 ID 查找渠道描述（不区分大小写）
+pub fn get(id: &str) -> Option<ChannelDescriptor> {
+    all().into_iter().find(|c| c.id.eq_ignore_ascii_case(id))
+}
+
+/// 按 ID 查找渠道描述，找不到时返回错误
+pub fn require(id: &str) -> Result<ChannelDescriptor, String> {
+    get(id).ok_or_else(|| {
+        format!(
+            "未知的渠道: {}（可在 {} 中添加渠道描述）",
+            id,
+            user_dir()
+        )
+    })
+}
+
+/// 检查账号名称：只允许字母、数字、- 和 _
+pub fn check_account_name(account: &str) -> Result<(), String> {
+    let valid = !account.is_empty()
+        && account
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("账号名称无效: {}（只能包含字母、数字、- 和 _）", account))
+    }
+}
+
+/// 渠道配置 ID：默认账号为渠道类型，其他账号为 <渠道类型>:<账号>
+pub fn config_id(channel: &str, account: &str) -> String {
+    if account == DEFAULT_CHANNEL_ACCOUNT {
+        channel.to_string()
+    } else {
+        format!("{}:{}", channel, account)
+    }
+}
+
+/// 日志和提示中使用的名称：默认账号为渠道类型，其他账号为 <渠道类型> (<账号>)
+pub fn account_label(channel: &str, account: &str) -> String {
+    if account == DEFAULT_CHANNEL_ACCOUNT {
+        channel.to_string()
+    } else {
+        format!("{} ({})", channel, account)
+    }
+}
+
+fn load_user_descriptors() -> Vec<ChannelDescriptor> {
+    let Ok(entries) = fs::read_dir(user_dir()) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+
+    let mut descriptors = Vec::new();
+    for path in paths {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<Value>(&content).map_err(|e| e.to_string()))
+            .and_then(|value| {
+                let items = match value {
+                    Value::Array(items) => items,
+                    other => vec![other],
+                };
+                items
+                    .into_iter()
+                    .map(serde_json::from_value::<ChannelDescriptor>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e.to_string())
+            });
+        match parsed {
+            Ok(items) => {
+                for descriptor in items {
+                    match check_channel_id(&descriptor.id) {
+                        Ok(()) => descriptors.push(descriptor),
+                        Err(e) => warn!("[渠道] 忽略渠道描述 {}: {}", path.display(), e),
+                    }
+                }
+            }
+            Err(e) => warn!("[渠道] 忽略无效的渠道描述 {}: {}", path.display(), e),
+        }
+    }
+    descriptors
+}
+
+/// 检查渠道 ID：只允许小写字母、数字和 _
+/// ID 会出现在 bash 脚本、临时文件路径和环境变量名中
+fn check_channel_id(id: &str) -> Result<(), String> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("渠道 ID 无效: {}（只能包含小写字母、数字和 _）", id))
+    }
+}
@@ -0,0 +1,231 @@
+use crate::models::{ChannelDescriptor, ConfigIssue};
+use crate::utils::config_validator::{index_path, key_path};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// 按渠道描述中的 JSON Schema 校验渠道配置，返回字段级问题（path 为字段名，如 botToken、allowFrom[0]）
+/// 没有 schema 的渠道（如未提供 schema 的用户描述）不做校验
+/// schema 不可能列全 OpenClaw 支持的字段，未知字段只给出警告；与已知字段拼写相近的才视为错误
+pub fn validate(descriptor: &ChannelDescriptor, config: &HashMap<String, Value>) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    if let Some(schema) = &descriptor.schema {
+        let object: Map<String, Value> = config
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        validate_value(schema, &Value::Object(object), "", &mut issues);
+        issues.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    issues
+}
+
+/// 校验单个值。支持 JSON Schema 的常用子集：
+/// type、enum、pattern、minLength、maxLength、minimum、maximum、
+/// properties、required、additionalProperties、items、anyOf，以及 errorMessage（自定义提示）
+fn validate_value(schema: &Value, value: &Value, path: &str, issues: &mut Vec<ConfigIssue>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let before = issues.len();
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| type_matches(t, value)) {
+            issues.push(error(path, format!("类型应为 {}", types.join(" 或 "))));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(display).collect();
+            issues.push(error(path, format!("取值应为 {} 之一", allowed.join(" / "))));
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        let length = text.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(|v| v.as_u64()) {
+            if length < min {
+                issues.push(error(path, format!("长度不能少于 {} 个字符", min)));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(|v| v.as_u64()) {
+            if length > max {
+                issues.push(error(path, format!("长度不能超过 {} 个字符", max)));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+            match Regex::new(pattern) {
+                Ok(re) if !re.is_match(text) => {
+                    issues.push(error(path, format!("格式不正确（应匹配 {}）", pattern)))
+                }
+                Ok(_) => {}
+                Err(e) => issues.push(error(path, format!("schema 中的正则无效: {}", e))),
+            }
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|v| v.as_f64()) {
+            if number < min {
+                issues.push(error(path, format!("不能小于 {}", min)));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(|v| v.as_f64()) {
+            if number > max {
+                issues.push(error(path, format!("不能大于 {}", max)));
+            }
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("anyOf") {
+        let matched = options.iter().any(|option| {
+            let mut nested = Vec::new();
+            validate_value(option, value, path, &mut nested);
+            !has_errors(&nested)
+        });
+        if !matched {
+            issues.push(error(path, "不符合任何允许的格式"));
+        }
+    }
+
+    // 自定义提示替换该字段自身的所有问题（不影响子字段）
+    if issues.len() > before {
+        if let Some(message) = schema.get("errorMessage").and_then(|v| v.as_str()) {
+            issues.truncate(before);
+            issues.push(error(path, message));
+        }
+    }
+
+    match value {
+        Value::Object(object) => validate_object(schema, object, path, issues),
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_value(item_schema, item, &index_path(path, i), issues);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(|v| v.as_object())
+        .unwrap_or(&empty);
+
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(|k| k.as_str()) {
+            let missing = match object.get(key) {
+                None | Some(Value::Null) => true,
+                Some(Value::String(s)) => s.trim().is_empty(),
+                Some(_) => false,
+            };
+            if missing {
+                issues.push(error(&key_path(path, key), "必填"));
+            }
+        }
+    }
+
+    for (key, value) in object {
+        let field_path = key_path(path, key);
+        if let Some(property) = properties.get(key) {
+            validate_value(property, value, &field_path, issues);
+            continue;
+        }
+        match schema.get("additionalProperties") {
+            Some(Value::Bool(false)) => match suggest(key, properties.keys()) {
+                Some(known) => {
+                    issues.push(error(&field_path, format!("未知字段 {}，是否应为 {}？", key, known)))
+                }
+                None => issues.push(warning(
+                    &field_path,
+                    format!("未知字段 {}，管理器无法校验，将原样保存", key),
+                )),
+            },
+            Some(additional @ Value::Object(_)) => {
+                validate_value(additional, value, &field_path, issues)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// 是否包含错误（警告不影响保存）
+pub fn has_errors(issues: &[ConfigIssue]) -> bool {
+    issues.iter().any(|issue| issue.severity == "error")
+}
+
+fn error(path: &str, message: impl Into<String>) -> ConfigIssue {
+    ConfigIssue {
+        path: path.to_string(),
+        message: message.into(),
+        severity: "error".to_string(),
+    }
+}
+
+fn warning(path: &str, message: impl Into<String>) -> ConfigIssue {
+    ConfigIssue {
+        path: path.to_string(),
+        message: message.into(),
+        severity: "warning".to_string(),
+    }
+}
+
+/// 为未知字段找一个拼写相近的已知字段（忽略大小写的编辑距离不超过 2）
+fn suggest<'a>(key: &str, known: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    let key = key.to_lowercase();
+    known
+        .map(|k| (edit_distance(&key, &k.to_lowercase()), k.as_str()))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| k)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,311 @@
+use crate::models::{
+    AgentsConfig, ConfigBackup, GatewayConfig, MetaConfig, ModelsConfig, OpenClawConfig,
+    PluginsConfig, Profile, ProviderConfig,
+};
+use crate::utils::{config_watcher, file, settings};
+use chrono::{Local, NaiveDateTime, TimeZone};
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 保留的备份数量
+const MAX_BACKUPS: usize = 20;
+/// 备份文件名中的时间格式
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+/// 备份文件名前缀与后缀
+const BACKUP_PREFIX: &str = "openclaw.json.";
+const BACKUP_SUFFIX: &str = ".bak";
+
+/// 备份目录：<配置目录>/backups
+fn backup_dir(profile: &Profile) -> PathBuf {
+    Path::new(&profile.config_dir).join("backups")
+}
+
+/// 读取 openclaw.json，文件不存在时返回空对象
+pub fn load() -> Result<Value, String> {
+    load_with_revision().map(|(config, _)| config)
+}
+
+/// 读取 openclaw.json 及其版本（内容哈希，文件不存在时为空字符串）
+pub fn load_with_revision() -> Result<(Value, String), String> {
+    load_with_revision_for(&settings::active_profile())
+}
+
+/// 读取指定档案的 openclaw.json 及其版本
+fn load_with_revision_for(profile: &Profile) -> Result<(Value, String), String> {
+    let config_path = profile.config_file_path();
+
+    if !file::file_exists(&config_path) {
+        return Ok((json!({}), String::new()));
+    }
+
+    let content =
+        file::read_file(&config_path).map_err(|e| format!("读取配置文件失败: {}", e))?;
+    let revision = config_watcher::revision_of(content.as_bytes());
+
+    let config =
+        serde_json::from_str(&content).map_err(|e| format!("解析配置文件失败: {}", e))?;
+    Ok((config, revision))
+}
+
+/// 获取 openclaw.json 当前版本
+pub fn revision() -> String {
+    config_watcher::file_revision(&settings::active_profile().config_file_path())
+}
+
+/// 保存 openclaw.json：先备份当前文件，再原子写入
+/// `expected_revision` 不为空时，文件版本不一致（已被外部修改）则拒绝保存
+fn save_for(profile: &Profile, config: &Value, expected_revision: Option<&str>) -> Result<(), String> {
+    let content =
+        serde_json::to_string_pretty(config).map_err(|e| format!("序列化配置失败: {}", e))?;
+    write_with_backup(profile, &content, expected_revision)
+}
+
+/// 读取并解析为类型化配置及其版本（未建模的字段保存在各层的 extra 中）
+pub fn load_typed_with_revision() -> Result<(OpenClawConfig, String), String> {
+    load_typed_for(&settings::active_profile())
+}
+
+/// 读取指定档案的类型化配置及其版本
+pub fn load_typed_for(profile: &Profile) -> Result<(OpenClawConfig, String), String> {
+    let (value, revision) = load_with_revision_for(profile)?;
+    Ok((parse_typed(value)?, revision))
+}
+
+/// 读取类型化配置用于修改，返回配置及读取时的版本
+/// 传入前端持有的版本时，若文件已被外部修改则拒绝，避免基于过期数据覆盖
+pub fn load_typed_for_update(expected_revision: Option<&str>) -> Result<(OpenClawConfig, String), String> {
+    let (config, revision) = load_typed_with_revision()?;
+    if expected_revision.is_some_and(|expected| expected != revision) {
+        warn!("[配置] 前端持有的配置版本已过期，拒绝修改");
+        return Err(conflict_error());
+    }
+    Ok((config, revision))
+}
+
+/// 把配置 JSON 解析为类型化配置；存在类型错误时宽松解析，无法解析的节点保存时原样写回
+pub fn parse_typed(value: Value) -> Result<OpenClawConfig, String> {
+    match serde_json::from_value(value.clone()) {
+        Ok(config) => Ok(config),
+        Err(e) => {
+            warn!("[配置读取] 配置存在类型错误，跳过无法解析的节点: {}", e);
+            parse_lenient(value)
+        }
+    }
+}
+
+/// 顶层配置段及检查该段能否解析的函数
+type SectionCheck = (&'static str, fn(&Value) -> bool);
+
+/// 宽松解析：把无法解析的节点（顶层各段、单个 Provider）取出放入 `unparsed`，
+/// 其余部分照常解析。具体错误由 validate_config 报告，保存时原样写回这些节点
+fn parse_lenient(mut value: Value) -> Result<OpenClawConfig, String> {
+    fn fits<T: DeserializeOwned>(value: &Value) -> bool {
+        serde_json::from_value::<T>(value.clone()).is_ok()
+    }
+
+    let mut unparsed = Vec::new();
+    let Some(root) = value.as_object_mut() else {
+        return Err("配置文件格式错误: 根节点必须是 JSON 对象".to_string());
+    };
+
+    if let Some(Value::Object(providers)) = root
+        .get_mut("models")
+        .and_then(|m| m.get_mut("providers"))
+    {
+        let broken: Vec<String> = providers
+            .iter()
+            .filter(|(_, p)| !fits::<ProviderConfig>(p))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in broken {
+            if let Some(raw) = providers.remove(&name) {
+                unparsed.push((vec!["models".to_string(), "providers".to_string(), name], raw));
+            }
+        }
+    }
+
+    let sections: [SectionCheck; 6] = [
+        ("agents", fits::<AgentsConfig>),
+        ("models", fits::<ModelsConfig>),
+        ("gateway", fits::<GatewayConfig>),
+        ("plugins", fits::<PluginsConfig>),
+        ("meta", fits::<MetaConfig>),
+        ("channels", Value::is_object),
+    ];
+    for (key, check) in sections {
+        if root.get(key).is_some_and(|section| !check(section)) {
+            if let Some(raw) = root.remove(key) {
+                unparsed.push((vec![key.to_string()], raw));
+            }
+        }
+    }
+
+    for (path, _) in &unparsed {
+        warn!("[配置读取] 无法解析 {}，保存时原样保留", path.join("."));
+    }
+    let mut config: OpenClawConfig =
+        serde_json::from_value(value).map_err(|e| format!("配置文件格式错误: {}", e))?;
+    config.unparsed = unparsed;
+    Ok(config)
+}
+
+/// 将读取时无法解析的节点写回原位置（覆盖类型化配置在该位置生成的默认值）
+fn restore_unparsed(value: &mut Value, unparsed: &[(Vec<String>, Value)]) {
+    for (path, raw) in unparsed {
+        let Some((last, parents)) = path.split_last() else {
+            continue;
+        };
+        let mut node = &mut *value;
+        for key in parents {
+            if !node.is_object() {
+                *node = json!({});
+            }
+            node = &mut node[key.as_str()];
+        }
+        if !node.is_object() {
+            *node = json!({});
+        }
+        node[last.as_str()] = raw.clone();
+    }
+}
+
+/// 保存类型化配置
+pub fn save_typed(config: &OpenClawConfig, expected_revision: Option<&str>) -> Result<(), String> {
+    save_typed_for(&settings::active_profile(), config, expected_revision)
+}
+
+/// 保存指定档案的类型化配置
+pub fn save_typed_for(
+    profile: &Profile,
+    config: &OpenClawConfig,
+    expected_revision: Option<&str>,
+) -> Result<(), String> {
+    let mut value =
+        serde_json::to_value(config).map_err(|e| format!("序列化配置失败: {}", e))?;
+    restore_unparsed(&mut value, &config.unparsed);
+    save_for(profile, &value, expected_revision)
+}
+
+/// 版本冲突时的错误信息
+pub fn conflict_error() -> String {
+    "配置文件已被其他程序修改，请刷新后重试".to_string()
+}
+
+/// 备份当前配置后原子写入新内容，内容未变化时跳过
+fn write_with_backup(
+    profile: &Profile,
+    content: &str,
+    expected_revision: Option<&str>,
+) -> Result<(), String> {
+    let config_path = profile.config_file_path();
+    let current = file::read_file(&config_path).ok();
+
+    if let Some(expected) = expected_revision {
+        let actual = current
+            .as_deref()
+            .map(|c| config_watcher::revision_of(c.as_bytes()))
+            .unwrap_or_default();
+        if actual != expected {
+            warn!("[配置保存] 版本冲突，拒绝保存 (期望 {}, 实际 {})", expected, actual);
+            return Err(conflict_error());
+        }
+    }
+
+    if let Some(current) = current {
+        if current == content {
+            return Ok(());
+        }
+        backup_current(profile, &current)?;
+    }
+
+    file::write_file_atomic(&config_path, content)
+        .map_err(|e| format!("写入配置文件失败: {}", e))?;
+    Ok(())
+}
+
+/// 将当前配置内容写入新的备份文件，并清理多余的旧备份
+fn backup_current(profile: &Profile, current: &str) -> Result<(), String> {
+    let dir = backup_dir(profile);
+    let name = format!(
+        "{}{}{}",
+        BACKUP_PREFIX,
+        Local::now().format(BACKUP_TIME_FORMAT),
+        BACKUP_SUFFIX
+    );
+    let path = dir.join(&name);
+
+    file::write_file_atomic(&path.display().to_string(), current)
+        .map_err(|e| format!("备份配置文件失败: {}", e))?;
+    info!("[配置备份] 已备份到 {}", path.display());
+
+    prune_backups(profile);
+    Ok(())
+}
+
+/// 只保留最新的 MAX_BACKUPS 个备份
+fn prune_backups(profile: &Profile) {
+    for backup in list_backups_for(profile).into_iter().skip(MAX_BACKUPS) {
+        if let Err(e) = fs::remove_file(&backup.path) {
+            warn!("[配置备份] 删除旧备份 {} 失败: {}", backup.name, e);
+        }
+    }
+}
+
+/// 从备份文件名解析备份时间
+fn backup_time(name: &str) -> Option<String> {
+    let stamp = name.strip_prefix(BACKUP_PREFIX)?.strip_suffix(BACKUP_SUFFIX)?;
+    let naive = NaiveDateTime::parse_from_str(stamp, BACKUP_TIME_FORMAT).ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|t| t.to_rfc3339())
+}
+
+/// 列出当前档案的所有备份（最新的在前）
+pub fn list_backups() -> Vec<ConfigBackup> {
+    list_backups_for(&settings::active_profile())
+}
+
+fn list_backups_for(profile: &Profile) -> Vec<ConfigBackup> {
+    let Ok(entries) = fs::read_dir(backup_dir(profile)) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<ConfigBackup> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let created_at = backup_time(&name)?;
+            let meta = entry.metadata().ok()?;
+            Some(ConfigBackup {
+                path: entry.path().display().to_string(),
+                name,
+                created_at,
+                size_bytes: meta.len(),
+            })
+        })
+        .collect();
+
+    // 文件名中的时间戳定长，按名称倒序即按时间倒序
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    backups
+}
+
+/// 用指定备份恢复 openclaw.json（恢复前会先备份当前配置）
+pub fn restore_backup(name: &str) -> Result<(), String> {
+    let profile = settings::active_profile();
+    let backup = list_backups_for(&profile)
+        .into_iter()
+        .find(|b| b.name == name)
+        .ok_or_else(|| format!("备份不存在: {}", name))?;
+
+    let content =
+        file::read_file(&backup.path).map_err(|e| format!("读取备份失败: {}", e))?;
+    serde_json::from_str::<Value>(&content)
+        .map_err(|e| format!("备份内容不是有效的 JSON: {}", e))?;
+
+    write_with_backup(&profile, &content, None)
+}
@@ -0,0 +1,224 @@
+use crate::models::{
+    AgentsConfig, ConfigIssue, GatewayConfig, MetaConfig, ModelConfig, OpenClawConfig,
+    PluginsConfig, ProviderConfig,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// 拼接对象字段路径，包含 `.` `/` 等特殊字符的键使用 ["key"] 形式
+pub fn key_path(parent: &str, key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    match (parent.is_empty(), plain) {
+        (true, true) => key.to_string(),
+        (false, true) => format!("{}.{}", parent, key),
+        (_, false) => format!("{}[\"{}\"]", parent, key),
+    }
+}
+
+/// 拼接数组下标路径
+pub fn index_path(parent: &str, index: usize) -> String {
+    format!("{}[{}]", parent, index)
+}
+
+fn error(path: String, message: impl Into<String>) -> ConfigIssue {
+    ConfigIssue {
+        path,
+        message: message.into(),
+        severity: "error".to_string(),
+    }
+}
+
+fn warning(path: String, message: impl Into<String>) -> ConfigIssue {
+    ConfigIssue {
+        path,
+        message: message.into(),
+        severity: "warning".to_string(),
+    }
+}
+
+/// 校验 openclaw.json，返回所有问题（为空表示通过）
+pub fn validate(value: &Value) -> Vec<ConfigIssue> {
+    if !value.is_object() {
+        return vec![error("$".to_string(), "配置根节点必须是 JSON 对象")];
+    }
+
+    match serde_json::from_value::<OpenClawConfig>(value.clone()) {
+        Ok(config) => validate_typed(&config),
+        // 类型不匹配时逐段解析，定位到具体路径
+        Err(_) => locate_type_errors(value),
+    }
+}
+
+/// 逐段尝试解析，记录无法解析的节点
+fn locate_type_errors(value: &Value) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    fn check<T: DeserializeOwned>(value: Option<&Value>, path: &str, issues: &mut Vec<ConfigIssue>) -> bool {
+        match value {
+            Some(v) => match serde_json::from_value::<T>(v.clone()) {
+                Ok(_) => true,
+                Err(e) => {
+                    issues.push(error(path.to_string(), format!("类型错误: {}", e)));
+                    false
+                }
+            },
+            None => true,
+        }
+    }
+
+    check::<AgentsConfig>(value.get("agents"), "agents", &mut issues);
+    check::<GatewayConfig>(value.get("gateway"), "gateway", &mut issues);
+    check::<PluginsConfig>(value.get("plugins"), "plugins", &mut issues);
+    check::<MetaConfig>(value.get("meta"), "meta", &mut issues);
+
+    if let Some(channels) = value.get("channels") {
+        if !channels.is_object() {
+            issues.push(error("channels".to_string(), "必须是对象"));
+        }
+    }
+
+    match value.pointer("/models/providers") {
+        Some(Value::Object(providers)) => {
+            for (name, provider) in providers {
+                let provider_path = key_path("models.providers", name);
+                if check::<ProviderConfig>(Some(provider), &provider_path, &mut issues) {
+                    continue;
+                }
+                // Provider 整体解析失败时，再定位到具体模型
+                if let Some(Value::Array(models)) = provider.get("models") {
+                    for (i, model) in models.iter().enumerate() {
+                        let model_path = index_path(&key_path(&provider_path, "models"), i);
+                        check::<ModelConfig>(Some(model), &model_path, &mut issues);
+                    }
+                }
+            }
+        }
+        Some(_) => issues.push(error("models.providers".to_string(), "必须是对象")),
+        None => {}
+    }
+
+    if issues.is_empty() {
+        issues.push(error("$".to_string(), "配置无法解析"));
+    }
+    issues
+}
+
+/// 对已成功解析的配置做语义校验
+fn validate_typed(config: &OpenClawConfig) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let mut defined_models: HashSet<String> = HashSet::new();
+
+    // Provider 与模型
+    for (name, provider) in &config.models.providers {
+        let provider_path = key_path("models.providers", name);
+
+        let base_url = provider.base_url.trim();
+        if base_url.is_empty() {
+            issues.push(error(key_path(&provider_path, "baseUrl"), "缺少 baseUrl"));
+        } else if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            issues.push(warning(
+                key_path(&provider_path, "baseUrl"),
+                format!("baseUrl 不是 http(s) 地址: {}", base_url),
+            ));
+        }
+
+        if provider.models.is_empty() {
+            issues.push(warning(key_path(&provider_path, "models"), "没有配置任何模型"));
+        }
+
+        let mut seen = HashSet::new();
+        for (i, model) in provider.models.iter().enumerate() {
+            let model_path = index_path(&key_path(&provider_path, "models"), i);
+            if model.id.trim().is_empty() {
+                issues.push(error(key_path(&model_path, "id"), "缺少模型 id"));
+                continue;
+            }
+            if !seen.insert(model.id.as_str()) {
+                issues.push(warning(
+                    key_path(&model_path, "id"),
+                    format!("模型 id 重复: {}", model.id),
+                ));
+            }
+            defined_models.insert(format!("{}/{}", name, model.id));
+        }
+    }
+
+    // 主模型必须由某个 Provider 定义
+    if let Some(primary) = config.agents.defaults.model.primary.as_deref() {
+        let path = "agents.defaults.model.primary".to_string();
+        if !primary.contains('/') {
+            issues.push(error(path, format!("主模型格式应为 provider/model: {}", primary)));
+        } else if !defined_models.contains(primary) {
+            issues.push(error(path, format!("主模型 {} 未在任何 Provider 中定义", primary)));
+        }
+    }
+
+    // 备用模型同样必须有定义，且不应与主模型或彼此重复
+    let mut seen_fallbacks = HashSet::new();
+    for (i, fallback) in config.agents.defaults.model.fallbacks.iter().enumerate() {
+        let path = index_path("agents.defaults.model.fallbacks", i);
+        if !defined_models.contains(fallback) {
+            issues.push(error(path, format!("备用模型 {} 未在任何 Provider 中定义", fallback)));
+        } else if config.agents.defaults.model.primary.as_deref() == Some(fallback.as_str())
+            || !seen_fallbacks.insert(fallback.as_str())
+        {
+            issues.push(warning(path, format!("备用模型重复: {}", fallback)));
+        }
+    }
+
+    // 可用模型列表中的模型也应有定义
+    for model_id in config.agents.defaults.models.keys() {
+        if !defined_models.contains(model_id) {
+            issues.push(warning(
+                key_path("agents.defaults.models", model_id),
+                format!("模型 {} 未在任何 Provider 中定义", model_id),
+            ));
+        }
+    }
+
+    // 插件白名单与插件条目
+    for (i, plugin_id) in config.plugins.allow.iter().enumerate() {
+        if !config.plugins.entries.contains_key(plugin_id) {
+            issues.push(error(
+                index_path("plugins.allow", i),
+                format!("插件 {} 在 allow 中但没有对应的 plugins.entries 条目", plugin_id),
+            ));
+        }
+    }
+    for (plugin_id, entry) in &config.plugins.entries {
+        let enabled = entry.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false);
+        if enabled && !config.plugins.allow.contains(plugin_id) {
+            issues.push(warning(
+                key_path("plugins.entries", plugin_id),
+                format!("插件 {} 已启用但不在 plugins.allow 中", plugin_id),
+            ));
+        }
+    }
+
+    // 网关
+    if config.gateway.port == Some(0) {
+        issues.push(error("gateway.port".to_string(), "端口无效: 0"));
+    }
+    if let Some(auth) = &config.gateway.auth {
+        let token_empty = auth.token.as_deref().map(str::trim).unwrap_or("").is_empty();
+        if auth.mode.as_deref() == Some("token") && token_empty {
+            issues.push(error(
+                "gateway.auth.token".to_string(),
+                "认证模式为 token 但未设置 token",
+            ));
+        }
+    }
+
+    // 渠道配置必须是对象
+    for (channel_id, channel) in &config.channels {
+        if !channel.is_object() {
+            issues.push(error(key_path("channels", channel_id), "渠道配置必须是对象"));
+        }
+    }
+
+    issues
+}
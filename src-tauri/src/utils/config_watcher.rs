@@ -0,0 +1,124 @@
+use crate::utils::platform;
+use log::{info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 配置文件被外部修改时推送的事件名
+pub const CONFIG_CHANGED_EVENT: &str = "config-file-changed";
+
+/// 文件检查间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 已知的文件版本：路径 -> 内容哈希
+/// 管理器自身写入后会更新此表，避免把自己的保存当成外部修改
+static KNOWN_REVISIONS: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+/// 推送给前端的文件变更
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigFileChange {
+    /// 文件类型：config（openclaw.json）或 env
+    pub kind: String,
+    /// 文件路径
+    pub path: String,
+    /// 变更后的版本
+    pub revision: String,
+}
+
+/// 计算内容版本（SHA-256 十六进制）
+pub fn revision_of(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 读取文件当前版本，文件不存在时为空字符串
+pub fn file_revision(path: &str) -> String {
+    std::fs::read(path)
+        .map(|content| revision_of(&content))
+        .unwrap_or_default()
+}
+
+fn known_revisions() -> std::sync::MutexGuard<'static, Option<HashMap<String, String>>> {
+    KNOWN_REVISIONS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 记录管理器即将写入的内容版本，须在新内容替换原文件之前调用，
+/// 否则监听线程可能先看到新内容而把自己的写入当成外部修改
+pub fn note_self_write(path: &str, content: &[u8]) {
+    record_revision(path, revision_of(content));
+}
+
+/// 重新记录文件当前的版本（写入失败时撤销 note_self_write 记录的版本）
+pub fn note_current(path: &str) {
+    record_revision(path, file_revision(path));
+}
+
+fn record_revision(path: &str, revision: String) {
+    known_revisions()
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_string(), revision);
+}
+
+/// 启动后台监听线程，配置文件或 env 文件被外部修改时推送 `config-file-changed` 事件
+pub fn spawn(app: AppHandle) {
+    thread::Builder::new()
+        .name("config-watcher".to_string())
+        .spawn(move || watch_loop(app))
+        .expect("无法启动配置监听线程");
+}
+
+fn watch_loop(app: AppHandle) {
+    let mut watched: Vec<(String, String)> = Vec::new();
+
+    loop {
+        let current = vec![
+            ("config".to_string(), platform::get_config_file_path()),
+            ("env".to_string(), platform::get_env_file_path()),
+        ];
+
+        // 首次启动或切换配置档案：只记录当前版本，不推送
+        if current != watched {
+            let mut known = known_revisions();
+            let map = known.get_or_insert_with(HashMap::new);
+            for (_, path) in &current {
+                map.insert(path.clone(), file_revision(path));
+            }
+            info!("[配置监听] 开始监听 {:?}", current.iter().map(|(_, p)| p).collect::<Vec<_>>());
+            watched = current;
+        }
+
+        for (kind, path) in &watched {
+            let revision = file_revision(path);
+            let changed = {
+                let mut known = known_revisions();
+                let map = known.get_or_insert_with(HashMap::new);
+                if map.get(path) == Some(&revision) {
+                    false
+                } else {
+                    map.insert(path.clone(), revision.clone());
+                    true
+                }
+            };
+
+            if changed {
+                info!("[配置监听] 检测到外部修改: {}", path);
+                let change = ConfigFileChange {
+                    kind: kind.clone(),
+                    path: path.clone(),
+                    revision,
+                };
+                if let Err(e) = app.emit(CONFIG_CHANGED_EVENT, &change) {
+                    warn!("[配置监听] 推送事件失败: {}", e);
+                }
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::utils::file;
+use std::io;
+
+/// env 文件中的一行或一个跨行的赋值
+#[derive(Debug, Clone)]
+enum EnvLine {
+    /// 注释、空行或无法识别的内容，原样保留
+    Raw(String),
+    /// 变量赋值
+    Entry(EnvEntry),
+}
+
+#[derive(Debug, Clone)]
+struct EnvEntry {
+    key: String,
+    value: String,
+    /// 是否带 `export` 前缀
+    export: bool,
+    /// 值之后的行尾注释（含前面的空白，如 ` # keep`），重新生成赋值行时保留
+    comment: Option<String>,
+    /// 原始文本（可能跨多行）；被修改后为 None，写出时重新生成
+    raw: Option<String>,
+}
+
+/// 兼容 shell `source` 和 dotenv 的 env 文件（如 ~/.openclaw/env）
+///
+/// 支持 `export KEY=...` 和 `KEY=...`、单引号、双引号、反斜杠转义、跨行的引号值和行尾注释。
+/// 未修改的行原样写回，注释、空行和原有的引号风格都不会丢失。
+/// 不做变量展开：`"$HOME"` 读出的值就是 `$HOME`。
+#[derive(Debug, Clone, Default)]
+pub struct EnvFile {
+    lines: Vec<EnvLine>,
+    /// 原文件是否以换行结尾
+    trailing_newline: bool,
+}
+
+impl EnvFile {
+    /// 解析 env 文件内容
+    pub fn parse(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut pos = 0;
+
+        while pos < content.len() {
+            let line_end = content[pos..]
+                .find('\n')
+                .map(|i| pos + i)
+                .unwrap_or(content.len());
+
+            match parse_entry(content, pos) {
+                Some(ParsedEntry { key, value, export, comment, end }) => {
+                    lines.push(EnvLine::Entry(EnvEntry {
+                        key,
+                        value,
+                        export,
+                        comment,
+                        raw: Some(content[pos..end].to_string()),
+                    }));
+                    pos = end + 1;
+                }
+                None => {
+                    lines.push(EnvLine::Raw(content[pos..line_end].to_string()));
+                    pos = line_end + 1;
+                }
+            }
+        }
+
+        Self {
+            lines,
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+        }
+    }
+
+    /// 读取 env 文件，文件不存在时为空
+    pub fn load(path: &str) -> io::Result<Self> {
+        match file::read_file(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 原子写入 env 文件
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        file::write_file_atomic(path, &self.serialize())
+    }
+
+    /// 获取变量值（与 shell 一致，重复赋值时以最后一次为准）
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries()
+            .filter(|e| e.key == key)
+            .last()
+            .map(|e| e.value.as_str())
+    }
+
+    /// 所有变量（按首次出现的顺序，重复赋值取最后一次的值）
+    pub fn vars(&self) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = Vec::new();
+        for entry in self.entries() {
+            match vars.iter_mut().find(|(k, _)| *k == entry.key) {
+                Some((_, value)) => *value = entry.value.clone(),
+                None => vars.push((entry.key.clone(), entry.value.clone())),
+            }
+        }
+        vars
+    }
+
+    /// 设置变量：更新生效的那一次赋值，不存在时以 `export KEY="..."` 追加到末尾
+    pub fn set(&mut self, key: &str, value: &str) {
+        let existing = self.lines.iter_mut().rev().find_map(|line| match line {
+            EnvLine::Entry(entry) if entry.key == key => Some(entry),
+            _ => None,
+        });
+
+        match existing {
+            Some(entry) => {
+                if entry.value != value {
+                    entry.value = value.to_string();
+                    entry.raw = None;
+                }
+            }
+            None => self.lines.push(EnvLine::Entry(EnvEntry {
+                key: key.to_string(),
+                value: value.to_string(),
+                export: true,
+                comment: None,
+                raw: None,
+            })),
+        }
+    }
+
+    /// 删除变量的所有赋值，返回是否存在
+    pub fn remove(&mut self, key: &str) -> bool {
+        let before = self.lines.len();
+        self.lines
+            .retain(|line| !matches!(line, EnvLine::Entry(entry) if entry.key == key));
+        self.lines.len() != before
+    }
+
+    /// 序列化为文件内容
+    pub fn serialize(&self) -> String {
+        let mut out = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                EnvLine::Raw(raw) => raw.clone(),
+                EnvLine::Entry(entry) => match &entry.raw {
+                    Some(raw) => raw.clone(),
+                    None => format_entry(entry),
+                },
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.trailing_newline && !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &EnvEntry> {
+        self.lines.iter().filter_map(|line| match line {
+            EnvLine::Entry(entry) => Some(entry),
+            EnvLine::Raw(_) => None,
+        })
+    }
+}
+
+/// 变量名：字母或下划线开头，仅含字母、数字和下划线
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 生成赋值行：值统一用双引号包裹，并转义 shell 双引号内的特殊字符，保留原有的行尾注释
+fn format_entry(entry: &EnvEntry) -> String {
+    let mut quoted = String::with_capacity(entry.value.len() + 2);
+    quoted.push('"');
+    for c in entry.value.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+
+    let comment = entry.comment.as_deref().unwrap_or("");
+    if entry.export {
+        format!("export {}={}{}", entry.key, quoted, comment)
+    } else {
+        format!("{}={}{}", entry.key, quoted, comment)
+    }
+}
+
+/// 解析出的一个赋值
+struct ParsedEntry {
+    key: String,
+    value: String,
+    export: bool,
+    comment: Option<String>,
+    /// 结束位置：最后一行的行尾（不含换行符）
+    end: usize,
+}
+
+/// 从 `start` 开始解析一个赋值，不是合法赋值时返回 None
+fn parse_entry(content: &str, start: usize) -> Option<ParsedEntry> {
+    let text = &content[start..];
+    let line = text.split('\n').next().unwrap_or("");
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let mut rest = trimmed;
+    let mut export = false;
+    if let Some(after) = rest.strip_prefix("export") {
+        if after.starts_with([' ', '\t']) {
+            export = true;
+            rest = after.trim_start_matches([' ', '\t']);
+        }
+    }
+
+    let key_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let key = &rest[..key_len];
+    if !is_valid_key(key) {
+        return None;
+    }
+
+    // 兼容 dotenv 的 `KEY = value` 写法
+    let after_key = rest[key_len..].trim_start_matches([' ', '\t']);
+    let value_text = after_key.strip_prefix('=')?.trim_start_matches([' ', '\t']);
+    // value_text 是首行的后缀，换算为在整个文件中的位置，以便值跨行
+    let value_start = start + line.len() - value_text.len();
+    let value_text = &content[value_start..];
+
+    let mut value = String::new();
+    let mut chars = value_text.char_indices().peekable();
+    let mut end = value_text.len();
+    let mut comment = None;
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\n' => {
+                end = i;
+                break;
+            }
+            ' ' | '\t' | '\r' => {
+                // 值之后只允许空白和注释
+                let tail_end = value_text[i..].find('\n').map(|n| i + n).unwrap_or(value_text.len());
+                let tail = value_text[i..tail_end].trim();
+                if !tail.is_empty() && !tail.starts_with('#') {
+                    return None;
+                }
+                if !tail.is_empty() {
+                    comment = Some(value_text[i..tail_end].trim_end().to_string());
+                }
+                end = tail_end;
+                break;
+            }
+            '\'' => loop {
+                match chars.next() {
+                    Some((_, '\'')) => break,
+                    Some((_, ch)) => value.push(ch),
+                    None => return None,
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, ch @ ('"' | '\\' | '$' | '`'))) => value.push(ch),
+                        // 行尾反斜杠为续行
+                        Some((_, '\n')) => {}
+                        Some((_, ch)) => {
+                            value.push('\\');
+                            value.push(ch);
+                        }
+                        None => return None,
+                    },
+                    Some((_, ch)) => value.push(ch),
+                    None => return None,
+                }
+            },
+            '\\' => match chars.next() {
+                Some((_, '\n')) => {}
+                Some((_, ch)) => value.push(ch),
+                None => value.push('\\'),
+            },
+            _ => value.push(c),
+        }
+    }
+
+    Some(ParsedEntry {
+        key: key.to_string(),
+        value,
+        export,
+        comment,
+        end: value_start + end,
+    })
+}
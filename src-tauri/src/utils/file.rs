@@ -1,19 +1,84 @@
+use crate::utils::config_watcher;
+use crate::utils::env_file::{self, EnvFile};
 use std::fs;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 原子写入临时文件的序号，保证同一进程内并发写入使用不同的临时文件
+static TMP_SEQ: AtomicU64 = AtomicU64::new(0);
 
 /// 读取文件内容
 pub fn read_file(path: &str) -> io::Result<String> {
     fs::read_to_string(path)
 }
 
-/// 写入文件内容
-pub fn write_file(path: &str, content: &str) -> io::Result<()> {
-    // 确保父目录存在
-    if let Some(parent) = Path::new(path).parent() {
-        fs::create_dir_all(parent)?;
+/// 原子写入文件内容
+/// 先写入同目录下的临时文件并 fsync，再重命名覆盖原文件；
+/// 中途崩溃或磁盘写满时原文件保持不变。
+/// 重命名前会通知配置监听，避免把管理器自身的写入当成外部修改
+pub fn write_file_atomic(path: &str, content: &str) -> io::Result<()> {
+    write_atomic(path, content, false)
+}
+
+/// 原子写入仅当前用户可读写的文件（Unix 下权限为 0600），用于密钥等敏感内容
+pub fn write_private_file_atomic(path: &str, content: &str) -> io::Result<()> {
+    write_atomic(path, content, true)
+}
+
+fn write_atomic(path: &str, content: &str, private: bool) -> io::Result<()> {
+    use std::io::Write;
+
+    let target = Path::new(path);
+    let parent = match target.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+
+    let file_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let seq = TMP_SEQ.fetch_add(1, Ordering::Relaxed);
+    let tmp_path = parent.join(format!(".{}.tmp-{}-{}", file_name, std::process::id(), seq));
+
+    let result = (|| {
+        let mut tmp = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)?;
+        if private {
+            // 写入内容前先收紧权限
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                tmp.set_permissions(fs::Permissions::from_mode(0o600))?;
+            }
+        } else if let Ok(meta) = fs::metadata(target) {
+            // 沿用原文件权限（配置中可能包含密钥）
+            tmp.set_permissions(meta.permissions())?;
+        }
+        tmp.write_all(content.as_bytes())?;
+        tmp.sync_all()?;
+        drop(tmp);
+        config_watcher::note_self_write(path, content.as_bytes());
+        fs::rename(&tmp_path, target)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+        config_watcher::note_current(path);
+        return result;
+    }
+
+    // 同步目录项，确保重命名本身落盘
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(parent) {
+        let _ = dir.sync_all();
     }
-    fs::write(path, content)
+
+    Ok(())
 }
 
 /// 追加文件内容
@@ -46,53 +111,27 @@ pub fn read_last_lines(path: &str, n: usize) -> io::Result<Vec<String>> {
 
 /// 从环境变量文件读取值
 pub fn read_env_value(env_file: &str, key: &str) -> Option<String> {
-    let content = read_file(env_file).ok()?;
-    
-    for line in content.lines() {
-        let line = line.trim();
-        if line.starts_with(&format!("export {}=", key)) {
-            let value = line
-                .trim_start_matches(&format!("export {}=", key))
-                .trim_matches('"')
-                .trim_matches('\'');
-            return Some(value.to_string());
-        }
-    }
-    
-    None
+    EnvFile::load(env_file).ok()?.get(key).map(str::to_string)
 }
 
-/// 设置环境变量文件中的值
+/// 设置环境变量文件中的值（保留其余内容和格式）
 pub fn set_env_value(env_file: &str, key: &str, value: &str) -> io::Result<()> {
-    let content = read_file(env_file).unwrap_or_default();
-    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-    
-    let new_line = format!("export {}=\"{}\"", key, value);
-    let mut found = false;
-    
-    for line in &mut lines {
-        if line.starts_with(&format!("export {}=", key)) {
-            *line = new_line.clone();
-            found = true;
-            break;
-        }
-    }
-    
-    if !found {
-        lines.push(new_line);
+    if !env_file::is_valid_key(key) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("环境变量名无效: {}", key),
+        ));
     }
-    
-    write_file(env_file, &lines.join("\n"))
+    let mut env = EnvFile::load(env_file)?;
+    env.set(key, value);
+    env.save(env_file)
 }
 
 /// 从环境变量文件中删除指定的值
 pub fn remove_env_value(env_file: &str, key: &str) -> io::Result<()> {
-    let content = read_file(env_file).unwrap_or_default();
-    let lines: Vec<String> = content
-        .lines()
-        .filter(|line| !line.starts_with(&format!("export {}=", key)))
-        .map(|s| s.to_string())
-        .collect();
-    
-    write_file(env_file, &lines.join("\n"))
+    let mut env = EnvFile::load(env_file)?;
+    if env.remove(key) {
+        env.save(env_file)?;
+    }
+    Ok(())
 }
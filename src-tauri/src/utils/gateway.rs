@@ -0,0 +1,283 @@
+use crate::utils::supervisor::GatewaySupervisor;
+use crate::utils::{platform, settings, shell};
+use log::{debug, info};
+use std::process::Command;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+/// Windows CREATE_NO_WINDOW 标志，用于隐藏控制台窗口
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 检测端口是否有服务在监听，返回 PID
+/// 简单直接：端口被占用 = 服务运行中
+pub fn listening_pid(port: u16) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        let output = Command::new("lsof")
+            .args(["-ti", &format!(":{}", port)])
+            .output()
+            .ok()?;
+        
+        if output.status.success() {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .and_then(|line| line.trim().parse::<u32>().ok())
+        } else {
+            None
+        }
+    }
+    
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("netstat");
+        cmd.args(["-ano"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        
+        let output = cmd.output().ok()?;
+        
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for line in stdout.lines() {
+                if line.contains(&format!(":{}", port)) && line.contains("LISTENING") {
+                    if let Some(pid_str) = line.split_whitespace().last() {
+                        if let Ok(pid) = pid_str.parse::<u32>() {
+                            return Some(pid);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 判断占用端口的进程是否为 openclaw gateway
+/// 是 gateway 时返回 None，否则返回该进程的描述
+fn describe_non_gateway_process(pid: u32) -> Option<String> {
+    #[cfg(unix)]
+    let description = {
+        let output = Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "command="])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    
+    #[cfg(windows)]
+    let description = {
+        let mut cmd = Command::new("tasklist");
+        cmd.args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        let output = cmd.output().ok()?;
+        // 格式: "node.exe","1234",...，取进程名
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .and_then(|line| line.split(',').next())
+            .map(|name| name.trim_matches('"').to_string())
+            .unwrap_or_default()
+    };
+    
+    // 无法识别时按 gateway 处理，避免误报
+    if description.is_empty() {
+        return None;
+    }
+    
+    let lower = description.to_lowercase();
+    // gateway 以 node 运行 openclaw；Windows 上只能拿到进程名
+    let is_gateway = lower.contains("openclaw") || (cfg!(windows) && lower.starts_with("node"));
+    if is_gateway {
+        None
+    } else {
+        Some(description)
+    }
+}
+
+/// 当前档案的 gateway 是否在运行（端口有进程监听）
+pub fn is_running() -> bool {
+    listening_pid(platform::get_gateway_port()).is_some()
+}
+
+/// 以当前档案启动 gateway（由监管器持有子进程），等待端口开始监听
+pub fn start(supervisor: &GatewaySupervisor) -> Result<String, String> {
+    info!("[服务] 启动服务...");
+    
+    // 检查端口是否已被占用：gateway 已在运行，或被其他进程占用
+    let profile = settings::active_profile();
+    let port = platform::get_profile_gateway_port(&profile);
+    info!("[服务] 当前档案: {}", profile.name);
+    if let Some(pid) = listening_pid(port) {
+        return match describe_non_gateway_process(pid) {
+            None => {
+                info!("[服务] 服务已在运行中");
+                Err("服务已在运行中".to_string())
+            }
+            Some(process) => {
+                info!("[服务] 端口 {} 被其他进程占用: PID {} ({})", port, pid, process);
+                Err(format!(
+                    "端口 {} 已被其他进程占用 (PID: {}, {})，请结束该进程或在 openclaw.json 的 gateway.port 中更换端口",
+                    port, pid, process
+                ))
+            }
+        };
+    }
+    
+    // 检查 openclaw 命令是否存在
+    let openclaw_path = shell::get_openclaw_path();
+    if openclaw_path.is_none() {
+        info!("[服务] 找不到 openclaw 命令");
+        return Err("找不到 openclaw 命令，请先通过 npm install -g openclaw 安装".to_string());
+    }
+    info!("[服务] openclaw 路径: {:?}", openclaw_path);
+    
+    // 由监管器后台启动 gateway（不等待 doctor，避免阻塞）
+    info!("[服务] 后台启动 gateway...");
+    supervisor
+        .start(&profile)
+        .map_err(|e| format!("启动服务失败: {}", e))?;
+    
+    // 轮询等待端口开始监听（最多 15 秒）
+    info!("[服务] 等待端口 {} 开始监听...", port);
+    for i in 1..=15 {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        if let Some(pid) = listening_pid(port) {
+            info!("[服务] ✓ 启动成功 ({}秒), PID: {}", i, pid);
+            return Ok(format!("服务已启动，PID: {}", pid));
+        }
+        if i % 3 == 0 {
+            debug!("[服务] 等待中... ({}秒)", i);
+        }
+    }
+    
+    info!("[服务] 等待超时，端口仍未监听");
+    Err("服务启动超时（15秒），请检查 openclaw 日志".to_string())
+}
+
+/// 获取监听指定端口的所有 PID
+fn get_pids_on_port(port: u16) -> Vec<u32> {
+    #[cfg(unix)]
+    {
+        let output = Command::new("lsof")
+            .args(["-ti", &format!(":{}", port)])
+            .output();
+        
+        match output {
+            Ok(out) if out.status.success() => {
+                String::from_utf8_lossy(&out.stdout)
+                    .lines()
+                    .filter_map(|line| line.trim().parse::<u32>().ok())
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+    
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("netstat");
+        cmd.args(["-ano"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        
+        match cmd.output() {
+            Ok(out) if out.status.success() => {
+                let stdout = String::from_utf8_lossy(&out.stdout);
+                stdout.lines()
+                    .filter(|line| line.contains(&format!(":{}", port)) && line.contains("LISTENING"))
+                    .filter_map(|line| line.split_whitespace().last())
+                    .filter_map(|pid_str| pid_str.parse::<u32>().ok())
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// 通过 PID 杀死进程
+fn kill_process(pid: u32, force: bool) -> bool {
+    info!("[服务] 杀死进程 PID: {}, force: {}", pid, force);
+    
+    #[cfg(unix)]
+    {
+        let signal = if force { "-9" } else { "-TERM" };
+        Command::new("kill")
+            .args([signal, &pid.to_string()])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+    
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("taskkill");
+        if force {
+            cmd.args(["/F", "/PID", &pid.to_string()]);
+        } else {
+            cmd.args(["/PID", &pid.to_string()]);
+        }
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        cmd.output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+}
+
+/// 停止当前档案的 gateway（先停止监管，再杀死监听端口的进程）
+pub fn stop(supervisor: &GatewaySupervisor) -> Result<String, String> {
+    info!("[服务] 停止服务...");
+    
+    // 先停止监管，避免被杀死的进程被自动重启
+    let profile = settings::active_profile();
+    supervisor.stop(&profile.name);
+    
+    let port = platform::get_profile_gateway_port(&profile);
+    let pids = get_pids_on_port(port);
+    if pids.is_empty() {
+        info!("[服务] 端口 {} 无进程监听，服务未运行", port);
+        return Ok("服务未在运行".to_string());
+    }
+    
+    info!("[服务] 发现 {} 个进程监听端口 {}: {:?}", pids.len(), port, pids);
+    
+    // 第一步：优雅终止 (SIGTERM)
+    for &pid in &pids {
+        kill_process(pid, false);
+    }
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    
+    // 检查是否已停止
+    let remaining = get_pids_on_port(port);
+    if remaining.is_empty() {
+        info!("[服务] ✓ 已停止");
+        return Ok("服务已停止".to_string());
+    }
+    
+    // 第二步：强制终止 (SIGKILL)
+    info!("[服务] 仍有 {} 个进程存活，强制终止...", remaining.len());
+    for &pid in &remaining {
+        kill_process(pid, true);
+    }
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    
+    let still_running = get_pids_on_port(port);
+    if still_running.is_empty() {
+        info!("[服务] ✓ 已强制停止");
+        Ok("服务已停止".to_string())
+    } else {
+        Err(format!("无法停止服务，仍有进程: {:?}", still_running))
+    }
+}
+
+/// 重启 gateway：先停止再启动
+pub fn restart(supervisor: &GatewaySupervisor) -> Result<String, String> {
+    info!("[服务] 重启服务...");
+    
+    // 先停止
+    let _ = stop(supervisor);
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    
+    // 再启动
+    start(supervisor)
+}
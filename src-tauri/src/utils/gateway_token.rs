@@ -0,0 +1,159 @@
+use crate::models::{GatewayTokenInfo, OpenClawConfig, Profile};
+use crate::utils::supervisor::GatewaySupervisor;
+use crate::utils::{config_store, gateway, secrets, settings};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use log::{info, warn};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Token 过期检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 生成 Gateway Token：32 字节操作系统 CSPRNG 随机数的十六进制
+pub fn generate() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("生成随机 Token 失败: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// 将新 token 写入配置，返回新 token（调用方负责保存配置）
+pub fn apply_new_token(config: &mut OpenClawConfig) -> Result<String, String> {
+    let token = generate()?;
+    let auth = config.gateway.auth.get_or_insert_with(Default::default);
+    auth.token = Some(token.clone());
+    auth.mode = Some("token".to_string());
+    config.gateway.mode.get_or_insert_with(|| "local".to_string());
+    Ok(token)
+}
+
+/// 读取档案配置的 Gateway Token（gateway.auth.token，支持密钥引用），没有配置时返回 None
+/// 只读，不会修改配置；执行 openclaw 命令时注入环境变量使用此函数
+pub fn configured(profile: &Profile) -> Result<Option<String>, String> {
+    let (config, _) = config_store::load_typed_for(profile)?;
+    configured_in(profile, &config)
+}
+
+fn configured_in(profile: &Profile, config: &OpenClawConfig) -> Result<Option<String>, String> {
+    let Some(token) = config
+        .gateway
+        .auth
+        .as_ref()
+        .and_then(|auth| auth.token.as_deref())
+        .filter(|token| !token.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+    secrets::resolve(profile, token)
+        .filter(|token| !token.is_empty())
+        .map(Some)
+        .ok_or_else(|| format!("Gateway Token 引用的密钥不存在: {}", token))
+}
+
+/// 获取档案的 Gateway Token，配置中没有 token 时生成新的 token 并写入配置
+/// 只在应用启动、启动 gateway 和生成 Dashboard URL 时调用
+pub fn ensure(profile: &Profile) -> Result<String, String> {
+    let (mut config, revision) = config_store::load_typed_for(profile)?;
+    if let Some(token) = configured_in(profile, &config)? {
+        return Ok(token);
+    }
+
+    // 旧配置没有 token：生成新的 token 并写入配置
+    info!("[Gateway Token] 档案 {} 未配置 Token，生成新 Token", profile.name);
+    let token = apply_new_token(&mut config)?;
+    config_store::save_typed_for(profile, &config, Some(&revision))?;
+    record_issued(&profile.name, Utc::now())?;
+    Ok(token)
+}
+
+/// 轮换当前档案的 Gateway Token：写入新 token 并记录签发时间，Gateway 运行中则重启使其生效
+pub fn rotate(supervisor: &GatewaySupervisor) -> Result<(), String> {
+    let profile = settings::active_profile();
+    let (mut config, revision) = config_store::load_typed_for(&profile)?;
+    apply_new_token(&mut config)?;
+    config_store::save_typed_for(&profile, &config, Some(&revision))?;
+    record_issued(&profile.name, Utc::now())?;
+    info!("[Gateway Token] ✓ 新 Token 已保存到配置");
+
+    if gateway::is_running() {
+        info!("[Gateway Token] 重启 Gateway 以应用新 Token...");
+        gateway::restart(supervisor)?;
+    }
+    Ok(())
+}
+
+/// 记录档案当前 token 的签发时间
+pub fn record_issued(profile_name: &str, at: DateTime<Utc>) -> Result<(), String> {
+    settings::update(|manager_settings| {
+        manager_settings
+            .gateway_token_issued_at
+            .insert(profile_name.to_string(), at.to_rfc3339());
+        Ok(())
+    })
+}
+
+/// 获取档案 token 的签发与过期信息
+pub fn info(profile: &Profile) -> GatewayTokenInfo {
+    let issued_at = settings::load()
+        .gateway_token_issued_at
+        .get(&profile.name)
+        .cloned();
+    let expires_at = expires_at(issued_at.as_deref(), profile.token_ttl_hours);
+    GatewayTokenInfo {
+        issued_at,
+        ttl_hours: profile.token_ttl_hours,
+        expires_at: expires_at.map(|t| t.to_rfc3339()),
+    }
+}
+
+fn expires_at(issued_at: Option<&str>, ttl_hours: Option<u32>) -> Option<DateTime<Utc>> {
+    let issued = DateTime::parse_from_rfc3339(issued_at?).ok()?;
+    Some(issued.with_timezone(&Utc) + ChronoDuration::hours(ttl_hours? as i64))
+}
+
+/// 启动后台线程：先为当前档案补全缺失的 token（旧配置迁移），
+/// 之后当前档案的 token 过期时自动轮换（运行中的 Gateway 会被重启）
+pub fn spawn(app: AppHandle) {
+    thread::Builder::new()
+        .name("gateway-token-expiry".to_string())
+        .spawn(move || {
+            if let Err(e) = ensure(&settings::active_profile()) {
+                warn!("[Gateway Token] 初始化 Token 失败: {}", e);
+            }
+            loop {
+                check_expiry(&app);
+                thread::sleep(CHECK_INTERVAL);
+            }
+        })
+        .expect("无法启动 Token 过期检查线程");
+}
+
+fn check_expiry(app: &AppHandle) {
+    let profile = settings::active_profile();
+    if profile.token_ttl_hours.is_none() {
+        return;
+    }
+
+    let info = info(&profile);
+    if info.issued_at.is_none() {
+        // 设置有效期前签发的 token 没有记录，从现在开始计时
+        if let Err(e) = record_issued(&profile.name, Utc::now()) {
+            warn!("[Gateway Token] 记录签发时间失败: {}", e);
+        }
+        return;
+    }
+
+    let expired = info
+        .expires_at
+        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+        .is_some_and(|t| t <= Utc::now());
+    if !expired {
+        return;
+    }
+
+    info!("[Gateway Token] 档案 {} 的 Token 已过期，自动轮换...", profile.name);
+    match rotate(&app.state::<GatewaySupervisor>()) {
+        Ok(_) => info!("[Gateway Token] ✓ Token 已自动轮换"),
+        Err(e) => warn!("[Gateway Token] 自动轮换失败: {}", e),
+    }
+}
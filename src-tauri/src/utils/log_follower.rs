@@ -0,0 +1,270 @@
+use crate::utils::platform;
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::fs::{self, File, Metadata};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 推送新日志行的事件名
+pub const GATEWAY_LOG_EVENT: &str = "gateway-log";
+
+/// 被跟踪的 gateway 日志文件
+const FOLLOWED_FILES: [&str; 2] = ["gateway.log", "gateway.err.log"];
+/// 文件检查间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 未换行的残留内容上限，超过后按整行输出，避免单行无限增长
+const MAX_PARTIAL_BYTES: usize = 64 * 1024;
+/// 读取末尾 N 行时每次向前读取的块大小
+const TAIL_CHUNK_SIZE: u64 = 8 * 1024;
+
+/// 推送给前端的一行日志
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayLogLine {
+    /// 来源文件名（gateway.log / gateway.err.log）
+    pub source: String,
+    /// 日志内容
+    pub line: String,
+}
+
+/// 单个被跟踪的文件
+struct FollowedFile {
+    source: &'static str,
+    path: PathBuf,
+    /// 当前打开的句柄（轮转后仍可读完旧文件的剩余内容）
+    file: Option<File>,
+    /// 文件标识，变化说明文件已被轮转
+    identity: Option<u64>,
+    /// 已读取到的位置
+    offset: u64,
+    /// 尚未换行的残留字节
+    partial: Vec<u8>,
+}
+
+impl FollowedFile {
+    fn new(source: &'static str, dir: &Path) -> Self {
+        Self {
+            source,
+            path: dir.join(source),
+            file: None,
+            identity: None,
+            offset: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    /// 检查文件变化，返回新增的完整行
+    /// `from_start` 为 false 时首次打开文件会跳到末尾，只跟踪之后写入的内容
+    fn poll(&mut self, from_start: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        let meta = match fs::metadata(&self.path) {
+            Ok(meta) => meta,
+            Err(_) => {
+                // 文件被移走（轮转中）：读完旧句柄后等待新文件出现
+                if self.file.is_some() {
+                    self.drain(&mut lines);
+                    self.flush_partial(&mut lines);
+                    self.file = None;
+                    self.identity = None;
+                }
+                return lines;
+            }
+        };
+
+        let identity = file_identity(&meta);
+        if self.file.is_some() && identity != self.identity {
+            debug!("[日志跟踪] 检测到 {} 已轮转", self.source);
+            self.drain(&mut lines);
+            self.flush_partial(&mut lines);
+            self.file = None;
+        }
+
+        if self.file.is_none() {
+            let mut file = match File::open(&self.path) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("[日志跟踪] 无法打开 {}: {}", self.path.display(), e);
+                    return lines;
+                }
+            };
+            // 轮转或新建后的文件从头读取；启动时已存在的文件从末尾开始
+            let start = if from_start || self.identity.is_some() { 0 } else { meta.len() };
+            if file.seek(SeekFrom::Start(start)).is_err() {
+                return lines;
+            }
+            self.file = Some(file);
+            self.identity = identity;
+            self.offset = start;
+        }
+
+        // 文件被截断：从头开始读
+        if meta.len() < self.offset {
+            debug!("[日志跟踪] 检测到 {} 被截断", self.source);
+            self.partial.clear();
+            if let Some(file) = self.file.as_mut() {
+                if file.seek(SeekFrom::Start(0)).is_err() {
+                    return lines;
+                }
+            }
+            self.offset = 0;
+        }
+
+        if meta.len() > self.offset {
+            self.drain(&mut lines);
+        }
+        lines
+    }
+
+    /// 读取当前句柄中的全部新内容
+    fn drain(&mut self, lines: &mut Vec<String>) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+
+        let mut buf = Vec::new();
+        match file.read_to_end(&mut buf) {
+            Ok(n) => self.offset += n as u64,
+            Err(e) => {
+                warn!("[日志跟踪] 读取 {} 失败: {}", self.source, e);
+                return;
+            }
+        }
+
+        self.partial.extend_from_slice(&buf);
+        while let Some(pos) = self.partial.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = self.partial.drain(..=pos).collect();
+            push_line(lines, &raw);
+        }
+        if self.partial.len() > MAX_PARTIAL_BYTES {
+            self.flush_partial(lines);
+        }
+    }
+
+    /// 输出残留的未换行内容
+    fn flush_partial(&mut self, lines: &mut Vec<String>) {
+        if !self.partial.is_empty() {
+            let raw = std::mem::take(&mut self.partial);
+            push_line(lines, &raw);
+        }
+    }
+}
+
+fn push_line(lines: &mut Vec<String>, raw: &[u8]) {
+    let line = String::from_utf8_lossy(raw);
+    let line = line.trim_end_matches(['\r', '\n']);
+    if !line.trim().is_empty() {
+        lines.push(line.to_string());
+    }
+}
+
+/// 文件标识：Unix 上为 inode，其他平台使用创建时间
+fn file_identity(meta: &Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(meta.ino())
+    }
+
+    #[cfg(not(unix))]
+    {
+        meta.created()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64)
+    }
+}
+
+/// 启动后台日志跟踪线程，新日志以 `gateway-log` 事件推送给前端
+/// 切换配置档案后自动改为跟踪新档案的日志目录
+pub fn spawn(app: AppHandle) {
+    thread::Builder::new()
+        .name("gateway-log-follower".to_string())
+        .spawn(move || follow_loop(app))
+        .expect("无法启动日志跟踪线程");
+}
+
+fn follow_loop(app: AppHandle) {
+    let mut log_dir = PathBuf::from(platform::get_log_dir());
+    let mut files = open_files(&log_dir);
+    // 启动时已有的内容由 get_logs 提供，这里只推送之后的新内容
+    let mut from_start = false;
+    info!("[日志跟踪] 开始跟踪 {}", log_dir.display());
+
+    loop {
+        let current_dir = PathBuf::from(platform::get_log_dir());
+        if current_dir != log_dir {
+            info!("[日志跟踪] 日志目录变更，改为跟踪 {}", current_dir.display());
+            log_dir = current_dir;
+            files = open_files(&log_dir);
+            from_start = false;
+        }
+
+        let mut batch = Vec::new();
+        for followed in files.iter_mut() {
+            for line in followed.poll(from_start) {
+                batch.push(GatewayLogLine {
+                    source: followed.source.to_string(),
+                    line,
+                });
+            }
+        }
+        // 首轮之后新出现的文件需要从头读取
+        from_start = true;
+
+        if !batch.is_empty() {
+            if let Err(e) = app.emit(GATEWAY_LOG_EVENT, &batch) {
+                warn!("[日志跟踪] 推送日志事件失败: {}", e);
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn open_files(dir: &Path) -> Vec<FollowedFile> {
+    FOLLOWED_FILES
+        .iter()
+        .map(|source| FollowedFile::new(source, dir))
+        .collect()
+}
+
+/// 读取文件末尾的 N 行（从末尾按块向前读，不依赖 tail 命令）
+pub fn read_last_lines(path: &Path, n: usize) -> Vec<String> {
+    let Ok(mut file) = File::open(path) else {
+        return Vec::new();
+    };
+    let Ok(len) = file.metadata().map(|m| m.len()) else {
+        return Vec::new();
+    };
+
+    let mut pos = len;
+    let mut buf: Vec<u8> = Vec::new();
+    // 多读一行换行符，保证最前面的一行是完整的
+    while pos > 0 && buf.iter().filter(|&&b| b == b'\n').count() <= n {
+        let size = TAIL_CHUNK_SIZE.min(pos);
+        pos -= size;
+        let mut chunk = vec![0u8; size as usize];
+        if file.seek(SeekFrom::Start(pos)).is_err() || file.read_exact(&mut chunk).is_err() {
+            return Vec::new();
+        }
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+    }
+
+    let content = String::from_utf8_lossy(&buf);
+    let mut lines: Vec<&str> = content.lines().collect();
+    if pos > 0 && !lines.is_empty() {
+        // 第一行可能只读到一半
+        lines.remove(0);
+    }
+    let lines: Vec<String> = lines
+        .into_iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let skip = lines.len().saturating_sub(n);
+    lines.into_iter().skip(skip).collect()
+}
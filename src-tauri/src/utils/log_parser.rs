@@ -0,0 +1,309 @@
+use crate::models::{GatewayLogEntry, LogQuery};
+use crate::utils::shell;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde_json::Value;
+
+/// 日志级别，按严重程度从低到高排列
+const LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "fatal"];
+
+/// 规范化日志级别名称
+fn normalize_level(token: &str) -> Option<&'static str> {
+    let level = match token.to_ascii_lowercase().as_str() {
+        "trace" | "silly" => "trace",
+        "debug" | "dbg" | "verbose" => "debug",
+        "info" | "inf" | "notice" => "info",
+        "warn" | "warning" | "wrn" => "warn",
+        "error" | "err" | "erro" => "error",
+        "fatal" | "crit" | "critical" | "panic" => "fatal",
+        _ => return None,
+    };
+    Some(level)
+}
+
+/// 日志级别的严重程度，未知级别返回 None
+pub fn level_rank(level: &str) -> Option<usize> {
+    let level = normalize_level(level)?;
+    LEVELS.iter().position(|l| *l == level)
+}
+
+/// pino 风格的数字级别
+fn numeric_level(n: u64) -> &'static str {
+    match n {
+        0..=10 => "trace",
+        11..=20 => "debug",
+        21..=30 => "info",
+        31..=40 => "warn",
+        41..=50 => "error",
+        _ => "fatal",
+    }
+}
+
+/// 解析时间戳文本，支持 RFC 3339、`YYYY-MM-DD HH:MM:SS` 和仅时间（按今天处理）
+fn parse_timestamp(text: &str) -> Option<DateTime<FixedOffset>> {
+    let text = text.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+        return Some(dt);
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(text, format) {
+            return local_to_fixed(naive);
+        }
+    }
+    if let Ok(time) = NaiveTime::parse_from_str(text, "%H:%M:%S%.f") {
+        return local_to_fixed(Local::now().date_naive().and_time(time));
+    }
+    None
+}
+
+fn local_to_fixed(naive: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.fixed_offset())
+}
+
+/// 去掉包裹标记的方括号和结尾的冒号
+fn unwrap_token(token: &str) -> &str {
+    let token = token.trim_end_matches(':');
+    token
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .unwrap_or(token)
+}
+
+/// 解析一行日志
+/// 支持 JSON 行（tslog / pino）和 `时间 级别 [子系统] 正文` 形式的文本行，各部分均可缺省、顺序可互换
+pub fn parse_line(line: &str, source: &str) -> GatewayLogEntry {
+    let raw = shell::strip_ansi_codes(line).trim_end().to_string();
+    let trimmed = raw.trim();
+
+    if trimmed.starts_with('{') {
+        if let Ok(Value::Object(obj)) = serde_json::from_str::<Value>(trimmed) {
+            return parse_json_line(&obj, &raw, source);
+        }
+    }
+
+    let mut timestamp = None;
+    let mut level = None;
+    let mut subsystem = None;
+    let mut rest = trimmed;
+
+    // 依次识别行首的时间戳、级别和子系统标签
+    loop {
+        let (token, after) = match rest.split_once(char::is_whitespace) {
+            Some((token, after)) => (token, after.trim_start()),
+            None => (rest, ""),
+        };
+        if token.is_empty() {
+            break;
+        }
+        let inner = unwrap_token(token);
+
+        if timestamp.is_none() {
+            if let Some(ts) = parse_timestamp(inner) {
+                timestamp = Some(ts);
+                rest = after;
+                continue;
+            }
+            // 日期和时间以空格分隔的情况
+            if NaiveDate::parse_from_str(inner, "%Y-%m-%d").is_ok() {
+                if let Some((time, after_time)) = after.split_once(char::is_whitespace) {
+                    if let Some(ts) = parse_timestamp(&format!("{} {}", inner, unwrap_token(time))) {
+                        timestamp = Some(ts);
+                        rest = after_time.trim_start();
+                        continue;
+                    }
+                }
+            }
+        }
+
+        if level.is_none() {
+            let candidate = inner.strip_prefix("level=").unwrap_or(inner);
+            if let Some(l) = normalize_level(candidate) {
+                level = Some(l.to_string());
+                rest = after;
+                continue;
+            }
+        }
+
+        let bracketed = token.starts_with('[') && token.trim_end_matches(':').ends_with(']');
+        if subsystem.is_none() && bracketed && !inner.is_empty() {
+            subsystem = Some(inner.to_string());
+            rest = after;
+            continue;
+        }
+
+        break;
+    }
+
+    GatewayLogEntry {
+        timestamp: timestamp.map(|t| t.to_rfc3339()),
+        level,
+        subsystem,
+        message: rest.to_string(),
+        source: source.to_string(),
+        raw,
+    }
+}
+
+/// 解析 JSON 格式的日志行
+fn parse_json_line(
+    obj: &serde_json::Map<String, Value>,
+    raw: &str,
+    source: &str,
+) -> GatewayLogEntry {
+    let meta = obj.get("_meta").and_then(|m| m.as_object());
+    let field = |keys: &[&str]| -> Option<&Value> {
+        keys.iter().find_map(|k| obj.get(*k))
+    };
+    let meta_str = |key: &str| meta.and_then(|m| m.get(key)).and_then(|v| v.as_str());
+
+    let timestamp = field(&["time", "timestamp", "ts"])
+        .and_then(|v| v.as_str())
+        .or_else(|| meta_str("date"))
+        .and_then(parse_timestamp)
+        .map(|t| t.to_rfc3339());
+
+    let level = match field(&["level", "lvl"]) {
+        Some(Value::Number(n)) => n.as_u64().map(|n| numeric_level(n).to_string()),
+        Some(Value::String(s)) => normalize_level(s).map(|l| l.to_string()),
+        _ => None,
+    }
+    .or_else(|| meta_str("logLevelName").and_then(normalize_level).map(|l| l.to_string()));
+
+    let subsystem = field(&["subsystem", "module", "channel"])
+        .and_then(|v| v.as_str())
+        .or_else(|| meta_str("name"))
+        .map(|s| s.to_string());
+
+    let message = match field(&["msg", "message", "0"]) {
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => raw.trim().to_string(),
+    };
+
+    GatewayLogEntry {
+        timestamp,
+        level,
+        subsystem,
+        message: shell::strip_ansi_codes(&message),
+        source: source.to_string(),
+        raw: raw.to_string(),
+    }
+}
+
+/// 解析同一文件中的多行日志
+/// 没有时间戳的续行（如堆栈）沿用上一条的时间、级别和子系统；
+/// stderr 文件中无级别的行按 error 处理
+pub fn parse_lines(lines: &[String], source: &str) -> Vec<GatewayLogEntry> {
+    let is_stderr = source.contains(".err") || source.starts_with("stderr");
+    let mut entries: Vec<GatewayLogEntry> = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let mut entry = parse_line(line, source);
+        if entry.timestamp.is_none() {
+            if let Some(prev) = entries.last() {
+                let continuation = line.starts_with(char::is_whitespace);
+                entry.timestamp = prev.timestamp.clone();
+                if continuation {
+                    entry.level = entry.level.or_else(|| prev.level.clone());
+                    entry.subsystem = entry.subsystem.or_else(|| prev.subsystem.clone());
+                }
+            }
+        }
+        if entry.level.is_none() && is_stderr {
+            entry.level = Some("error".to_string());
+        }
+        entries.push(entry);
+    }
+    entries
+}
+
+/// 解析日志条目的时间戳
+pub fn entry_time(entry: &GatewayLogEntry) -> Option<DateTime<FixedOffset>> {
+    entry
+        .timestamp
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+}
+
+/// 按时间戳稳定排序（无时间戳的条目排在最前，并保持原有顺序）
+pub fn sort_entries(entries: &mut [GatewayLogEntry]) {
+    entries.sort_by_key(entry_time);
+}
+
+/// 编译后的查询条件
+pub struct LogFilter {
+    min_level: Option<usize>,
+    since: Option<DateTime<FixedOffset>>,
+    until: Option<DateTime<FixedOffset>>,
+    channel: Option<String>,
+    search: Option<String>,
+}
+
+impl LogFilter {
+    /// 校验并编译查询条件
+    pub fn new(query: &LogQuery) -> Result<Self, String> {
+        let min_level = match query.level.as_deref().filter(|l| !l.is_empty()) {
+            Some(level) => Some(level_rank(level).ok_or_else(|| format!("未知的日志级别: {}", level))?),
+            None => None,
+        };
+        let parse_bound = |value: &Option<String>, name: &str| -> Result<_, String> {
+            match value.as_deref().filter(|v| !v.is_empty()) {
+                Some(v) => DateTime::parse_from_rfc3339(v)
+                    .map(Some)
+                    .map_err(|e| format!("{} 时间格式无效（需要 RFC 3339）: {}", name, e)),
+                None => Ok(None),
+            }
+        };
+        let lowercase = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_lowercase)
+        };
+
+        Ok(Self {
+            min_level,
+            since: parse_bound(&query.since, "since")?,
+            until: parse_bound(&query.until, "until")?,
+            channel: lowercase(&query.channel),
+            search: lowercase(&query.search),
+        })
+    }
+
+    /// 判断条目是否满足全部条件
+    pub fn matches(&self, entry: &GatewayLogEntry) -> bool {
+        if let Some(min) = self.min_level {
+            match entry.level.as_deref().and_then(level_rank) {
+                Some(rank) if rank >= min => {}
+                _ => return false,
+            }
+        }
+
+        if self.since.is_some() || self.until.is_some() {
+            let Some(at) = entry_time(entry) else {
+                return false;
+            };
+            if self.since.is_some_and(|since| at < since) || self.until.is_some_and(|until| at > until) {
+                return false;
+            }
+        }
+
+        if let Some(channel) = &self.channel {
+            match &entry.subsystem {
+                Some(subsystem) if subsystem.to_lowercase().contains(channel.as_str()) => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(search) = &self.search {
+            if !entry.message.to_lowercase().contains(search.as_str()) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -1,3 +1,22 @@
+pub mod budget;
+pub mod channel_registry;
+pub mod channel_schema;
+pub mod config_store;
+pub mod config_validator;
+pub mod config_watcher;
+pub mod env_file;
 pub mod file;
+pub mod gateway;
+pub mod gateway_token;
+pub mod log_follower;
+pub mod log_parser;
 pub mod platform;
+pub mod plugins;
+pub mod process_stats;
+pub mod provider_api;
+pub mod secrets;
+pub mod settings;
 pub mod shell;
+pub mod supervisor;
+pub mod usage;
+pub mod vault;
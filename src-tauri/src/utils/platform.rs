@@ -1,5 +1,10 @@
+use crate::models::Profile;
+use crate::utils::{file, settings};
 use std::env;
 
+/// 默认的 Gateway 端口
+pub const DEFAULT_GATEWAY_PORT: u16 = 18789;
+
 /// 获取操作系统类型
 pub fn get_os() -> String {
     env::consts::OS.to_string()
@@ -10,8 +15,8 @@ pub fn get_arch() -> String {
     env::consts::ARCH.to_string()
 }
 
-/// 获取配置目录路径
-pub fn get_config_dir() -> String {
+/// 获取默认配置目录路径（~/.openclaw）
+pub fn get_default_config_dir() -> String {
     if let Some(home) = dirs::home_dir() {
         if is_windows() {
             format!("{}\\.openclaw", home.display())
@@ -23,31 +28,69 @@ pub fn get_config_dir() -> String {
     }
 }
 
-/// 获取环境变量文件路径
+/// 获取当前配置档案的配置目录路径
+pub fn get_config_dir() -> String {
+    settings::active_profile().config_dir
+}
+
+/// 获取当前配置档案的环境变量文件路径
 pub fn get_env_file_path() -> String {
-    if is_windows() {
-        format!("{}\\env", get_config_dir())
-    } else {
-        format!("{}/env", get_config_dir())
-    }
+    settings::active_profile().env_file_path()
 }
 
-/// 获取 openclaw.json 配置文件路径
+/// 获取当前配置档案的 openclaw.json 配置文件路径
 pub fn get_config_file_path() -> String {
-    if is_windows() {
-        format!("{}\\openclaw.json", get_config_dir())
-    } else {
-        format!("{}/openclaw.json", get_config_dir())
+    settings::active_profile().config_file_path()
+}
+
+/// 获取当前配置档案的日志目录
+pub fn get_log_dir() -> String {
+    settings::active_profile().log_dir_path()
+}
+
+/// 获取管理器自身的数据目录（保存管理器设置等）
+pub fn get_manager_dir() -> String {
+    let base = dirs::config_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    base.join("openclaw-manager").display().to_string()
+}
+
+/// 获取管理器设置文件路径
+pub fn get_manager_settings_path() -> String {
+    std::path::Path::new(&get_manager_dir())
+        .join("settings.json")
+        .display()
+        .to_string()
+}
+
+/// 获取当前配置档案的 Gateway 端口
+pub fn get_gateway_port() -> u16 {
+    get_profile_gateway_port(&settings::active_profile())
+}
+
+/// 获取指定配置档案的 Gateway 端口
+/// 优先级：档案中的端口覆盖 > openclaw.json 中的 gateway.port > 默认 18789
+pub fn get_profile_gateway_port(profile: &Profile) -> u16 {
+    if let Some(port) = profile.gateway_port {
+        return port;
     }
+
+    file::read_file(&profile.config_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config.pointer("/gateway/port").and_then(|v| v.as_u64()))
+        .and_then(|port| u16::try_from(port).ok())
+        .filter(|port| *port != 0)
+        .unwrap_or(DEFAULT_GATEWAY_PORT)
 }
 
 /// 获取日志文件路径
 pub fn get_log_file_path() -> String {
-    if is_windows() {
-        format!("{}\\logs\\gateway.err.log", get_config_dir())
-    } else {
-        format!("{}/logs/gateway.err.log", get_config_dir())
-    }
+    std::path::Path::new(&get_log_dir())
+        .join("gateway.err.log")
+        .display()
+        .to_string()
 }
 
 /// 检测当前平台是否为 macOS
@@ -0,0 +1,223 @@
+use crate::models::{OpenClawConfig, PluginCatalogEntry};
+use crate::utils::{channel_registry, shell};
+use serde_json::{json, Value};
+
+/// `openclaw plugins list` 输出中的一个插件
+#[derive(Debug, Clone, Default)]
+pub struct ListedPlugin {
+    pub id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub source: Option<String>,
+    /// 插件列表中的状态（loaded / enabled 为 true，disabled 为 false）
+    pub enabled: Option<bool>,
+}
+
+/// 插件目录：渠道描述中声明了 plugin_package 的渠道插件
+pub fn catalogue() -> Vec<PluginCatalogEntry> {
+    channel_registry::all()
+        .into_iter()
+        .filter_map(|channel| {
+            let package = channel.plugin_package?;
+            Some(PluginCatalogEntry {
+                id: channel.id.clone(),
+                name: channel.name,
+                package,
+                channel: Some(channel.id),
+            })
+        })
+        .collect()
+}
+
+/// 按插件 ID 或安装包查找目录条目
+pub fn find_in_catalogue(spec: &str) -> Option<PluginCatalogEntry> {
+    catalogue()
+        .into_iter()
+        .find(|entry| entry.id.eq_ignore_ascii_case(spec) || entry.package == spec)
+}
+
+/// 运行 `openclaw plugins list` 获取已安装的插件，优先使用 --json 输出
+pub fn list_installed() -> Result<Vec<ListedPlugin>, String> {
+    let output = shell::run_openclaw(&["plugins", "list", "--json"])
+        .or_else(|_| shell::run_openclaw(&["plugins", "list"]))?;
+    Ok(parse_list(&output))
+}
+
+/// 解析 `openclaw plugins list` 的输出：JSON（数组或 {"plugins": [...]}）优先，否则按表格解析
+pub fn parse_list(output: &str) -> Vec<ListedPlugin> {
+    let json = shell::extract_json_from_output(output)
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok());
+    if let Some(value) = json {
+        let items = match &value {
+            Value::Array(items) => Some(items),
+            other => other.get("plugins").and_then(|p| p.as_array()),
+        };
+        if let Some(items) = items {
+            return items.iter().filter_map(parse_json_item).collect();
+        }
+    }
+    parse_table(&shell::strip_ansi_codes(output))
+}
+
+fn parse_json_item(item: &Value) -> Option<ListedPlugin> {
+    let text = |key: &str| {
+        item.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let id = text("id").or_else(|| text("name"))?;
+    let enabled = item
+        .get("enabled")
+        .and_then(|v| v.as_bool())
+        .or_else(|| text("status").and_then(|s| status_enabled(&s)));
+    Some(ListedPlugin {
+        id,
+        name: text("name"),
+        version: text("version"),
+        source: text("source").or_else(|| text("origin")),
+        enabled,
+    })
+}
+
+/// 解析表格输出，表头中需要有 ID 或 Name 列，其余列（Status、Source/Origin、Version）可选
+/// 如: "│ Feishu │ feishu │ loaded │ global │ 0.1.2 │"
+fn parse_table(output: &str) -> Vec<ListedPlugin> {
+    let mut columns: Option<Vec<String>> = None;
+    let mut plugins: Vec<ListedPlugin> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.replace(['│', '┃'], "|");
+        if !line.contains('|') {
+            continue;
+        }
+        let mut cells: Vec<&str> = line.split('|').map(str::trim).collect();
+        if cells.first() == Some(&"") {
+            cells.remove(0);
+        }
+        if cells.last() == Some(&"") {
+            cells.pop();
+        }
+
+        let Some(header) = &columns else {
+            let lower: Vec<String> = cells.iter().map(|c| c.to_lowercase()).collect();
+            if lower.iter().any(|c| c == "id" || c == "name" || c == "plugin") {
+                columns = Some(lower);
+            }
+            continue;
+        };
+
+        let cell = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.contains(&h.as_str()))
+                .and_then(|i| cells.get(i))
+                .filter(|c| !c.is_empty() && !c.chars().all(|ch| ch == '-' || ch == '─'))
+                .map(|c| c.to_string())
+        };
+        // 长单元格换行后的续行：第一列为空，拼接到上一个插件
+        if cells.first().is_some_and(|c| c.is_empty()) {
+            if let Some(last) = plugins.last_mut() {
+                if let Some(rest) = cell(&["id"]) {
+                    last.id.push_str(&rest);
+                }
+                if let (Some(name), Some(rest)) = (last.name.as_mut(), cell(&["name", "plugin"])) {
+                    name.push(' ');
+                    name.push_str(&rest);
+                }
+            }
+            continue;
+        }
+        let Some(id) = cell(&["id"]).or_else(|| cell(&["name", "plugin"])) else {
+            continue;
+        };
+        plugins.push(ListedPlugin {
+            id,
+            name: cell(&["name", "plugin"]),
+            version: cell(&["version"]),
+            source: cell(&["source", "origin"]),
+            enabled: cell(&["status", "state", "enabled"]).and_then(|s| status_enabled(&s)),
+        });
+    }
+    plugins
+}
+
+fn status_enabled(status: &str) -> Option<bool> {
+    let status = status.to_lowercase();
+    if status.contains("disabled") || status == "no" || status == "false" {
+        Some(false)
+    } else if status.contains("loaded")
+        || status.contains("enabled")
+        || status == "yes"
+        || status == "true"
+    {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// 启用或停用插件：设置 plugins.entries.<插件>.enabled（保留条目中的其他字段），启用时加入 plugins.allow
+pub fn set_enabled(config: &mut OpenClawConfig, plugin_id: &str, enabled: bool) {
+    let entry = config
+        .plugins
+        .entries
+        .entry(plugin_id.to_string())
+        .or_insert_with(|| json!({}));
+    if !entry.is_object() {
+        *entry = json!({});
+    }
+    entry["enabled"] = json!(enabled);
+
+    if enabled && !config.plugins.allow.iter().any(|id| id == plugin_id) {
+        config.plugins.allow.push(plugin_id.to_string());
+    }
+}
+
+/// 从 plugins.allow 和 plugins.entries 中移除插件，保留安装记录
+pub fn remove_entry(config: &mut OpenClawConfig, plugin_id: &str) {
+    config.plugins.allow.retain(|id| id != plugin_id);
+    config.plugins.entries.remove(plugin_id);
+}
+
+/// 完全移除插件：plugins.allow、plugins.entries 和 plugins.installs
+pub fn remove(config: &mut OpenClawConfig, plugin_id: &str) {
+    remove_entry(config, plugin_id);
+    config.plugins.installs.remove(plugin_id);
+}
+
+/// 记录插件的安装来源；OpenClaw 已写入安装记录时只更新版本
+pub fn record_install(config: &mut OpenClawConfig, plugin_id: &str, spec: &str, version: Option<&str>) {
+    let source = if spec.starts_with('.') || spec.starts_with('/') { "path" } else { "npm" };
+    config
+        .plugins
+        .installs
+        .entry(plugin_id.to_string())
+        .or_insert_with(|| {
+            json!({
+                "source": source,
+                "spec": spec,
+                "installedAt": chrono::Utc::now().to_rfc3339(),
+            })
+        });
+    if let Some(version) = version {
+        set_installed_version(config, plugin_id, version);
+    }
+}
+
+/// 更新安装记录中的版本，没有安装记录时返回 false
+pub fn set_installed_version(config: &mut OpenClawConfig, plugin_id: &str, version: &str) -> bool {
+    match config
+        .plugins
+        .installs
+        .get_mut(plugin_id)
+        .and_then(|r| r.as_object_mut())
+    {
+        Some(record) => {
+            record.insert("version".to_string(), json!(version));
+            true
+        }
+        None => false,
+    }
+}
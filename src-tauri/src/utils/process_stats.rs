@@ -0,0 +1,216 @@
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+use std::process::Command;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+/// Windows CREATE_NO_WINDOW 标志，用于隐藏控制台窗口
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 进程资源占用
+#[derive(Debug, Clone, Default)]
+pub struct ProcessStats {
+    /// 运行时长（秒）
+    pub uptime_seconds: Option<u64>,
+    /// 常驻内存（MB）
+    pub memory_mb: Option<f64>,
+    /// CPU 使用率（单核百分比，多核满载可超过 100）
+    pub cpu_percent: Option<f64>,
+}
+
+/// 采集指定进程的运行时长、内存和 CPU 使用率
+pub fn sample(pid: u32) -> ProcessStats {
+    #[cfg(target_os = "linux")]
+    {
+        let uptime_seconds = linux::uptime_seconds(pid);
+        let memory_mb = linux::rss_kb(pid).map(|kb| kb as f64 / 1024.0);
+        let cpu_percent = linux::cpu_percent(pid);
+        ProcessStats { uptime_seconds, memory_mb, cpu_percent }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::sample(pid).unwrap_or_default()
+    }
+
+    #[cfg(windows)]
+    {
+        ProcessStats {
+            uptime_seconds: None,
+            memory_mb: windows::working_set_kb(pid).map(|kb| kb as f64 / 1024.0),
+            cpu_percent: None,
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    {
+        let _ = pid;
+        ProcessStats::default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::Command;
+    use log::debug;
+    use std::sync::{Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+
+    /// 两次 CPU 采样之间的最小间隔，过短时结果噪声很大
+    const MIN_CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+    /// 上一次 CPU 采样
+    struct CpuSample {
+        pid: u32,
+        /// 累计 CPU 时间
+        time: Duration,
+        /// 采样时刻
+        at: Instant,
+        /// 根据再上一次采样算出的使用率
+        percent: Option<f64>,
+    }
+
+    static LAST_CPU_SAMPLE: Mutex<Option<CpuSample>> = Mutex::new(None);
+
+    /// 每秒时钟滴答数（/proc 中时间字段的单位）
+    fn clock_ticks() -> f64 {
+        static CLK_TCK: OnceLock<f64> = OnceLock::new();
+        *CLK_TCK.get_or_init(|| {
+            Command::new("getconf")
+                .arg("CLK_TCK")
+                .output()
+                .ok()
+                .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(100.0)
+        })
+    }
+
+    /// 读取 /proc/<pid>/stat 中进程名之后的字段（第 3 个字段起）
+    fn stat_fields(pid: u32) -> Option<Vec<String>> {
+        let content = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        // 进程名可能包含空格和括号，以最后一个 ')' 为界
+        let rest = &content[content.rfind(')')? + 1..];
+        Some(rest.split_whitespace().map(|s| s.to_string()).collect())
+    }
+
+    /// 进程累计 CPU 时间（utime + stime）
+    fn cpu_time(pid: u32) -> Option<Duration> {
+        let fields = stat_fields(pid)?;
+        // utime 为第 14 个字段，stime 为第 15 个字段
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+        Some(Duration::from_secs_f64((utime + stime) as f64 / clock_ticks()))
+    }
+
+    /// 进程运行时长：系统运行时间减去进程启动时刻（第 22 个字段）
+    pub fn uptime_seconds(pid: u32) -> Option<u64> {
+        let fields = stat_fields(pid)?;
+        let start_ticks: u64 = fields.get(19)?.parse().ok()?;
+        let system_uptime: f64 = std::fs::read_to_string("/proc/uptime")
+            .ok()?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()?;
+        let started_after_boot = start_ticks as f64 / clock_ticks();
+        Some((system_uptime - started_after_boot).max(0.0) as u64)
+    }
+
+    /// 根据与上一次采样之间累计 CPU 时间的差值计算 CPU 使用率
+    /// 不在调用中等待：同一进程的首次采样返回 None，距上次采样过近时返回上次的结果
+    pub fn cpu_percent(pid: u32) -> Option<f64> {
+        let now_time = cpu_time(pid)?;
+        let now_at = Instant::now();
+
+        let mut last = LAST_CPU_SAMPLE.lock().unwrap_or_else(|e| e.into_inner());
+        let prev = match last.as_ref() {
+            Some(prev) if prev.pid == pid => prev,
+            _ => {
+                *last = Some(CpuSample { pid, time: now_time, at: now_at, percent: None });
+                return None;
+            }
+        };
+
+        let wall = now_at.duration_since(prev.at);
+        if wall < MIN_CPU_SAMPLE_INTERVAL {
+            return prev.percent;
+        }
+        let busy = now_time.saturating_sub(prev.time).as_secs_f64();
+        let percent = ((busy / wall.as_secs_f64() * 100.0) * 10.0).round() / 10.0;
+        *last = Some(CpuSample { pid, time: now_time, at: now_at, percent: Some(percent) });
+        drop(last);
+
+        debug!("[进程统计] PID {} CPU: {:.1}%", pid, percent);
+        Some(percent)
+    }
+
+    /// 常驻内存（kB），来自 /proc/<pid>/status 的 VmRSS
+    pub fn rss_kb(pid: u32) -> Option<u64> {
+        let content = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+        content
+            .lines()
+            .find(|line| line.starts_with("VmRSS:"))?
+            .split_whitespace()
+            .nth(1)?
+            .parse()
+            .ok()
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{Command, ProcessStats};
+
+    /// 解析 ps 的 etime 格式：[[dd-]hh:]mm:ss
+    fn parse_etime(etime: &str) -> Option<u64> {
+        let (days, clock) = match etime.split_once('-') {
+            Some((d, rest)) => (d.parse::<u64>().ok()?, rest),
+            None => (0, etime),
+        };
+        let mut seconds = 0u64;
+        for part in clock.split(':') {
+            seconds = seconds * 60 + part.parse::<u64>().ok()?;
+        }
+        Some(days * 86400 + seconds)
+    }
+
+    /// 通过 ps 获取运行时长、RSS 和 CPU 使用率
+    pub fn sample(pid: u32) -> Option<ProcessStats> {
+        let output = Command::new("ps")
+            .args(["-o", "etime=,rss=,%cpu=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut parts = stdout.split_whitespace();
+        let uptime_seconds = parts.next().and_then(parse_etime);
+        let memory_mb = parts
+            .next()
+            .and_then(|v| v.parse::<f64>().ok())
+            .map(|kb| kb / 1024.0);
+        let cpu_percent = parts.next().and_then(|v| v.parse::<f64>().ok());
+        Some(ProcessStats { uptime_seconds, memory_mb, cpu_percent })
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use super::{Command, CommandExt, CREATE_NO_WINDOW};
+
+    /// 通过 tasklist 获取工作集内存（kB）
+    pub fn working_set_kb(pid: u32) -> Option<u64> {
+        let mut cmd = Command::new("tasklist");
+        cmd.args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        let output = cmd.output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // 格式: "node.exe","1234","Console","1","123,456 K"
+        let mem_field = stdout.lines().next()?.rsplit("\",\"").next()?;
+        let digits: String = mem_field.chars().filter(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    }
+}
@@ -0,0 +1,896 @@
+use crate::models::{ModelTestResult, Profile, ProviderConfig, ProviderErrorClass, SuggestedModel};
+use crate::utils::secrets;
+use log::debug;
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// Anthropic API 版本头
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Ollama 默认端口
+const OLLAMA_PORT: &str = ":11434";
+/// 连通性测试的提示词
+const TEST_PROMPT: &str = "回复 OK";
+/// 连通性测试的最大输出 token 数
+const TEST_MAX_TOKENS: u32 = 16;
+
+/// Provider 接口协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderProtocol {
+    /// OpenAI 兼容接口（/models、/chat/completions）
+    OpenAi,
+    /// Anthropic Messages 接口
+    Anthropic,
+    /// Ollama 原生接口（/api/tags），对话走 OpenAI 兼容接口
+    Ollama,
+}
+
+/// 访问 Provider 所需的连接信息
+/// 直接构造时可指向任意地址（如本地 mock 服务器）
+#[derive(Debug, Clone)]
+pub struct ProviderEndpoint {
+    pub protocol: ProviderProtocol,
+    pub base_url: String,
+    pub api_key: Option<String>,
+}
+
+impl ProviderEndpoint {
+    /// 从 openclaw.json 中的 Provider 配置构建，`${NAME}` 形式的 API Key 从密钥库解析
+    pub fn from_config(
+        name: &str,
+        provider: &ProviderConfig,
+        profile: &Profile,
+    ) -> Result<Self, String> {
+        let base_url = provider.base_url.trim().to_string();
+        if base_url.is_empty() {
+            return Err(format!("Provider {} 未配置 baseUrl", name));
+        }
+
+        let api_type = provider
+            .extra
+            .get("api")
+            .and_then(|v| v.as_str())
+            .or_else(|| provider.models.iter().find_map(|m| m.api.as_deref()))
+            .unwrap_or("openai-completions");
+        let protocol = if api_type.starts_with("anthropic") {
+            ProviderProtocol::Anthropic
+        } else if name == "ollama" || base_url.contains(OLLAMA_PORT) {
+            ProviderProtocol::Ollama
+        } else {
+            ProviderProtocol::OpenAi
+        };
+
+        let api_key = match provider.api_key.as_deref().filter(|k| !k.is_empty()) {
+            Some(key) => Some(
+                secrets::resolve(profile, key)
+                    .ok_or_else(|| format!("Provider {} 的 API Key 引用的密钥不存在: {}", name, key))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            protocol,
+            base_url,
+            api_key,
+        })
+    }
+
+    fn base(&self) -> &str {
+        self.base_url.trim_end_matches('/')
+    }
+
+    /// Anthropic API 根路径（统一带 /v1）
+    pub fn anthropic_root(&self) -> String {
+        let base = self.base();
+        if base.ends_with("/v1") {
+            base.to_string()
+        } else {
+            format!("{}/v1", base)
+        }
+    }
+
+    /// OpenAI 兼容 API 根路径（baseUrl 通常已包含 /v1）
+    pub fn openai_root(&self) -> String {
+        self.base().to_string()
+    }
+
+    /// Ollama 原生 API 根路径（去掉 OpenAI 兼容接口的 /v1）
+    fn ollama_root(&self) -> String {
+        let base = self.base();
+        base.strip_suffix("/v1").unwrap_or(base).to_string()
+    }
+
+    /// 对话接口地址：Anthropic 为 /v1/messages，其他为 OpenAI 兼容的 /chat/completions
+    fn chat_url(&self) -> String {
+        match self.protocol {
+            ProviderProtocol::Anthropic => format!("{}/messages", self.anthropic_root()),
+            ProviderProtocol::OpenAi => format!("{}/chat/completions", self.openai_root()),
+            ProviderProtocol::Ollama => format!("{}/v1/chat/completions", self.ollama_root()),
+        }
+    }
+
+    /// 构建带认证头的请求
+    pub fn request(&self, client: &Client, method: Method, url: &str) -> RequestBuilder {
+        let mut request = client.request(method, url);
+        match self.protocol {
+            ProviderProtocol::Anthropic => {
+                request = request.header("anthropic-version", ANTHROPIC_VERSION);
+                if let Some(key) = &self.api_key {
+                    request = request.header("x-api-key", key);
+                }
+            }
+            ProviderProtocol::OpenAi | ProviderProtocol::Ollama => {
+                if let Some(key) = &self.api_key {
+                    request = request.bearer_auth(key);
+                }
+            }
+        }
+        request
+    }
+}
+
+/// 创建 HTTP 客户端
+pub fn http_client(timeout: Duration) -> Result<Client, String> {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))
+}
+
+/// 截断过长的响应正文，用于错误信息
+pub fn truncate_body(body: &str, max_chars: usize) -> String {
+    let body = body.trim();
+    if body.chars().count() > max_chars {
+        format!("{}...", body.chars().take(max_chars).collect::<String>())
+    } else {
+        body.to_string()
+    }
+}
+
+/// 发送请求并解析 JSON 响应，非 2xx 状态返回错误
+async fn fetch_json(request: RequestBuilder, url: &str) -> Result<Value, String> {
+    debug!("[Provider API] 请求 {}", url);
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("请求 {} 失败: {}", url, e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("读取 {} 响应失败: {}", url, e))?;
+
+    if !status.is_success() {
+        return Err(format!(
+            "{} 返回 HTTP {}: {}",
+            url,
+            status.as_u16(),
+            truncate_body(&body, 200)
+        ));
+    }
+    serde_json::from_str(&body).map_err(|e| format!("解析 {} 响应失败: {}", url, e))
+}
+
+/// 列出 Provider 当前可用的模型
+/// OpenAI 兼容：GET /models；Anthropic：GET /v1/models（分页）；Ollama：GET /api/tags
+pub async fn list_models(
+    client: &Client,
+    endpoint: &ProviderEndpoint,
+) -> Result<Vec<SuggestedModel>, String> {
+    let mut models = match endpoint.protocol {
+        ProviderProtocol::OpenAi => list_openai_models(client, endpoint).await?,
+        ProviderProtocol::Anthropic => list_anthropic_models(client, endpoint).await?,
+        ProviderProtocol::Ollama => list_ollama_models(client, endpoint).await?,
+    };
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    models.dedup_by(|a, b| a.id == b.id);
+    Ok(models)
+}
+
+async fn list_openai_models(
+    client: &Client,
+    endpoint: &ProviderEndpoint,
+) -> Result<Vec<SuggestedModel>, String> {
+    let url = format!("{}/models", endpoint.openai_root());
+    let body = fetch_json(endpoint.request(client, Method::GET, &url), &url).await?;
+
+    // 标准格式为 {"data": [...]}，部分兼容服务直接返回数组
+    let items = body
+        .get("data")
+        .and_then(|d| d.as_array())
+        .or_else(|| body.as_array())
+        .ok_or_else(|| format!("{} 响应中没有模型列表", url))?;
+    Ok(items.iter().filter_map(model_from_json).collect())
+}
+
+async fn list_anthropic_models(
+    client: &Client,
+    endpoint: &ProviderEndpoint,
+) -> Result<Vec<SuggestedModel>, String> {
+    let root = endpoint.anthropic_root();
+    let mut models = Vec::new();
+    let mut after_id: Option<String> = None;
+
+    loop {
+        let url = match &after_id {
+            Some(id) => format!("{}/models?limit=100&after_id={}", root, id),
+            None => format!("{}/models?limit=100", root),
+        };
+        let body = fetch_json(endpoint.request(client, Method::GET, &url), &url).await?;
+        let items = body
+            .get("data")
+            .and_then(|d| d.as_array())
+            .ok_or_else(|| format!("{} 响应中没有模型列表", url))?;
+        models.extend(items.iter().filter_map(model_from_json));
+
+        let has_more = body.get("has_more").and_then(|v| v.as_bool()).unwrap_or(false);
+        let last_id = body.get("last_id").and_then(|v| v.as_str()).map(str::to_string);
+        match last_id {
+            Some(id) if has_more && after_id.as_deref() != Some(id.as_str()) => after_id = Some(id),
+            _ => break,
+        }
+    }
+    Ok(models)
+}
+
+async fn list_ollama_models(
+    client: &Client,
+    endpoint: &ProviderEndpoint,
+) -> Result<Vec<SuggestedModel>, String> {
+    let root = endpoint.ollama_root();
+    let url = format!("{}/api/tags", root);
+    let body = fetch_json(endpoint.request(client, Method::GET, &url), &url).await?;
+    let items = body
+        .get("models")
+        .and_then(|m| m.as_array())
+        .ok_or_else(|| format!("{} 响应中没有模型列表", url))?;
+
+    let mut models = Vec::new();
+    for item in items {
+        let Some(id) = item
+            .get("model")
+            .or_else(|| item.get("name"))
+            .and_then(|v| v.as_str())
+        else {
+            continue;
+        };
+
+        let description = item
+            .pointer("/details/parameter_size")
+            .and_then(|v| v.as_str())
+            .map(|size| format!("{} 参数", size));
+
+        // /api/show 提供上下文长度和能力，失败时忽略
+        let show_url = format!("{}/api/show", root);
+        let show = fetch_json(
+            endpoint
+                .request(client, Method::POST, &show_url)
+                .json(&json!({ "model": id })),
+            &show_url,
+        )
+        .await
+        .unwrap_or(Value::Null);
+
+        let context_window = show
+            .get("model_info")
+            .and_then(|info| info.as_object())
+            .and_then(|info| {
+                info.iter()
+                    .find(|(key, _)| key.ends_with(".context_length"))
+                    .and_then(|(_, v)| as_u32(v))
+            });
+
+        models.push(SuggestedModel {
+            id: id.to_string(),
+            name: id.to_string(),
+            description,
+            context_window,
+            max_tokens: None,
+            recommended: false,
+            capabilities: capabilities_of(&show),
+        });
+    }
+    Ok(models)
+}
+
+fn as_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|n| u32::try_from(n).ok())
+}
+
+/// 取第一个存在的数值字段
+fn first_u32(item: &Value, keys: &[&str]) -> Option<u32> {
+    keys.iter().find_map(|key| item.pointer(key).and_then(as_u32))
+}
+
+/// 将模型列表接口返回的条目转换为推荐模型，兼容各服务商的常见字段
+fn model_from_json(item: &Value) -> Option<SuggestedModel> {
+    let id = item.get("id").and_then(|v| v.as_str())?.to_string();
+    let name = item
+        .get("display_name")
+        .or_else(|| item.get("name"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(&id)
+        .to_string();
+
+    Some(SuggestedModel {
+        description: item
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        context_window: first_u32(
+            item,
+            &[
+                "/context_window",
+                "/context_length",
+                "/max_context_length",
+                "/max_input_tokens",
+                "/input_token_limit",
+                "/top_provider/context_length",
+            ],
+        ),
+        max_tokens: first_u32(
+            item,
+            &[
+                "/max_tokens",
+                "/max_output_tokens",
+                "/max_completion_tokens",
+                "/output_token_limit",
+                "/top_provider/max_completion_tokens",
+            ],
+        ),
+        recommended: false,
+        capabilities: capabilities_of(item),
+        id,
+        name,
+    })
+}
+
+/// 提取模型能力
+/// 支持 `capabilities` 数组或 `{能力: true}` 对象、`architecture.input_modalities`
+/// 和 `supported_parameters`（OpenRouter）
+fn capabilities_of(item: &Value) -> Vec<String> {
+    let mut capabilities: Vec<String> = Vec::new();
+
+    match item.get("capabilities") {
+        Some(Value::Array(list)) => {
+            capabilities.extend(list.iter().filter_map(|v| v.as_str()).map(str::to_string));
+        }
+        Some(Value::Object(map)) => {
+            for (name, value) in map {
+                let supported = value.as_bool().unwrap_or_else(|| {
+                    value.get("supported").and_then(|s| s.as_bool()).unwrap_or(false)
+                });
+                if supported {
+                    capabilities.push(name.clone());
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let contains = |pointer: &str, needle: &str| {
+        item.pointer(pointer)
+            .and_then(|v| v.as_array())
+            .is_some_and(|list| list.iter().any(|v| v.as_str() == Some(needle)))
+    };
+    if contains("/architecture/input_modalities", "image") {
+        capabilities.push("vision".to_string());
+    }
+    if contains("/supported_parameters", "tools") {
+        capabilities.push("tools".to_string());
+    }
+    if contains("/supported_parameters", "reasoning") {
+        capabilities.push("reasoning".to_string());
+    }
+
+    capabilities.sort();
+    capabilities.dedup();
+    capabilities
+}
+
+/// 用最小的流式请求测试模型是否可用，记录 HTTP 状态、错误分类、首 token 延迟和总耗时
+/// `api_type` 为模型配置的 `api`（anthropic-messages / openai-completions），为空时沿用 Provider 的协议
+pub async fn test_model(
+    client: &Client,
+    endpoint: &ProviderEndpoint,
+    provider: &str,
+    model_id: &str,
+    api_type: Option<&str>,
+) -> ModelTestResult {
+    let mut endpoint = endpoint.clone();
+    match api_type {
+        Some(api) if api.starts_with("anthropic") => endpoint.protocol = ProviderProtocol::Anthropic,
+        Some(api) if api.starts_with("openai") && endpoint.protocol == ProviderProtocol::Anthropic => {
+            endpoint.protocol = ProviderProtocol::OpenAi
+        }
+        _ => {}
+    }
+
+    let mut result = ModelTestResult {
+        success: false,
+        provider: provider.to_string(),
+        model: model_id.to_string(),
+        api: match endpoint.protocol {
+            ProviderProtocol::Anthropic => "anthropic-messages",
+            _ => "openai-completions",
+        }
+        .to_string(),
+        status: None,
+        error_class: None,
+        error: None,
+        response: None,
+        first_token_ms: None,
+        total_ms: 0,
+    };
+
+    let url = endpoint.chat_url();
+    let started = Instant::now();
+    // 部分 OpenAI 模型（如 o 系列）不接受 max_tokens，按提示改用 max_completion_tokens 重试一次
+    let mut token_field = "max_tokens";
+    let response = loop {
+        let body = test_request_body(model_id, token_field);
+        debug!("[Provider API] 测试请求 {} ({})", url, model_id);
+        let response = match endpoint.request(client, Method::POST, &url).json(&body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let class = if e.is_timeout() {
+                    ProviderErrorClass::Timeout
+                } else {
+                    ProviderErrorClass::Network
+                };
+                result.error_class = Some(class);
+                result.error = Some(format!("请求 {} 失败: {}", url, e));
+                result.total_ms = elapsed_ms(started);
+                return result;
+            }
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            break response;
+        }
+        let body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 400
+            && token_field == "max_tokens"
+            && endpoint.protocol != ProviderProtocol::Anthropic
+            && body.contains("max_completion_tokens")
+        {
+            token_field = "max_completion_tokens";
+            continue;
+        }
+
+        result.status = Some(status.as_u16());
+        result.error_class = Some(classify_error(Some(status.as_u16()), &body));
+        result.error = Some(format!("HTTP {}: {}", status.as_u16(), truncate_body(&body, 300)));
+        result.total_ms = elapsed_ms(started);
+        return result;
+    };
+
+    result.status = Some(response.status().as_u16());
+    let outcome = read_test_response(response, endpoint.protocol, started).await;
+    result.total_ms = elapsed_ms(started);
+    result.first_token_ms = outcome.first_token_ms;
+    match outcome.error {
+        Some(error) => {
+            result.error_class = Some(classify_error(None, &error));
+            result.error = Some(truncate_body(&error, 300));
+        }
+        None => {
+            result.success = true;
+            result.response = Some(outcome.text.trim().to_string()).filter(|t| !t.is_empty());
+        }
+    }
+    result
+}
+
+fn test_request_body(model_id: &str, token_field: &str) -> Value {
+    let mut body = json!({
+        "model": model_id,
+        "messages": [{ "role": "user", "content": TEST_PROMPT }],
+        "stream": true,
+    });
+    body[token_field] = json!(TEST_MAX_TOKENS);
+    body
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+/// 测试请求的响应内容
+#[derive(Default)]
+struct TestOutcome {
+    text: String,
+    first_token_ms: Option<u64>,
+    /// 流中途返回的错误（如 Anthropic 的 overloaded_error）
+    error: Option<String>,
+}
+
+/// 读取测试请求的响应：SSE 流逐行解析以记录首 token 时间，
+/// 忽略 `stream` 参数直接返回 JSON 的兼容服务则以完整响应的时间作为首 token 时间
+async fn read_test_response(
+    mut response: Response,
+    protocol: ProviderProtocol,
+    started: Instant,
+) -> TestOutcome {
+    let mut outcome = TestOutcome::default();
+    let is_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/event-stream"));
+
+    if !is_stream {
+        match response.text().await {
+            Ok(body) => {
+                let value: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+                if let Some(error) = value.get("error").filter(|e| !e.is_null()) {
+                    outcome.error = Some(error.to_string());
+                } else {
+                    outcome.text = message_text(&value, protocol);
+                    outcome.first_token_ms = Some(elapsed_ms(started));
+                }
+            }
+            Err(e) => outcome.error = Some(format!("读取响应失败: {}", e)),
+        }
+        return outcome;
+    }
+
+    let mut buffer: Vec<u8> = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(bytes)) => buffer.extend_from_slice(&bytes),
+            Ok(None) => break,
+            Err(e) => {
+                outcome.error = Some(format!("读取响应流失败: {}", e));
+                break;
+            }
+        }
+        // 按行处理，不完整的行留到下一个分块
+        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if data == "[DONE]" {
+                continue;
+            }
+            let Ok(event) = serde_json::from_str::<Value>(data) else {
+                continue;
+            };
+
+            let is_error = event.get("type").and_then(|t| t.as_str()) == Some("error")
+                || event.get("error").is_some_and(|e| !e.is_null());
+            if is_error {
+                outcome.error = Some(event.get("error").unwrap_or(&event).to_string());
+                return outcome;
+            }
+            if let Some(delta) = stream_delta(&event, protocol) {
+                if outcome.first_token_ms.is_none() {
+                    outcome.first_token_ms = Some(elapsed_ms(started));
+                }
+                outcome.text.push_str(delta);
+            }
+        }
+    }
+    outcome
+}
+
+/// 流式事件中的增量输出（含思考内容），无输出的事件返回 None
+fn stream_delta(event: &Value, protocol: ProviderProtocol) -> Option<&str> {
+    match protocol {
+        ProviderProtocol::Anthropic => {
+            if event.get("type").and_then(|t| t.as_str()) != Some("content_block_delta") {
+                return None;
+            }
+            let delta = event.get("delta")?;
+            // 思考内容只计入首 token 时间，不计入回复
+            match delta.get("text").and_then(|t| t.as_str()) {
+                Some(text) => Some(text),
+                None => delta.get("thinking").map(|_| ""),
+            }
+        }
+        ProviderProtocol::OpenAi | ProviderProtocol::Ollama => {
+            let delta = event.pointer("/choices/0/delta")?;
+            match delta.get("content").and_then(|c| c.as_str()).filter(|c| !c.is_empty()) {
+                Some(text) => Some(text),
+                None => delta
+                    .get("reasoning_content")
+                    .or_else(|| delta.get("reasoning"))
+                    .and_then(|r| r.as_str())
+                    .filter(|r| !r.is_empty())
+                    .map(|_| ""),
+            }
+        }
+    }
+}
+
+/// 非流式响应中的回复文本
+fn message_text(value: &Value, protocol: ProviderProtocol) -> String {
+    let text = match protocol {
+        ProviderProtocol::Anthropic => value.pointer("/content/0/text"),
+        ProviderProtocol::OpenAi | ProviderProtocol::Ollama => {
+            value.pointer("/choices/0/message/content")
+        }
+    };
+    text.and_then(|t| t.as_str()).unwrap_or_default().to_string()
+}
+
+/// 根据 HTTP 状态码和错误正文判断错误类别
+/// 正文优先：不少兼容服务用 400/403 表示额度不足或模型不存在
+pub fn classify_error(status: Option<u16>, body: &str) -> ProviderErrorClass {
+    let body = body.to_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|n| body.contains(n));
+
+    if mentions(&[
+        "insufficient_quota",
+        "quota",
+        "insufficient balance",
+        "billing",
+        "credit balance",
+        "余额",
+        "额度",
+    ]) {
+        return ProviderErrorClass::Quota;
+    }
+    if mentions(&[
+        "model_not_found",
+        "model not found",
+        "does not exist",
+        "unknown model",
+        "invalid model",
+        "no such model",
+        "模型不存在",
+    ]) {
+        return ProviderErrorClass::ModelNotFound;
+    }
+    if mentions(&[
+        "invalid_api_key",
+        "invalid x-api-key",
+        "invalid api key",
+        "incorrect api key",
+        "authentication_error",
+        "permission_error",
+        "unauthorized",
+    ]) {
+        return ProviderErrorClass::Auth;
+    }
+    if mentions(&[
+        "rate_limit",
+        "rate limit",
+        "too many requests",
+    ]) {
+        return ProviderErrorClass::RateLimited;
+    }
+    if mentions(&[
+        "overloaded",
+        "internal server error",
+        "api_error",
+    ]) {
+        return ProviderErrorClass::Server;
+    }
+
+    match status {
+        Some(401 | 403) => ProviderErrorClass::Auth,
+        Some(402) => ProviderErrorClass::Quota,
+        Some(429) => ProviderErrorClass::RateLimited,
+        // Anthropic 对未知模型返回 404 not_found_error
+        Some(404) if body.contains("model") => ProviderErrorClass::ModelNotFound,
+        Some(400 | 404 | 405 | 413 | 422) => ProviderErrorClass::InvalidRequest,
+        Some(code) if code >= 500 => ProviderErrorClass::Server,
+        _ => ProviderErrorClass::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 收到的请求：(方法, 路径含查询串, 小写的请求头)
+    type Recorded = Arc<Mutex<Vec<(String, String, Vec<(String, String)>)>>>;
+
+    /// 启动本地 mock HTTP 服务器，按 (方法, 路径含查询串) 返回预设的 JSON，未匹配时返回 404
+    async fn mock_server(routes: Vec<(&'static str, &'static str, Value)>) -> (String, Recorded) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let recorded: Recorded = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&recorded);
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let routes = routes.clone();
+                let log = Arc::clone(&log);
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    // 读取请求头和正文（按 Content-Length）
+                    let head_end = loop {
+                        let n = socket.read(&mut chunk).await.unwrap_or(0);
+                        if n == 0 {
+                            return;
+                        }
+                        buf.extend_from_slice(&chunk[..n]);
+                        if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            break i + 4;
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+                    let mut lines = head.lines();
+                    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+                    let method = request_line.next().unwrap_or("").to_string();
+                    let path = request_line.next().unwrap_or("").to_string();
+                    let headers: Vec<(String, String)> = lines
+                        .filter_map(|l| l.split_once(':'))
+                        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+                        .collect();
+                    let length: usize = headers
+                        .iter()
+                        .find(|(k, _)| k == "content-length")
+                        .and_then(|(_, v)| v.parse().ok())
+                        .unwrap_or(0);
+                    while buf.len() < head_end + length {
+                        let n = socket.read(&mut chunk).await.unwrap_or(0);
+                        if n == 0 {
+                            break;
+                        }
+                        buf.extend_from_slice(&chunk[..n]);
+                    }
+                    log.lock().unwrap().push((method.clone(), path.clone(), headers));
+
+                    let (status, body) = match routes.iter().find(|(m, p, _)| *m == method && *p == path) {
+                        Some((_, _, body)) => ("200 OK", body.to_string()),
+                        None => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        (base, recorded)
+    }
+
+    fn endpoint(protocol: ProviderProtocol, base_url: String) -> ProviderEndpoint {
+        ProviderEndpoint {
+            protocol,
+            base_url,
+            api_key: Some("test-key".to_string()),
+        }
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        headers.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    #[tokio::test]
+    async fn lists_openai_models() {
+        let (base, recorded) = mock_server(vec![(
+            "GET",
+            "/v1/models",
+            json!({
+                "object": "list",
+                "data": [
+                    { "id": "gpt-b", "context_length": 128000 },
+                    { "id": "gpt-a", "name": "GPT A" },
+                ],
+            }),
+        )])
+        .await;
+
+        let client = http_client(Duration::from_secs(5)).unwrap();
+        let models = list_models(&client, &endpoint(ProviderProtocol::OpenAi, format!("{}/v1/", base)))
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["gpt-a", "gpt-b"]);
+        assert_eq!(models[0].name, "GPT A");
+        assert_eq!(models[1].context_window, Some(128000));
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(header(&requests[0].2, "authorization"), Some("Bearer test-key"));
+    }
+
+    #[tokio::test]
+    async fn follows_anthropic_pagination() {
+        let (base, recorded) = mock_server(vec![
+            (
+                "GET",
+                "/v1/models?limit=100",
+                json!({
+                    "data": [{ "id": "claude-b", "display_name": "Claude B" }],
+                    "has_more": true,
+                    "last_id": "claude-b",
+                }),
+            ),
+            (
+                "GET",
+                "/v1/models?limit=100&after_id=claude-b",
+                json!({
+                    "data": [{ "id": "claude-a", "display_name": "Claude A" }],
+                    "has_more": false,
+                    "last_id": "claude-a",
+                }),
+            ),
+        ])
+        .await;
+
+        let client = http_client(Duration::from_secs(5)).unwrap();
+        let models = list_models(&client, &endpoint(ProviderProtocol::Anthropic, base))
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["Claude A", "Claude B"]);
+
+        let requests = recorded.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for (_, _, headers) in requests.iter() {
+            assert_eq!(header(headers, "x-api-key"), Some("test-key"));
+            assert_eq!(header(headers, "anthropic-version"), Some(ANTHROPIC_VERSION));
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_ollama_tags_with_details() {
+        let (base, recorded) = mock_server(vec![
+            (
+                "GET",
+                "/api/tags",
+                json!({
+                    "models": [{ "name": "llama3:8b", "details": { "parameter_size": "8B" } }],
+                }),
+            ),
+            (
+                "POST",
+                "/api/show",
+                json!({
+                    "model_info": { "llama.context_length": 8192 },
+                    "capabilities": ["completion", "tools"],
+                }),
+            ),
+        ])
+        .await;
+
+        let client = http_client(Duration::from_secs(5)).unwrap();
+        // baseUrl 为 OpenAI 兼容地址时，原生接口去掉 /v1
+        let models = list_models(&client, &endpoint(ProviderProtocol::Ollama, format!("{}/v1", base)))
+            .await
+            .unwrap();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "llama3:8b");
+        assert_eq!(models[0].description.as_deref(), Some("8B 参数"));
+        assert_eq!(models[0].context_window, Some(8192));
+        assert!(models[0].capabilities.iter().any(|c| c == "tools"));
+
+        let paths: Vec<String> = recorded.lock().unwrap().iter().map(|r| r.1.clone()).collect();
+        assert_eq!(paths, ["/api/tags", "/api/show"]);
+    }
+
+    #[tokio::test]
+    async fn reports_http_errors() {
+        let (base, _) = mock_server(Vec::new()).await;
+
+        let client = http_client(Duration::from_secs(5)).unwrap();
+        let error = list_models(&client, &endpoint(ProviderProtocol::OpenAi, base))
+            .await
+            .unwrap_err();
+
+        assert!(error.contains("HTTP 404"), "{}", error);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::models::{Profile, ProviderConfig};
+use crate::utils::vault::VaultBackend;
+use log::warn;
+use std::collections::BTreeMap;
+
+/// 密钥存储后端
+/// 新增后端（如系统钥匙串）只需实现此 trait 并在 `backend` 中选择
+pub trait SecretBackend {
+    /// 后端名称
+    fn name(&self) -> &'static str;
+    /// 存储位置（用于展示）
+    fn location(&self) -> String;
+    /// 加密密钥来源（用于展示）
+    fn key_source(&self) -> String;
+    /// 读取全部密钥
+    fn load_all(&self) -> Result<BTreeMap<String, String>, String>;
+    /// 保存密钥
+    fn set(&self, name: &str, value: &str) -> Result<(), String>;
+    /// 删除密钥，返回是否存在
+    fn delete(&self, name: &str) -> Result<bool, String>;
+
+    /// 读取单个密钥
+    fn get(&self, name: &str) -> Result<Option<String>, String> {
+        Ok(self.load_all()?.remove(name))
+    }
+
+    /// 批量修改（值为 None 表示删除）；后端支持时应一次性写入
+    fn apply(&self, changes: &[(String, Option<String>)]) -> Result<(), String> {
+        for (name, value) in changes {
+            match value {
+                Some(value) => self.set(name, value)?,
+                None => {
+                    self.delete(name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 获取配置档案的密钥后端（默认使用档案目录下的加密密钥库）
+pub fn backend(profile: &Profile) -> Box<dyn SecretBackend> {
+    Box::new(VaultBackend::new(profile.vault_file_path()))
+}
+
+/// 校验密钥名称：需可作为环境变量名（大写字母、数字、下划线，不以数字开头）
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "密钥名称无效: {}（仅允许大写字母、数字和下划线，且不能以数字开头）",
+            name
+        ))
+    }
+}
+
+/// 生成配置中引用密钥的占位符 `${NAME}`
+/// OpenClaw 读取配置时会用同名环境变量替换，密钥在启动 Gateway 时注入
+pub fn reference(name: &str) -> String {
+    format!("${{{}}}", name)
+}
+
+/// 解析 `${NAME}` 形式的密钥引用，返回密钥名称
+pub fn parse_reference(value: &str) -> Option<&str> {
+    let name = value.strip_prefix("${")?.strip_suffix('}')?;
+    validate_name(name).ok().map(|_| name)
+}
+
+/// 解析配置值：密钥引用从密钥库读取，其他值原样返回
+pub fn resolve(profile: &Profile, value: &str) -> Option<String> {
+    match parse_reference(value) {
+        Some(name) => backend(profile).get(name).unwrap_or_else(|e| {
+            warn!("[密钥库] 读取 {} 失败: {}", name, e);
+            None
+        }),
+        None => Some(value.to_string()),
+    }
+}
+
+/// 脱敏显示：保留首尾各 4 个字符
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() > 8 {
+        let head: String = chars[..4].iter().collect();
+        let tail: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}...{}", head, tail)
+    } else {
+        "****".to_string()
+    }
+}
+
+/// Provider API Key 在密钥库中的名称，如 anthropic -> ANTHROPIC_API_KEY
+pub fn provider_secret_name(provider_name: &str) -> String {
+    let normalized: String = provider_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    let normalized = normalized.trim_matches('_');
+    if normalized.is_empty() || normalized.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("PROVIDER_{}_API_KEY", normalized)
+    } else {
+        format!("{}_API_KEY", normalized)
+    }
+}
+
+/// Provider 实际使用的密钥名称：已引用密钥时为引用的名称，
+/// 配置中是明文 Key 时为迁移后将使用的名称，没有 Key 时为 None
+fn provider_effective_secret(provider_name: &str, provider: &ProviderConfig) -> Option<String> {
+    let key = provider.api_key.as_deref().filter(|k| !k.is_empty())?;
+    Some(
+        parse_reference(key)
+            .map(str::to_string)
+            .unwrap_or_else(|| provider_secret_name(provider_name)),
+    )
+}
+
+/// 为 Provider 选择保存 API Key 的密钥名称：`current_key` 已是密钥引用时沿用，否则按名称推导。
+/// 与其他 Provider 的密钥名称相同（如 foo-bar 与 foo_bar 都是 FOO_BAR_API_KEY）时返回错误，避免互相覆盖
+pub fn provider_secret_name_for(
+    providers: &BTreeMap<String, ProviderConfig>,
+    provider_name: &str,
+    current_key: Option<&str>,
+) -> Result<String, String> {
+    let name = current_key
+        .and_then(parse_reference)
+        .map(str::to_string)
+        .unwrap_or_else(|| provider_secret_name(provider_name));
+
+    let clash = providers.iter().find(|(other, provider)| {
+        other.as_str() != provider_name
+            && provider_effective_secret(other, provider).as_deref() == Some(name.as_str())
+    });
+    match clash {
+        Some((other, _)) => Err(format!(
+            "Provider {} 与 {} 的密钥名称相同（{}），请修改其中一个 Provider 的名称",
+            provider_name, other, name
+        )),
+        None => Ok(name),
+    }
+}
+
+/// 判断 env 文件中的变量是否应作为密钥保存
+pub fn is_secret_env_key(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    ["KEY", "TOKEN", "SECRET", "PASSWORD"]
+        .iter()
+        .any(|marker| key.contains(marker))
+}
+
+/// 启动 openclaw 时需要注入的密钥环境变量（读取失败时记录警告并返回空）
+pub fn env_vars(profile: &Profile) -> BTreeMap<String, String> {
+    backend(profile).load_all().unwrap_or_else(|e| {
+        warn!("[密钥库] 加载密钥失败，将不注入密钥: {}", e);
+        BTreeMap::new()
+    })
+}
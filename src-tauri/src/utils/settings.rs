@@ -0,0 +1,108 @@
+use crate::models::{ManagerSettings, Profile, DEFAULT_PROFILE_NAME};
+use crate::utils::{file, platform};
+use log::warn;
+use std::sync::{Mutex, RwLock};
+
+/// 已加载的管理器设置（保存时同步更新）
+static CACHE: RwLock<Option<ManagerSettings>> = RwLock::new(None);
+
+/// 串行化设置的读-改-写，避免命令与后台线程同时修改时互相覆盖
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 读取管理器设置，文件不存在或损坏时返回默认值
+pub fn load() -> ManagerSettings {
+    if let Some(cached) = CACHE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return cached.clone();
+    }
+
+    let settings = normalize(read_from_disk());
+    *CACHE.write().unwrap_or_else(|e| e.into_inner()) = Some(settings.clone());
+    settings
+}
+
+/// 保存管理器设置
+pub fn save(settings: &ManagerSettings) -> Result<(), String> {
+    let settings = normalize(settings.clone());
+    let path = platform::get_manager_settings_path();
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("序列化管理器设置失败: {}", e))?;
+    file::write_file_atomic(&path, &content).map_err(|e| format!("写入管理器设置失败: {}", e))?;
+    *CACHE.write().unwrap_or_else(|e| e.into_inner()) = Some(settings);
+    Ok(())
+}
+
+/// 在同一把锁内读取、修改并保存管理器设置；`modify` 返回错误时不保存
+pub fn update<T>(
+    modify: impl FnOnce(&mut ManagerSettings) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut settings = load();
+    let result = modify(&mut settings)?;
+    save(&settings)?;
+    Ok(result)
+}
+
+/// 获取当前激活的配置档案（找不到时回退到默认档案）
+pub fn active_profile() -> Profile {
+    let settings = load();
+    let active = settings
+        .active_profile
+        .as_deref()
+        .unwrap_or(DEFAULT_PROFILE_NAME);
+    settings
+        .profiles
+        .iter()
+        .find(|p| p.name == active)
+        .or_else(|| settings.profiles.iter().find(|p| p.name == DEFAULT_PROFILE_NAME))
+        .cloned()
+        .unwrap_or_else(default_profile)
+}
+
+/// 默认档案：~/.openclaw
+fn default_profile() -> Profile {
+    Profile {
+        name: DEFAULT_PROFILE_NAME.to_string(),
+        config_dir: platform::get_default_config_dir(),
+        env_file: None,
+        log_dir: None,
+        gateway_port: None,
+        token_ttl_hours: None,
+    }
+}
+
+fn read_from_disk() -> ManagerSettings {
+    let path = platform::get_manager_settings_path();
+    if !file::file_exists(&path) {
+        return ManagerSettings::default();
+    }
+
+    match file::read_file(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("[设置] 解析管理器设置失败，使用默认值: {}", e);
+            ManagerSettings::default()
+        }),
+        Err(e) => {
+            warn!("[设置] 读取管理器设置失败，使用默认值: {}", e);
+            ManagerSettings::default()
+        }
+    }
+}
+
+/// 保证默认档案存在，并迁移旧版的全局端口设置
+fn normalize(mut settings: ManagerSettings) -> ManagerSettings {
+    if !settings.profiles.iter().any(|p| p.name == DEFAULT_PROFILE_NAME) {
+        settings.profiles.insert(0, default_profile());
+    }
+
+    if let Some(port) = settings.gateway_port.take() {
+        if let Some(default) = settings
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == DEFAULT_PROFILE_NAME)
+        {
+            default.gateway_port.get_or_insert(port);
+        }
+    }
+
+    settings
+}
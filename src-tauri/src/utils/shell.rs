@@ -1,8 +1,9 @@
-use std::process::{Command, Output};
+use std::process::{Child, Command, Output};
 use std::io;
 use std::collections::HashMap;
-use crate::utils::platform;
-use crate::utils::file;
+use crate::models::Profile;
+use crate::utils::{gateway_token, platform, secrets, settings};
+use crate::utils::env_file::EnvFile;
 use log::{info, debug, warn};
 
 #[cfg(windows)]
@@ -364,14 +365,16 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
     let extended_path = get_extended_path();
     debug!("[Shell] 扩展 PATH: {}", extended_path);
     
+    let profile = settings::active_profile();
+    
     let output = if openclaw_path.ends_with(".cmd") {
         // Windows: .cmd 文件需要通过 cmd /c 执行
         let mut cmd_args = vec!["/c", &openclaw_path];
         cmd_args.extend(args);
         let mut cmd = Command::new("cmd");
         cmd.args(&cmd_args)
-            .env("OPENCLAW_GATEWAY_TOKEN", DEFAULT_GATEWAY_TOKEN)
             .env("PATH", &extended_path);
+        apply_profile_env(&mut cmd, &profile);
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
@@ -380,8 +383,8 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
     } else {
         let mut cmd = Command::new(&openclaw_path);
         cmd.args(args)
-            .env("OPENCLAW_GATEWAY_TOKEN", DEFAULT_GATEWAY_TOKEN)
             .env("PATH", &extended_path);
+        apply_profile_env(&mut cmd, &profile);
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
@@ -409,42 +412,67 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
     }
 }
 
-/// 默认的 Gateway Token
-pub const DEFAULT_GATEWAY_TOKEN: &str = "openclaw-manager-local-token";
+/// 设置配置档案相关的环境变量，让 openclaw 使用该档案的配置目录和端口，
+/// 注入密钥库中的密钥（供配置中的 `${NAME}` 引用解析），
+/// 并设置 OPENCLAW_GATEWAY_TOKEN 为该档案配置的 token，这样所有子命令都能自动使用。
+/// 只读取配置，不会生成 token 或写入 openclaw.json
+fn apply_profile_env(cmd: &mut Command, profile: &Profile) {
+    cmd.env("OPENCLAW_STATE_DIR", &profile.config_dir)
+        .env("OPENCLAW_CONFIG_PATH", profile.config_file_path())
+        .env(
+            "OPENCLAW_GATEWAY_PORT",
+            platform::get_profile_gateway_port(profile).to_string(),
+        );
+    cmd.envs(secrets::env_vars(profile));
+    match gateway_token::configured(profile) {
+        Ok(Some(token)) => {
+            cmd.env("OPENCLAW_GATEWAY_TOKEN", token);
+        }
+        Ok(None) => debug!("[Shell] 档案 {} 未配置 Gateway Token", profile.name),
+        Err(e) => warn!("[Shell] 无法解析 Gateway Token，将不设置 OPENCLAW_GATEWAY_TOKEN: {}", e),
+    }
+}
+
+/// 生成当前档案环境变量的 shell export 语句，供终端脚本使用
+/// 包含密钥库中的密钥（与 run_openclaw 注入的一致），脚本须以仅当前用户可读的权限写入
+pub fn profile_shell_exports() -> String {
+    let profile = settings::active_profile();
+    let mut exports = format!(
+        "export OPENCLAW_STATE_DIR={}\nexport OPENCLAW_CONFIG_PATH={}\nexport OPENCLAW_GATEWAY_PORT={}",
+        shell_quote(&profile.config_dir),
+        shell_quote(&profile.config_file_path()),
+        platform::get_profile_gateway_port(&profile)
+    );
+    for (name, value) in secrets::env_vars(&profile) {
+        if secrets::validate_name(&name).is_ok() {
+            exports.push_str(&format!("\nexport {}={}", name, shell_quote(&value)));
+        }
+    }
+    exports
+}
+
+/// 将字符串转为 shell 单引号字面量，内部的 ' 转义为 '\''
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
 
-/// 从 ~/.openclaw/env 文件读取所有环境变量
+/// 从 env 文件（默认 ~/.openclaw/env）读取所有环境变量
 /// 与 shell 脚本 `source ~/.openclaw/env` 行为一致
-fn load_openclaw_env_vars() -> HashMap<String, String> {
-    let mut env_vars = HashMap::new();
-    let env_path = platform::get_env_file_path();
-    
-    if let Ok(content) = file::read_file(&env_path) {
-        for line in content.lines() {
-            let line = line.trim();
-            // 跳过注释和空行
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-            // 解析 export KEY=VALUE 或 KEY=VALUE 格式
-            let line = line.strip_prefix("export ").unwrap_or(line);
-            if let Some((key, value)) = line.split_once('=') {
-                let key = key.trim();
-                // 去除值周围的引号
-                let value = value.trim()
-                    .trim_matches('"')
-                    .trim_matches('\'');
-                env_vars.insert(key.to_string(), value.to_string());
-            }
+pub fn load_openclaw_env_vars(env_path: &str) -> HashMap<String, String> {
+    match EnvFile::load(env_path) {
+        Ok(env) => env.vars().into_iter().collect(),
+        Err(e) => {
+            warn!("[Shell] 读取 env 文件失败: {}", e);
+            HashMap::new()
         }
     }
-    
-    env_vars
 }
 
-/// 后台启动 openclaw gateway
+/// 以指定配置档案后台启动 openclaw gateway，返回子进程句柄（由 GatewaySupervisor 持有）
 /// 与 shell 脚本行为一致：先加载 env 文件，再启动 gateway
-pub fn spawn_openclaw_gateway() -> io::Result<()> {
-    info!("[Shell] 后台启动 openclaw gateway...");
+pub fn spawn_openclaw_gateway(profile: &Profile) -> io::Result<Child> {
+    let port = platform::get_profile_gateway_port(profile);
+    info!("[Shell] 后台启动 openclaw gateway, 档案: {}, 端口: {}...", profile.name, port);
     
     let openclaw_path = get_openclaw_path().ok_or_else(|| {
         warn!("[Shell] 找不到 openclaw 命令");
@@ -458,7 +486,7 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
     
     // 加载用户的 env 文件环境变量（与 shell 脚本 source ~/.openclaw/env 一致）
     info!("[Shell] 加载用户环境变量...");
-    let user_env_vars = load_openclaw_env_vars();
+    let user_env_vars = load_openclaw_env_vars(&profile.env_file_path());
     info!("[Shell] 已加载 {} 个环境变量", user_env_vars.len());
     for key in user_env_vars.keys() {
         debug!("[Shell] - 环境变量: {}", key);
@@ -469,16 +497,16 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
     info!("[Shell] 扩展 PATH: {}", extended_path);
     
     // Windows 上 .cmd 文件需要通过 cmd /c 来执行
-    // 设置环境变量 OPENCLAW_GATEWAY_TOKEN，这样所有子命令都能自动使用
+    let port_arg = port.to_string();
     let mut cmd = if openclaw_path.ends_with(".cmd") {
         info!("[Shell] Windows 模式: 使用 cmd /c 执行");
         let mut c = Command::new("cmd");
-        c.args(["/c", &openclaw_path, "gateway", "--port", "18789"]);
+        c.args(["/c", &openclaw_path, "gateway", "--port", &port_arg]);
         c
     } else {
         info!("[Shell] Unix 模式: 直接执行");
         let mut c = Command::new(&openclaw_path);
-        c.args(["gateway", "--port", "18789"]);
+        c.args(["gateway", "--port", &port_arg]);
         c
     };
     
@@ -487,22 +515,27 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
         cmd.env(key, value);
     }
     
-    // 设置 PATH 和 gateway token
+    // 设置 PATH
     cmd.env("PATH", &extended_path);
-    cmd.env("OPENCLAW_GATEWAY_TOKEN", DEFAULT_GATEWAY_TOKEN);
+    // 启动前确保档案有 gateway token（旧配置没有时生成并写入配置）
+    if let Err(e) = gateway_token::ensure(profile) {
+        warn!("[Shell] 初始化 Gateway Token 失败: {}", e);
+    }
+    // 档案环境变量、密钥库中的密钥和 gateway token（覆盖 env 文件中的同名变量）
+    apply_profile_env(&mut cmd, profile);
     
     // Windows: 隐藏控制台窗口
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
     
     // 将 stdout/stderr 重定向到日志文件，以便 get_logs 可以读取
-    let logs_dir = format!("{}/logs", platform::get_config_dir());
+    let logs_dir = profile.log_dir_path();
     let _ = std::fs::create_dir_all(&logs_dir);
     
-    let stdout_log_path = format!("{}/gateway.log", logs_dir);
-    let stderr_log_path = format!("{}/gateway.err.log", logs_dir);
+    let stdout_log_path = std::path::Path::new(&logs_dir).join("gateway.log");
+    let stderr_log_path = std::path::Path::new(&logs_dir).join("gateway.err.log");
     
-    info!("[Shell] 日志输出到: {} / {}", stdout_log_path, stderr_log_path);
+    info!("[Shell] 日志输出到: {} / {}", stdout_log_path.display(), stderr_log_path.display());
     
     if let Ok(stdout_file) = std::fs::OpenOptions::new()
         .create(true).append(true).open(&stdout_log_path)
@@ -521,7 +554,7 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
     match child {
         Ok(c) => {
             info!("[Shell] ✓ Gateway 进程已启动, PID: {}", c.id());
-            Ok(())
+            Ok(c)
         }
         Err(e) => {
             warn!("[Shell] ✗ Gateway 启动失败: {}", e);
@@ -555,3 +588,81 @@ pub fn command_exists(cmd: &str) -> bool {
             .unwrap_or(false)
     }
 }
+
+/// 去除 ANSI 转义序列（颜色代码等）
+pub fn strip_ansi_codes(input: &str) -> String {
+    // 匹配 ANSI 转义序列: ESC[ ... m 或 ESC[ ... 其他控制字符
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // 跳过 ESC[...m 序列
+            if chars.peek() == Some(&'[') {
+                chars.next(); // 跳过 '['
+                // 跳过直到遇到字母
+                while let Some(&next) = chars.peek() {
+                    chars.next();
+                    if next.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// 从混合输出中提取 JSON 内容
+pub fn extract_json_from_output(output: &str) -> Option<String> {
+    // 先去除 ANSI 颜色代码
+    let clean_output = strip_ansi_codes(output);
+    
+    // 按行查找 JSON 开始位置
+    let lines: Vec<&str> = clean_output.lines().collect();
+    let mut json_start_line = None;
+    let mut json_end_line = None;
+    
+    // 找到 JSON 开始行：
+    // - 以 { 开头（JSON 对象）
+    // - 或以 [" 或 [数字 开头（真正的 JSON 数组，不是 [plugins] 这样的文本）
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('{') {
+            json_start_line = Some(i);
+            break;
+        }
+        // 检查是否是真正的 JSON 数组（以 [" 或 [数字 或 [{ 开头）
+        if trimmed.starts_with('[') && trimmed.len() > 1 {
+            let second_char = trimmed.chars().nth(1).unwrap_or(' ');
+            if second_char == '"' || second_char == '{' || second_char == '[' || second_char.is_ascii_digit() {
+                json_start_line = Some(i);
+                break;
+            }
+        }
+    }
+    
+    // 找到 JSON 结束行（以 } 或 ] 结尾的行，从后往前找）
+    for (i, line) in lines.iter().enumerate().rev() {
+        let trimmed = line.trim();
+        if trimmed == "}" || trimmed == "}," || trimmed.ends_with('}') {
+            json_end_line = Some(i);
+            break;
+        }
+        if trimmed == "]" || trimmed == "]," {
+            json_end_line = Some(i);
+            break;
+        }
+    }
+    
+    match (json_start_line, json_end_line) {
+        (Some(start), Some(end)) if start <= end => {
+            let json_lines: Vec<&str> = lines[start..=end].to_vec();
+            let json_str = json_lines.join("\n");
+            Some(json_str)
+        }
+        _ => None,
+    }
+}
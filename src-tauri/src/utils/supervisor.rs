@@ -0,0 +1,312 @@
+use crate::models::{GatewayExitRecord, Profile};
+use crate::utils::shell;
+use log::{error, info, warn};
+use std::collections::{HashMap, VecDeque};
+use std::process::{Child, ExitStatus};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use std::os::unix::process::ExitStatusExt;
+
+/// 子进程状态轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 首次重启的退避时长
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// 退避时长上限
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+/// 运行超过该时长视为已稳定，重置退避
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+/// 崩溃循环判定窗口
+const CRASH_LOOP_WINDOW: Duration = Duration::from_secs(300);
+/// 窗口内最多自动重启次数，超过后放弃重启
+const CRASH_LOOP_MAX_RESTARTS: usize = 5;
+/// 保留的退出记录条数
+const MAX_EXIT_HISTORY: usize = 50;
+
+/// Gateway 子进程监管器
+/// 按配置档案持有 gateway 的 Child 句柄，回收退出状态，并在崩溃后按指数退避自动重启
+pub struct GatewaySupervisor {
+    state: Arc<Mutex<SupervisorState>>,
+}
+
+struct SupervisorState {
+    /// 档案名称 -> 受监管的 gateway
+    gateways: HashMap<String, SupervisedGateway>,
+    /// 退出记录（最新的在末尾）
+    exits: VecDeque<GatewayExitRecord>,
+}
+
+struct SupervisedGateway {
+    /// 启动时使用的配置档案，自动重启沿用该档案
+    profile: Profile,
+    /// 当前由本应用启动的 gateway 进程
+    child: Option<Child>,
+    /// 当前进程的启动时间
+    started_at: Option<Instant>,
+    /// 是否期望 gateway 保持运行（崩溃循环后为 false，不再自动重启）
+    desired_running: bool,
+    /// 连续崩溃次数，决定下一次退避时长
+    consecutive_failures: u32,
+    /// 崩溃循环窗口内的自动重启时间点
+    recent_restarts: VecDeque<Instant>,
+    /// 下一次自动重启的时间
+    next_restart_at: Option<Instant>,
+}
+
+impl GatewaySupervisor {
+    /// 创建监管器并启动后台监控线程
+    pub fn new() -> Self {
+        let state = Arc::new(Mutex::new(SupervisorState {
+            gateways: HashMap::new(),
+            exits: VecDeque::new(),
+        }));
+
+        let monitor_state = Arc::clone(&state);
+        thread::Builder::new()
+            .name("gateway-supervisor".to_string())
+            .spawn(move || monitor_loop(monitor_state))
+            .expect("无法启动 gateway 监控线程");
+
+        Self { state }
+    }
+
+    /// 以指定配置档案启动 gateway 并开始监管，返回 PID
+    pub fn start(&self, profile: &Profile) -> Result<u32, String> {
+        let mut state = lock(&self.state);
+
+        if let Some(child) = state
+            .gateways
+            .get_mut(&profile.name)
+            .and_then(|g| g.child.as_mut())
+        {
+            if matches!(child.try_wait(), Ok(None)) {
+                return Err(format!("Gateway 已由管理器启动，PID: {}", child.id()));
+            }
+        }
+
+        // 手动启动视为重新开始，清除之前的崩溃计数
+        let mut gateway = SupervisedGateway {
+            profile: profile.clone(),
+            child: None,
+            started_at: None,
+            desired_running: true,
+            consecutive_failures: 0,
+            recent_restarts: VecDeque::new(),
+            next_restart_at: None,
+        };
+        let pid = spawn_into(&mut gateway)?;
+        state.gateways.insert(profile.name.clone(), gateway);
+        Ok(pid)
+    }
+
+    /// 停止监管指定档案的 gateway：不再自动重启，并结束由本应用启动的进程
+    pub fn stop(&self, profile_name: &str) {
+        let mut state = lock(&self.state);
+        let Some(mut gateway) = state.gateways.remove(profile_name) else {
+            return;
+        };
+
+        if let Some(mut child) = gateway.child.take() {
+            info!("[Supervisor] 结束受监管的 gateway 进程, PID: {}", child.id());
+            let _ = child.kill();
+            if let Ok(status) = child.wait() {
+                let record = exit_record(&gateway, child.id(), status, false);
+                push_exit(&mut state, record);
+            }
+        }
+    }
+
+    /// 获取退出记录（最新的在前）
+    pub fn exit_history(&self) -> Vec<GatewayExitRecord> {
+        lock(&self.state).exits.iter().rev().cloned().collect()
+    }
+}
+
+impl Default for GatewaySupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lock(state: &Arc<Mutex<SupervisorState>>) -> MutexGuard<'_, SupervisorState> {
+    // 监控线程 panic 不应导致整个应用不可用
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 启动 gateway 进程并记录到条目中
+fn spawn_into(gateway: &mut SupervisedGateway) -> Result<u32, String> {
+    let child = shell::spawn_openclaw_gateway(&gateway.profile).map_err(|e| e.to_string())?;
+    let pid = child.id();
+    gateway.child = Some(child);
+    gateway.started_at = Some(Instant::now());
+    Ok(pid)
+}
+
+/// 构造一条退出记录
+fn exit_record(
+    gateway: &SupervisedGateway,
+    pid: u32,
+    status: ExitStatus,
+    restart_scheduled: bool,
+) -> GatewayExitRecord {
+    #[cfg(unix)]
+    let signal = status.signal();
+    #[cfg(not(unix))]
+    let signal: Option<i32> = None;
+
+    let uptime_seconds = gateway
+        .started_at
+        .map(|t| t.elapsed().as_secs())
+        .unwrap_or(0);
+
+    GatewayExitRecord {
+        profile: gateway.profile.name.clone(),
+        pid,
+        code: status.code(),
+        signal,
+        exited_at: chrono::Local::now().to_rfc3339(),
+        uptime_seconds,
+        restart_scheduled,
+    }
+}
+
+/// 追加退出记录，超出上限时丢弃最旧的
+fn push_exit(state: &mut SupervisorState, record: GatewayExitRecord) {
+    state.exits.push_back(record);
+    while state.exits.len() > MAX_EXIT_HISTORY {
+        state.exits.pop_front();
+    }
+}
+
+/// 计算第 n 次连续失败后的退避时长
+fn backoff_for(failures: u32) -> Duration {
+    let exp = failures.saturating_sub(1).min(16);
+    BACKOFF_BASE
+        .checked_mul(1u32 << exp)
+        .unwrap_or(BACKOFF_MAX)
+        .min(BACKOFF_MAX)
+}
+
+/// 后台监控循环：回收退出的子进程，并按计划重启
+fn monitor_loop(shared: Arc<Mutex<SupervisorState>>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut state = lock(&shared);
+
+        let mut records = Vec::new();
+        for gateway in state.gateways.values_mut() {
+            if let Some(record) = poll_gateway(gateway) {
+                records.push(record);
+            }
+        }
+        for record in records {
+            push_exit(&mut state, record);
+        }
+
+        // 已放弃自动重启且进程已退出的档案不再监管
+        state
+            .gateways
+            .retain(|_, g| g.child.is_some() || g.desired_running);
+    }
+}
+
+/// 检查单个档案的 gateway：回收退出的进程并按计划重启，返回产生的退出记录
+fn poll_gateway(gateway: &mut SupervisedGateway) -> Option<GatewayExitRecord> {
+    let mut record = None;
+
+    // 1. 回收已退出的子进程
+    let exited = match gateway.child.as_mut() {
+        Some(child) => match child.try_wait() {
+            Ok(Some(status)) => Some((child.id(), status)),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("[Supervisor] 查询 gateway 进程状态失败: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    if let Some((pid, status)) = exited {
+        gateway.child = None;
+        let ran_for = gateway.started_at.map(|t| t.elapsed()).unwrap_or_default();
+
+        warn!(
+            "[Supervisor] Gateway ({}) 进程意外退出, PID: {}, 状态: {}, 运行 {} 秒",
+            gateway.profile.name,
+            pid,
+            status,
+            ran_for.as_secs()
+        );
+
+        // 稳定运行一段时间后再崩溃，重新从最短退避开始
+        if ran_for >= STABLE_UPTIME {
+            gateway.consecutive_failures = 0;
+        }
+        gateway.consecutive_failures += 1;
+
+        let now = Instant::now();
+        while let Some(&first) = gateway.recent_restarts.front() {
+            if now.duration_since(first) > CRASH_LOOP_WINDOW {
+                gateway.recent_restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if gateway.recent_restarts.len() >= CRASH_LOOP_MAX_RESTARTS {
+            error!(
+                "[Supervisor] ✗ Gateway ({}) 在 {} 秒内崩溃 {} 次，判定为崩溃循环，停止自动重启",
+                gateway.profile.name,
+                CRASH_LOOP_WINDOW.as_secs(),
+                gateway.recent_restarts.len() + 1
+            );
+            let exit = exit_record(gateway, pid, status, false);
+            gateway.started_at = None;
+            gateway.desired_running = false;
+            gateway.next_restart_at = None;
+            return Some(exit);
+        }
+
+        let delay = backoff_for(gateway.consecutive_failures);
+        info!(
+            "[Supervisor] 将在 {} 秒后重启 gateway ({})",
+            delay.as_secs(),
+            gateway.profile.name
+        );
+        record = Some(exit_record(gateway, pid, status, true));
+        gateway.started_at = None;
+        gateway.next_restart_at = Some(now + delay);
+    }
+
+    // 2. 到达重启时间则重新拉起
+    if gateway.child.is_none() && gateway.desired_running {
+        if let Some(at) = gateway.next_restart_at {
+            if Instant::now() >= at {
+                gateway.next_restart_at = None;
+                gateway.recent_restarts.push_back(Instant::now());
+                match spawn_into(gateway) {
+                    Ok(pid) => info!(
+                        "[Supervisor] ✓ Gateway ({}) 已自动重启, PID: {}",
+                        gateway.profile.name, pid
+                    ),
+                    Err(e) => {
+                        error!("[Supervisor] ✗ 自动重启失败: {}", e);
+                        if gateway.recent_restarts.len() >= CRASH_LOOP_MAX_RESTARTS {
+                            error!("[Supervisor] ✗ 多次重启失败，停止自动重启");
+                            gateway.desired_running = false;
+                        } else {
+                            gateway.consecutive_failures += 1;
+                            let delay = backoff_for(gateway.consecutive_failures);
+                            gateway.next_restart_at = Some(Instant::now() + delay);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    record
+}
@@ -0,0 +1,350 @@
+use crate::models::{
+    ModelCostConfig, OpenClawConfig, Profile, UsageBreakdown, UsagePeriod, UsagePeriodReport,
+    UsageReport, UsageTotals,
+};
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate, TimeZone};
+use log::{debug, warn};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// 无法确定渠道时使用的名称
+const UNKNOWN_CHANNEL: &str = "unknown";
+
+/// 从会话记录中提取的一次模型调用
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    /// 调用时间（本地时区）
+    pub time: DateTime<Local>,
+    pub provider: String,
+    pub model: String,
+    pub channel: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    /// 会话中记录的费用（OpenClaw 按内置价格计算），没有配置价格时使用
+    pub recorded_cost: Option<f64>,
+}
+
+/// 读取 `<configDir>/agents/*/sessions/*.jsonl` 中 `since` 之后的模型调用
+/// 返回调用记录和读取的会话文件数；修改时间早于 `since` 的文件直接跳过
+pub fn read_records(profile: &Profile, since: DateTime<Local>) -> (Vec<UsageRecord>, usize) {
+    let agents_dir = Path::new(&profile.config_dir).join("agents");
+    let mut records = Vec::new();
+    let mut scanned = 0;
+
+    let Ok(agents) = fs::read_dir(&agents_dir) else {
+        debug!("[用量统计] 会话目录不存在: {}", agents_dir.display());
+        return (records, scanned);
+    };
+    let since_time = SystemTime::from(since);
+
+    for agent in agents.flatten() {
+        let sessions_dir = agent.path().join("sessions");
+        let Ok(entries) = fs::read_dir(&sessions_dir) else {
+            continue;
+        };
+        let channels = session_channels(&sessions_dir.join("sessions.json"));
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let modified = entry.metadata().and_then(|m| m.modified());
+            if modified.is_ok_and(|m| m < since_time) {
+                continue;
+            }
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("[用量统计] 读取会话文件失败 {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            scanned += 1;
+
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            let channel = channel_for_file(&channels, stem);
+            records.extend(
+                content
+                    .lines()
+                    .filter_map(|line| parse_record(line, channel))
+                    .filter(|record| record.time >= since),
+            );
+        }
+    }
+
+    debug!("[用量统计] 读取 {} 个会话文件，{} 条调用记录", scanned, records.len());
+    (records, scanned)
+}
+
+/// 解析 sessions.json：会话 ID 或会话文件名 -> 渠道
+fn session_channels(path: &Path) -> HashMap<String, String> {
+    let mut channels = HashMap::new();
+    let Some(index) = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+    else {
+        return channels;
+    };
+    let Some(sessions) = index.as_object() else {
+        return channels;
+    };
+
+    for (key, session) in sessions {
+        let channel = ["/lastChannel", "/channel", "/deliveryContext/channel", "/origin/provider"]
+            .iter()
+            .find_map(|pointer| session.pointer(pointer).and_then(|v| v.as_str()))
+            .filter(|c| !c.is_empty())
+            // 会话键形如 agent:<agentId>:<channel>:...
+            .or_else(|| key.split(':').nth(2).filter(|c| *c != "main"))
+            .unwrap_or(UNKNOWN_CHANNEL)
+            .to_string();
+
+        if let Some(id) = session.get("sessionId").and_then(|v| v.as_str()) {
+            channels.insert(id.to_string(), channel.clone());
+        }
+        if let Some(file) = session.get("sessionFile").and_then(|v| v.as_str()) {
+            if let Some(stem) = Path::new(file).file_stem().and_then(|s| s.to_str()) {
+                channels.insert(stem.to_string(), channel);
+            }
+        }
+    }
+    channels
+}
+
+/// 会话文件对应的渠道；话题会话的文件名为 `<sessionId>-topic-<id>`
+fn channel_for_file<'a>(channels: &'a HashMap<String, String>, stem: &str) -> &'a str {
+    channels
+        .get(stem)
+        .or_else(|| {
+            channels
+                .iter()
+                .find(|(id, _)| stem.starts_with(id.as_str()))
+                .map(|(_, channel)| channel)
+        })
+        .map(String::as_str)
+        .unwrap_or(UNKNOWN_CHANNEL)
+}
+
+fn first_u64(value: &Value, keys: &[&str]) -> u64 {
+    keys.iter()
+        .find_map(|key| value.get(*key).and_then(|v| v.as_u64()))
+        .unwrap_or(0)
+}
+
+/// 解析会话文件中的一行，只有带 usage 的 assistant 消息会产生记录
+fn parse_record(line: &str, channel: &str) -> Option<UsageRecord> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    let message = value.get("message").unwrap_or(&value);
+    if message.get("role").and_then(|r| r.as_str()) != Some("assistant") {
+        return None;
+    }
+    let usage = message.get("usage")?;
+
+    let input_tokens = first_u64(usage, &["input", "input_tokens", "prompt_tokens"]);
+    let output_tokens = first_u64(usage, &["output", "output_tokens", "completion_tokens"]);
+    let cache_read_tokens = first_u64(usage, &["cacheRead", "cache_read_input_tokens"]);
+    let cache_write_tokens = first_u64(usage, &["cacheWrite", "cache_creation_input_tokens"]);
+    if input_tokens + output_tokens + cache_read_tokens + cache_write_tokens == 0 {
+        return None;
+    }
+
+    // 行级 timestamp 为 RFC 3339，消息内为毫秒时间戳
+    let time = value
+        .get("timestamp")
+        .and_then(|t| t.as_str())
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Local))
+        .or_else(|| {
+            message
+                .get("timestamp")
+                .and_then(|t| t.as_i64())
+                .and_then(|ms| Local.timestamp_millis_opt(ms).single())
+        })?;
+
+    let text = |key: &str| {
+        message
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .unwrap_or("unknown")
+            .to_string()
+    };
+    let recorded_cost = usage
+        .get("cost")
+        .and_then(|cost| cost.get("total").unwrap_or(cost).as_f64());
+
+    Some(UsageRecord {
+        time,
+        provider: text("provider"),
+        model: text("model"),
+        channel: channel.to_string(),
+        input_tokens,
+        output_tokens,
+        cache_read_tokens,
+        cache_write_tokens,
+        recorded_cost,
+    })
+}
+
+/// 按模型配置的价格（每百万 token）计算费用
+fn price(record: &UsageRecord, cost: &ModelCostConfig) -> f64 {
+    (record.input_tokens as f64 * cost.input
+        + record.output_tokens as f64 * cost.output
+        + record.cache_read_tokens as f64 * cost.cache_read
+        + record.cache_write_tokens as f64 * cost.cache_write)
+        / 1_000_000.0
+}
+
+/// 记录的费用：优先使用 openclaw.json 中配置的价格，全为 0 时使用会话中记录的费用
+pub fn record_cost(config: &OpenClawConfig, record: &UsageRecord) -> Option<f64> {
+    config
+        .models
+        .providers
+        .get(&record.provider)
+        .and_then(|p| p.models.iter().find(|m| m.id == record.model))
+        .and_then(|m| m.cost.as_ref())
+        .filter(|c| c.input > 0.0 || c.output > 0.0 || c.cache_read > 0.0 || c.cache_write > 0.0)
+        .map(|c| price(record, c))
+        .or(record.recorded_cost)
+}
+
+fn add(totals: &mut UsageTotals, record: &UsageRecord, cost: f64) {
+    totals.requests += 1;
+    totals.input_tokens += record.input_tokens;
+    totals.output_tokens += record.output_tokens;
+    totals.cache_read_tokens += record.cache_read_tokens;
+    totals.cache_write_tokens += record.cache_write_tokens;
+    totals.cost += cost;
+}
+
+fn merge(totals: &mut UsageTotals, other: &UsageTotals) {
+    totals.requests += other.requests;
+    totals.input_tokens += other.input_tokens;
+    totals.output_tokens += other.output_tokens;
+    totals.cache_read_tokens += other.cache_read_tokens;
+    totals.cache_write_tokens += other.cache_write_tokens;
+    totals.cost += other.cost;
+}
+
+/// 包含 `date` 的周期的开始日期
+pub fn period_start(period: UsagePeriod, date: NaiveDate) -> NaiveDate {
+    match period {
+        UsagePeriod::Day => date,
+        UsagePeriod::Week => date - Days::new(date.weekday().num_days_from_monday() as u64),
+        UsagePeriod::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn next_period_start(period: UsagePeriod, start: NaiveDate) -> NaiveDate {
+    match period {
+        UsagePeriod::Day => start + Days::new(1),
+        UsagePeriod::Week => start + Days::new(7),
+        UsagePeriod::Month => start + Months::new(1),
+    }
+}
+
+/// 截至 `today` 的最近 `count` 个周期的边界（升序，共 count + 1 个日期）
+pub fn period_bounds(period: UsagePeriod, count: u32, today: NaiveDate) -> Vec<NaiveDate> {
+    let current = period_start(period, today);
+    let first = match period {
+        UsagePeriod::Day => current - Days::new(count as u64 - 1),
+        UsagePeriod::Week => current - Days::new(7 * (count as u64 - 1)),
+        UsagePeriod::Month => current - Months::new(count - 1),
+    };
+
+    let mut bounds = vec![first];
+    for _ in 0..count {
+        let last = *bounds.last().unwrap_or(&first);
+        bounds.push(next_period_start(period, last));
+    }
+    bounds
+}
+
+/// 本地时间当天 0 点
+pub fn local_midnight(date: NaiveDate) -> DateTime<Local> {
+    let naive = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+/// 按维度汇总并按费用降序排列
+fn breakdown(map: BTreeMap<String, UsageTotals>) -> Vec<UsageBreakdown> {
+    let mut list: Vec<UsageBreakdown> = map
+        .into_iter()
+        .map(|(key, usage)| UsageBreakdown { key, usage })
+        .collect();
+    list.sort_by(|a, b| {
+        b.usage
+            .cost
+            .total_cmp(&a.usage.cost)
+            .then_with(|| {
+                let tokens = |u: &UsageTotals| u.input_tokens + u.output_tokens;
+                tokens(&b.usage).cmp(&tokens(&a.usage))
+            })
+    });
+    list
+}
+
+/// 将调用记录按周期和 Provider / 模型 / 渠道汇总
+pub fn build_report(
+    config: &OpenClawConfig,
+    records: &[UsageRecord],
+    period: UsagePeriod,
+    bounds: &[NaiveDate],
+    sessions_scanned: usize,
+) -> UsageReport {
+    let mut totals = UsageTotals::default();
+    let mut unpriced = BTreeSet::new();
+    let mut periods = Vec::new();
+
+    for window in bounds.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        let mut period_totals = UsageTotals::default();
+        let mut by_provider: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        let mut by_model: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        let mut by_channel: BTreeMap<String, UsageTotals> = BTreeMap::new();
+
+        for record in records {
+            let date = record.time.date_naive();
+            if date < start || date >= end {
+                continue;
+            }
+            let full_id = format!("{}/{}", record.provider, record.model);
+            let cost = record_cost(config, record).unwrap_or_else(|| {
+                unpriced.insert(full_id.clone());
+                0.0
+            });
+
+            add(&mut period_totals, record, cost);
+            add(by_provider.entry(record.provider.clone()).or_default(), record, cost);
+            add(by_model.entry(full_id).or_default(), record, cost);
+            add(by_channel.entry(record.channel.clone()).or_default(), record, cost);
+        }
+
+        merge(&mut totals, &period_totals);
+        periods.push(UsagePeriodReport {
+            start: start.format("%Y-%m-%d").to_string(),
+            end: end.format("%Y-%m-%d").to_string(),
+            totals: period_totals,
+            by_provider: breakdown(by_provider),
+            by_model: breakdown(by_model),
+            by_channel: breakdown(by_channel),
+        });
+    }
+
+    UsageReport {
+        period,
+        periods,
+        totals,
+        sessions_scanned,
+        unpriced_models: unpriced.into_iter().collect(),
+    }
+}
@@ -0,0 +1,266 @@
+use crate::utils::secrets::SecretBackend;
+use crate::utils::{file, platform};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// 设置后使用口令派生的密钥加密密钥库，否则使用本机随机生成的机器密钥
+pub const PASSPHRASE_ENV: &str = "OPENCLAW_MANAGER_VAULT_PASSPHRASE";
+
+/// 密钥库文件格式版本
+const VAULT_VERSION: u32 = 1;
+/// 机器密钥：直接使用本机密钥文件中的 32 字节随机数
+const KDF_MACHINE_KEY: &str = "machine-key";
+/// 口令：PBKDF2-HMAC-SHA256 派生
+const KDF_PASSPHRASE: &str = "pbkdf2-sha256";
+/// PBKDF2 迭代次数
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// 串行化密钥库的读-改-写
+static VAULT_LOCK: Mutex<()> = Mutex::new(());
+
+/// 口令派生结果缓存：(盐 + 口令哈希) -> 密钥，避免每次读取都重新派生
+static DERIVED_KEY_CACHE: Mutex<Option<(String, [u8; 32])>> = Mutex::new(None);
+
+/// 密钥库文件（JSON），密文为所有密钥组成的 JSON 对象
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    salt: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    iterations: u32,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+/// 加密的本地密钥库，离线可用
+/// 使用 ChaCha20-Poly1305 加密，每次写入使用新的随机 nonce
+pub struct VaultBackend {
+    path: String,
+}
+
+impl VaultBackend {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+
+    /// 读取并解密全部密钥，文件不存在时为空
+    fn read(&self) -> Result<BTreeMap<String, String>, String> {
+        if !file::file_exists(&self.path) {
+            return Ok(BTreeMap::new());
+        }
+
+        let content =
+            file::read_file(&self.path).map_err(|e| format!("读取密钥库失败: {}", e))?;
+        let vault: VaultFile =
+            serde_json::from_str(&content).map_err(|e| format!("密钥库文件格式错误: {}", e))?;
+        if vault.version != VAULT_VERSION {
+            return Err(format!("不支持的密钥库版本: {}", vault.version));
+        }
+
+        let key = match vault.kdf.as_str() {
+            KDF_MACHINE_KEY => machine_key(false)?,
+            KDF_PASSPHRASE => {
+                let passphrase = passphrase().ok_or_else(|| {
+                    format!("密钥库使用口令加密，请设置环境变量 {}", PASSPHRASE_ENV)
+                })?;
+                let salt = decode(&vault.salt, "salt")?;
+                derive_key(&passphrase, &salt, vault.iterations)
+            }
+            other => return Err(format!("未知的密钥派生方式: {}", other)),
+        };
+
+        let nonce = decode(&vault.nonce, "nonce")?;
+        if nonce.len() != 12 {
+            return Err("密钥库 nonce 长度无效".to_string());
+        }
+        let ciphertext = decode(&vault.ciphertext, "ciphertext")?;
+
+        let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| "解密密钥库失败：密钥或口令不正确，或文件已损坏".to_string())?;
+
+        serde_json::from_slice(&plaintext).map_err(|e| format!("密钥库内容格式错误: {}", e))
+    }
+
+    /// 加密并原子写入全部密钥
+    /// 设置了口令时使用口令加密（沿用已有的盐），否则使用机器密钥
+    fn write(&self, secrets: &BTreeMap<String, String>) -> Result<(), String> {
+        let (kdf, salt, iterations, key) = match passphrase() {
+            Some(passphrase) => {
+                let salt = self
+                    .existing_salt()
+                    .unwrap_or_else(|| random_bytes::<16>().to_vec());
+                let key = derive_key(&passphrase, &salt, PBKDF2_ITERATIONS);
+                (KDF_PASSPHRASE, BASE64.encode(&salt), PBKDF2_ITERATIONS, key)
+            }
+            None => (KDF_MACHINE_KEY, String::new(), 0, machine_key(true)?),
+        };
+
+        let plaintext =
+            serde_json::to_vec(secrets).map_err(|e| format!("序列化密钥失败: {}", e))?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| "加密密钥库失败".to_string())?;
+
+        let vault = VaultFile {
+            version: VAULT_VERSION,
+            kdf: kdf.to_string(),
+            salt,
+            iterations,
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let content = serde_json::to_string_pretty(&vault)
+            .map_err(|e| format!("序列化密钥库失败: {}", e))?;
+        file::write_private_file_atomic(&self.path, &content)
+            .map_err(|e| format!("写入密钥库失败: {}", e))
+    }
+
+    /// 已有口令加密密钥库的盐（重复使用以命中派生缓存）
+    fn existing_salt(&self) -> Option<Vec<u8>> {
+        let content = file::read_file(&self.path).ok()?;
+        let vault: VaultFile = serde_json::from_str(&content).ok()?;
+        if vault.kdf != KDF_PASSPHRASE || vault.iterations != PBKDF2_ITERATIONS {
+            return None;
+        }
+        BASE64.decode(vault.salt).ok().filter(|s| !s.is_empty())
+    }
+}
+
+impl SecretBackend for VaultBackend {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    fn location(&self) -> String {
+        self.path.clone()
+    }
+
+    fn key_source(&self) -> String {
+        if passphrase().is_some() {
+            "passphrase".to_string()
+        } else {
+            KDF_MACHINE_KEY.to_string()
+        }
+    }
+
+    fn load_all(&self) -> Result<BTreeMap<String, String>, String> {
+        let _guard = VAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.read()
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), String> {
+        let _guard = VAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut secrets = self.read()?;
+        secrets.insert(name.to_string(), value.to_string());
+        self.write(&secrets)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, String> {
+        let _guard = VAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut secrets = self.read()?;
+        if secrets.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.write(&secrets)?;
+        Ok(true)
+    }
+
+    fn apply(&self, changes: &[(String, Option<String>)]) -> Result<(), String> {
+        let _guard = VAULT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut secrets = self.read()?;
+        for (name, value) in changes {
+            match value {
+                Some(value) => secrets.insert(name.clone(), value.clone()),
+                None => secrets.remove(name),
+            };
+        }
+        self.write(&secrets)
+    }
+}
+
+fn decode(value: &str, field: &str) -> Result<Vec<u8>, String> {
+    BASE64
+        .decode(value)
+        .map_err(|e| format!("密钥库字段 {} 无效: {}", field, e))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn passphrase() -> Option<String> {
+    std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty())
+}
+
+/// 用 PBKDF2-HMAC-SHA256 从口令派生 32 字节密钥（结果按盐和口令缓存）
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(iterations.to_le_bytes());
+    hasher.update(passphrase.as_bytes());
+    let cache_id = BASE64.encode(hasher.finalize());
+
+    let mut cache = DERIVED_KEY_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((id, key)) = cache.as_ref() {
+        if *id == cache_id {
+            return *key;
+        }
+    }
+
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    *cache = Some((cache_id, key));
+    key
+}
+
+/// 机器密钥文件：<管理器目录>/vault.key，所有档案共用
+fn machine_key_path() -> String {
+    Path::new(&platform::get_manager_dir())
+        .join("vault.key")
+        .display()
+        .to_string()
+}
+
+/// 读取机器密钥，`create` 为 true 且不存在时生成新的随机密钥
+fn machine_key(create: bool) -> Result<[u8; 32], String> {
+    let path = machine_key_path();
+
+    if let Ok(content) = file::read_file(&path) {
+        let bytes = BASE64
+            .decode(content.trim())
+            .map_err(|e| format!("机器密钥文件格式错误: {}", e))?;
+        return bytes
+            .try_into()
+            .map_err(|_| "机器密钥长度无效".to_string());
+    }
+
+    if !create {
+        warn!("[密钥库] 机器密钥文件不存在: {}", path);
+        return Err(format!("机器密钥文件 {} 不存在，无法解密密钥库", path));
+    }
+
+    let key = random_bytes::<32>();
+    file::write_private_file_atomic(&path, &BASE64.encode(key))
+        .map_err(|e| format!("写入机器密钥失败: {}", e))?;
+    info!("[密钥库] 已生成机器密钥: {}", path);
+    Ok(key)
+}
@@ -24,6 +24,7 @@ import {
 } from 'lucide-react';
 import clsx from 'clsx';
 import { aiLogger } from '../../lib/logger';
+import { onConfigFileChanged } from '../../lib/tauri';
 
 // ============ 类型定义 ============
 
@@ -69,6 +70,7 @@ interface AIConfigOverview {
   primary_model: string | null;
   configured_providers: ConfiguredProvider[];
   available_models: string[];
+  revision: string;
 }
 
 interface ModelConfig {
@@ -99,10 +101,14 @@ interface ProviderDialogProps {
   onSave: () => void;
   // 编辑模式时传入现有配置
   editingProvider?: ConfiguredProvider | null;
+  // 读取配置时的版本，保存时传回以拒绝覆盖外部修改
+  revision?: string;
 }
 
-function ProviderDialog({ officialProviders, onClose, onSave, editingProvider }: ProviderDialogProps) {
+function ProviderDialog({ officialProviders, onClose, onSave, editingProvider, revision }: ProviderDialogProps) {
   const { t } = useTranslation();
+  // 使用打开对话框时的版本：编辑期间配置被外部修改时保存会被拒绝
+  const [baseRevision] = useState(revision);
   const isEditing = !!editingProvider;
   const [step, setStep] = useState<'select' | 'configure'>(isEditing ? 'configure' : 'select');
   const [selectedOfficial, setSelectedOfficial] = useState<OfficialProvider | null>(() => {
@@ -241,6 +247,7 @@ function ProviderDialog({ officialProviders, onClose, onSave, editingProvider }:
         apiKey: apiKey || null,
         apiType,
         models,
+        expectedRevision: baseRevision,
       });
 
       aiLogger.info(`✓ Provider ${providerName} 已${isEditing ? '更新' : '保存'}`);
@@ -635,9 +642,10 @@ interface ProviderCardProps {
   onSetPrimary: (modelId: string) => void;
   onRefresh: () => void;
   onEdit: (provider: ConfiguredProvider) => void;
+  revision?: string;
 }
 
-function ProviderCard({ provider, officialProviders, onSetPrimary, onRefresh, onEdit }: ProviderCardProps) {
+function ProviderCard({ provider, officialProviders, onSetPrimary, onRefresh, onEdit, revision }: ProviderCardProps) {
   const { t } = useTranslation();
   const [expanded, setExpanded] = useState(true);
   const [deleting, setDeleting] = useState(false);
@@ -661,7 +669,7 @@ function ProviderCard({ provider, officialProviders, onSetPrimary, onRefresh, on
     setDeleting(true);
     setDeleteError(null);
     try {
-      await invoke('delete_provider', { providerName: provider.name });
+      await invoke('delete_provider', { providerName: provider.name, expectedRevision: revision });
       setShowDeleteConfirm(false);
       onRefresh();
     } catch (e) {
@@ -914,9 +922,21 @@ export function AIConfig() {
     loadData();
   }, [loadData]);
 
+  // 配置文件被外部修改时重新加载，避免基于过期版本保存
+  useEffect(() => {
+    const unlisten = onConfigFileChanged((change) => {
+      if (change.kind !== 'config') return;
+      aiLogger.info('配置文件已被外部修改，重新加载');
+      loadData();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadData]);
+
   const handleSetPrimary = async (modelId: string) => {
     try {
-      await invoke('set_primary_model', { modelId });
+      await invoke('set_primary_model', { modelId, expectedRevision: aiConfig?.revision });
       aiLogger.info(`主模型已设置为: ${modelId}`);
       loadData();
     } catch (e) {
@@ -1085,6 +1105,7 @@ export function AIConfig() {
                   onSetPrimary={handleSetPrimary}
                   onRefresh={loadData}
                   onEdit={handleEditProvider}
+                  revision={aiConfig?.revision}
                 />
               ))}
             </div>
@@ -1142,6 +1163,7 @@ export function AIConfig() {
             onClose={handleCloseDialog}
             onSave={loadData}
             editingProvider={editingProvider}
+            revision={aiConfig?.revision}
           />
         )}
       </AnimatePresence>
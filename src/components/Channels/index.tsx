@@ -1,4 +1,4 @@
-import { useEffect, useState } from 'react';
+import { useEffect, useRef, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { motion } from 'framer-motion';
 import { invoke } from '@tauri-apps/api/core';
@@ -24,22 +24,43 @@ import {
   Package,
   AlertTriangle,
   Trash2,
+  Power,
 } from 'lucide-react';
 import clsx from 'clsx';
+import { onConfigFileChanged } from '../../lib/tauri';
+import type { ChannelDescriptor } from '../../lib/tauri';
 
-interface FeishuPluginStatus {
+// 渠道插件状态（渠道描述中声明了 plugin_package 的渠道）
+interface ChannelPluginStatus {
+  channel: string;
   installed: boolean;
   version: string | null;
   plugin_name: string | null;
 }
 
+interface PluginInfo {
+  id: string;
+  name: string;
+  version: string | null;
+  installed: boolean;
+  package: string | null;
+  channel: string | null;
+}
+
 interface ChannelConfig {
   id: string;
   channel_type: string;
+  account: string;
   enabled: boolean;
+  disabled: boolean;
   config: Record<string, unknown>;
 }
 
+interface ChannelsOverview {
+  channels: ChannelConfig[];
+  revision: string;
+}
+
 // 渠道配置字段定义
 interface ChannelField {
   key: string;
@@ -50,141 +71,61 @@ interface ChannelField {
   required?: boolean;
 }
 
-const channelInfo: Record<
-  string,
-  { 
-    name: string; 
-    icon: React.ReactNode; 
-    color: string;
-    fields: ChannelField[];
-    helpText?: string;
+interface ChannelInfo {
+  name: string;
+  icon: React.ReactNode;
+  color: string;
+  fields: ChannelField[];
+  helpText?: string;
+}
+
+// 内置渠道的图标和颜色，字段来自后端渠道描述
+const channelStyles: Record<string, { icon: React.ReactNode; color: string }> = {
+  telegram: { icon: <MessageCircle size={20} />, color: 'text-blue-400' },
+  discord: { icon: <Hash size={20} />, color: 'text-indigo-400' },
+  slack: { icon: <Slack size={20} />, color: 'text-purple-400' },
+  feishu: { icon: <MessagesSquare size={20} />, color: 'text-blue-500' },
+  imessage: { icon: <Apple size={20} />, color: 'text-green-400' },
+  whatsapp: { icon: <MessageCircle size={20} />, color: 'text-green-500' },
+  wechat: { icon: <MessageSquare size={20} />, color: 'text-green-600' },
+  dingtalk: { icon: <Bell size={20} />, color: 'text-blue-600' },
+};
+
+const defaultChannelStyle = { icon: <MessageSquare size={20} />, color: 'text-gray-400' };
+
+// 内置渠道的翻译文本，覆盖渠道描述中的名称、字段名称、占位符和选项名称
+interface ChannelText {
+  name?: string;
+  helpText?: string;
+  fields?: Record<string, { label?: string; placeholder?: string; options?: Record<string, string> }>;
+}
+
+interface ConfigIssue {
+  path: string;
+  message: string;
+  severity: 'error' | 'warning';
+}
+
+// 渠道 schema 中字段允许的类型
+const schemaTypes = (descriptor: ChannelDescriptor | undefined, key: string): string[] => {
+  const properties = descriptor?.schema?.properties as Record<string, { type?: string | string[] }> | undefined;
+  const type = properties?.[key]?.type;
+  if (type === undefined) return [];
+  return Array.isArray(type) ? type : [type];
+};
+
+// 表单中的值都是字符串，保存时按原值类型和 schema 类型还原数字和布尔值
+const restoreFormValue = (value: string, original: unknown, types: string[]): unknown => {
+  const numeric = typeof original === 'number' || types.includes('integer') || types.includes('number');
+  if (numeric && value.trim() !== '' && Number.isFinite(Number(value))) {
+    return Number(value);
+  }
+  const boolean =
+    typeof original === 'boolean' || types.includes('boolean') || (types.length === 0 && typeof original !== 'string');
+  if (boolean && (value === 'true' || value === 'false')) {
+    return value === 'true';
   }
-> = {
-  telegram: {
-    name: 'Telegram',
-    icon: <MessageCircle size={20} />,
-    color: 'text-blue-400',
-    fields: [
-      { key: 'botToken', label: 'Bot Token', type: 'password', placeholder: '从 @BotFather 获取', required: true },
-      { key: 'userId', label: 'User ID', type: 'text', placeholder: '你的 Telegram User ID', required: true },
-      { key: 'dmPolicy', label: '私聊策略', type: 'select', options: [
-        { value: 'pairing', label: '配对模式' },
-        { value: 'open', label: '开放模式' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-      { key: 'groupPolicy', label: '群组策略', type: 'select', options: [
-        { value: 'allowlist', label: '白名单' },
-        { value: 'open', label: '开放' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-    ],
-    helpText: '1. 搜索 @BotFather 发送 /newbot 获取 Token  2. 搜索 @userinfobot 获取 User ID',
-  },
-  discord: {
-    name: 'Discord',
-    icon: <Hash size={20} />,
-    color: 'text-indigo-400',
-    fields: [
-      { key: 'token', label: 'Bot Token', type: 'password', placeholder: 'Discord Bot Token', required: true },
-      { key: 'testChannelId', label: '测试 Channel ID', type: 'text', placeholder: '用于发送测试消息的频道 ID (可选)' },
-      { key: 'dmPolicy', label: '私聊策略', type: 'select', options: [
-        { value: 'pairing', label: '配对模式' },
-        { value: 'open', label: '开放模式' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-    ],
-    helpText: '从 Discord Developer Portal 获取，开启开发者模式可复制 Channel ID',
-  },
-  slack: {
-    name: 'Slack',
-    icon: <Slack size={20} />,
-    color: 'text-purple-400',
-    fields: [
-      { key: 'botToken', label: 'Bot Token', type: 'password', placeholder: 'xoxb-...', required: true },
-      { key: 'appToken', label: 'App Token', type: 'password', placeholder: 'xapp-...' },
-      { key: 'testChannelId', label: '测试 Channel ID', type: 'text', placeholder: '用于发送测试消息的频道 ID (可选)' },
-    ],
-    helpText: '从 Slack API 后台获取，Channel ID 可从频道详情复制',
-  },
-  feishu: {
-    name: '飞书',
-    icon: <MessagesSquare size={20} />,
-    color: 'text-blue-500',
-    fields: [
-      { key: 'appId', label: 'App ID', type: 'text', placeholder: '飞书应用 App ID', required: true },
-      { key: 'appSecret', label: 'App Secret', type: 'password', placeholder: '飞书应用 App Secret', required: true },
-      { key: 'testChatId', label: '测试 Chat ID', type: 'text', placeholder: '用于发送测试消息的群聊/用户 ID (可选)' },
-      { key: 'connectionMode', label: '连接模式', type: 'select', options: [
-        { value: 'websocket', label: 'WebSocket (推荐)' },
-        { value: 'webhook', label: 'Webhook' },
-      ]},
-      { key: 'domain', label: '部署区域', type: 'select', options: [
-        { value: 'feishu', label: '国内 (feishu.cn)' },
-        { value: 'lark', label: '海外 (larksuite.com)' },
-      ]},
-      { key: 'requireMention', label: '需要 @提及', type: 'select', options: [
-        { value: 'true', label: '是' },
-        { value: 'false', label: '否' },
-      ]},
-    ],
-    helpText: '从飞书开放平台获取凭证，Chat ID 可从群聊设置中获取',
-  },
-  imessage: {
-    name: 'iMessage',
-    icon: <Apple size={20} />,
-    color: 'text-green-400',
-    fields: [
-      { key: 'dmPolicy', label: '私聊策略', type: 'select', options: [
-        { value: 'pairing', label: '配对模式' },
-        { value: 'open', label: '开放模式' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-      { key: 'groupPolicy', label: '群组策略', type: 'select', options: [
-        { value: 'allowlist', label: '白名单' },
-        { value: 'open', label: '开放' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-    ],
-    helpText: '仅支持 macOS，需要授权消息访问权限',
-  },
-  whatsapp: {
-    name: 'WhatsApp',
-    icon: <MessageCircle size={20} />,
-    color: 'text-green-500',
-    fields: [
-      { key: 'dmPolicy', label: '私聊策略', type: 'select', options: [
-        { value: 'pairing', label: '配对模式' },
-        { value: 'open', label: '开放模式' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-      { key: 'groupPolicy', label: '群组策略', type: 'select', options: [
-        { value: 'allowlist', label: '白名单' },
-        { value: 'open', label: '开放' },
-        { value: 'disabled', label: '禁用' },
-      ]},
-    ],
-    helpText: '需要扫描二维码登录，运行: openclaw channels login --channel whatsapp',
-  },
-  wechat: {
-    name: '微信',
-    icon: <MessageSquare size={20} />,
-    color: 'text-green-600',
-    fields: [
-      { key: 'appId', label: 'App ID', type: 'text', placeholder: '微信开放平台 App ID' },
-      { key: 'appSecret', label: 'App Secret', type: 'password', placeholder: '微信开放平台 App Secret' },
-    ],
-    helpText: '微信公众号/企业微信配置',
-  },
-  dingtalk: {
-    name: '钉钉',
-    icon: <Bell size={20} />,
-    color: 'text-blue-600',
-    fields: [
-      { key: 'appKey', label: 'App Key', type: 'text', placeholder: '钉钉应用 App Key' },
-      { key: 'appSecret', label: 'App Secret', type: 'password', placeholder: '钉钉应用 App Secret' },
-    ],
-    helpText: '从钉钉开放平台获取',
-  },
+  return value;
 };
 
 interface TestResult {
@@ -196,158 +137,150 @@ interface TestResult {
 export function Channels() {
   const { t } = useTranslation();
 
-  const channelInfo: Record<
-    string,
-    {
-      name: string;
-      icon: React.ReactNode;
-      color: string;
-      fields: ChannelField[];
-      helpText?: string;
-    }
-  > = {
+  const dmPolicyOptions = {
+    pairing: t('channels.policyPairing'),
+    open: t('channels.policyOpen'),
+    disabled: t('channels.policyDisabled'),
+  };
+  const groupPolicyOptions = {
+    allowlist: t('channels.groupAllowlist'),
+    open: t('channels.groupOpen'),
+    disabled: t('channels.policyDisabled'),
+  };
+
+  const channelTexts: Record<string, ChannelText> = {
     telegram: {
-      name: 'Telegram',
-      icon: <MessageCircle size={20} />,
-      color: 'text-blue-400',
-      fields: [
-        { key: 'botToken', label: 'Bot Token', type: 'password', placeholder: t('channels.telegram.botTokenPlaceholder'), required: true },
-        { key: 'userId', label: 'User ID', type: 'text', placeholder: t('channels.telegram.userIdPlaceholder'), required: true },
-        { key: 'dmPolicy', label: t('channels.telegram.dmPolicy'), type: 'select', options: [
-          { value: 'pairing', label: t('channels.policyPairing') },
-          { value: 'open', label: t('channels.policyOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-        { key: 'groupPolicy', label: t('channels.telegram.groupPolicy'), type: 'select', options: [
-          { value: 'allowlist', label: t('channels.groupAllowlist') },
-          { value: 'open', label: t('channels.groupOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-      ],
       helpText: t('channels.telegram.helpText'),
+      fields: {
+        botToken: { placeholder: t('channels.telegram.botTokenPlaceholder') },
+        userId: { placeholder: t('channels.telegram.userIdPlaceholder') },
+        dmPolicy: { label: t('channels.telegram.dmPolicy'), options: dmPolicyOptions },
+        groupPolicy: { label: t('channels.telegram.groupPolicy'), options: groupPolicyOptions },
+      },
     },
     discord: {
-      name: 'Discord',
-      icon: <Hash size={20} />,
-      color: 'text-indigo-400',
-      fields: [
-        { key: 'botToken', label: 'Bot Token', type: 'password', placeholder: 'Discord Bot Token', required: true },
-        { key: 'testChannelId', label: t('channels.discord.testChannelId'), type: 'text', placeholder: t('channels.discord.testChannelIdPlaceholder') },
-        { key: 'dmPolicy', label: t('channels.discord.dmPolicy'), type: 'select', options: [
-          { value: 'pairing', label: t('channels.policyPairing') },
-          { value: 'open', label: t('channels.policyOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-      ],
       helpText: t('channels.discord.helpText'),
+      fields: {
+        testChannelId: { label: t('channels.discord.testChannelId'), placeholder: t('channels.discord.testChannelIdPlaceholder') },
+        dmPolicy: { label: t('channels.discord.dmPolicy'), options: dmPolicyOptions },
+      },
     },
     slack: {
-      name: 'Slack',
-      icon: <Slack size={20} />,
-      color: 'text-purple-400',
-      fields: [
-        { key: 'botToken', label: 'Bot Token', type: 'password', placeholder: 'xoxb-...', required: true },
-        { key: 'appToken', label: 'App Token', type: 'password', placeholder: 'xapp-...' },
-        { key: 'testChannelId', label: t('channels.slack.testChannelId'), type: 'text', placeholder: t('channels.slack.testChannelIdPlaceholder') },
-      ],
       helpText: t('channels.slack.helpText'),
+      fields: {
+        testChannelId: { label: t('channels.slack.testChannelId'), placeholder: t('channels.slack.testChannelIdPlaceholder') },
+      },
     },
     feishu: {
       name: t('channels.feishu.name'),
-      icon: <MessagesSquare size={20} />,
-      color: 'text-blue-500',
-      fields: [
-        { key: 'appId', label: 'App ID', type: 'text', placeholder: t('channels.feishu.appIdPlaceholder'), required: true },
-        { key: 'appSecret', label: 'App Secret', type: 'password', placeholder: t('channels.feishu.appSecretPlaceholder'), required: true },
-        { key: 'testChatId', label: t('channels.feishu.testChatId'), type: 'text', placeholder: t('channels.feishu.testChatIdPlaceholder') },
-        { key: 'connectionMode', label: t('channels.feishu.connectionMode'), type: 'select', options: [
-          { value: 'websocket', label: t('channels.feishu.websocket') },
-          { value: 'webhook', label: t('channels.feishu.webhook') },
-        ]},
-        { key: 'domain', label: t('channels.feishu.domain'), type: 'select', options: [
-          { value: 'feishu', label: t('channels.feishu.domestic') },
-          { value: 'lark', label: t('channels.feishu.overseas') },
-        ]},
-        { key: 'requireMention', label: t('channels.feishu.requireMention'), type: 'select', options: [
-          { value: 'true', label: t('channels.feishu.yes') },
-          { value: 'false', label: t('channels.feishu.no') },
-        ]},
-      ],
       helpText: t('channels.feishu.helpText'),
+      fields: {
+        appId: { placeholder: t('channels.feishu.appIdPlaceholder') },
+        appSecret: { placeholder: t('channels.feishu.appSecretPlaceholder') },
+        testChatId: { label: t('channels.feishu.testChatId'), placeholder: t('channels.feishu.testChatIdPlaceholder') },
+        connectionMode: {
+          label: t('channels.feishu.connectionMode'),
+          options: { websocket: t('channels.feishu.websocket'), webhook: t('channels.feishu.webhook') },
+        },
+        domain: {
+          label: t('channels.feishu.domain'),
+          options: { feishu: t('channels.feishu.domestic'), lark: t('channels.feishu.overseas') },
+        },
+        requireMention: {
+          label: t('channels.feishu.requireMention'),
+          options: { true: t('channels.feishu.yes'), false: t('channels.feishu.no') },
+        },
+      },
     },
     imessage: {
-      name: 'iMessage',
-      icon: <Apple size={20} />,
-      color: 'text-green-400',
-      fields: [
-        { key: 'dmPolicy', label: t('channels.imessage.dmPolicy'), type: 'select', options: [
-          { value: 'pairing', label: t('channels.policyPairing') },
-          { value: 'open', label: t('channels.policyOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-        { key: 'groupPolicy', label: t('channels.imessage.groupPolicy'), type: 'select', options: [
-          { value: 'allowlist', label: t('channels.groupAllowlist') },
-          { value: 'open', label: t('channels.groupOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-      ],
       helpText: t('channels.imessage.helpText'),
+      fields: {
+        dmPolicy: { label: t('channels.imessage.dmPolicy'), options: dmPolicyOptions },
+        groupPolicy: { label: t('channels.imessage.groupPolicy'), options: groupPolicyOptions },
+      },
     },
     whatsapp: {
-      name: 'WhatsApp',
-      icon: <MessageCircle size={20} />,
-      color: 'text-green-500',
-      fields: [
-        { key: 'dmPolicy', label: t('channels.whatsapp.dmPolicy'), type: 'select', options: [
-          { value: 'pairing', label: t('channels.policyPairing') },
-          { value: 'open', label: t('channels.policyOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-        { key: 'groupPolicy', label: t('channels.whatsapp.groupPolicy'), type: 'select', options: [
-          { value: 'allowlist', label: t('channels.groupAllowlist') },
-          { value: 'open', label: t('channels.groupOpen') },
-          { value: 'disabled', label: t('channels.policyDisabled') },
-        ]},
-      ],
       helpText: t('channels.whatsapp.helpText'),
+      fields: {
+        dmPolicy: { label: t('channels.whatsapp.dmPolicy'), options: dmPolicyOptions },
+        groupPolicy: { label: t('channels.whatsapp.groupPolicy'), options: groupPolicyOptions },
+      },
     },
     wechat: {
       name: t('channels.wechat.name'),
-      icon: <MessageSquare size={20} />,
-      color: 'text-green-600',
-      fields: [
-        { key: 'appId', label: 'App ID', type: 'text', placeholder: t('channels.wechat.appIdPlaceholder') },
-        { key: 'appSecret', label: 'App Secret', type: 'password', placeholder: t('channels.wechat.appSecretPlaceholder') },
-      ],
       helpText: t('channels.wechat.helpText'),
+      fields: {
+        appId: { placeholder: t('channels.wechat.appIdPlaceholder') },
+        appSecret: { placeholder: t('channels.wechat.appSecretPlaceholder') },
+      },
     },
     dingtalk: {
       name: t('channels.dingtalk.name'),
-      icon: <Bell size={20} />,
-      color: 'text-blue-600',
-      fields: [
-        { key: 'appKey', label: 'App Key', type: 'text', placeholder: t('channels.dingtalk.appKeyPlaceholder') },
-        { key: 'appSecret', label: 'App Secret', type: 'password', placeholder: t('channels.dingtalk.appSecretPlaceholder') },
-      ],
       helpText: t('channels.dingtalk.helpText'),
+      fields: {
+        appKey: { placeholder: t('channels.dingtalk.appKeyPlaceholder') },
+        appSecret: { placeholder: t('channels.dingtalk.appSecretPlaceholder') },
+      },
     },
   };
 
+  // 渠道描述（内置 + 用户描述），表单字段按描述渲染
+  const [descriptors, setDescriptors] = useState<ChannelDescriptor[]>([]);
+
+  // 用户描述文件覆盖的渠道不使用内置翻译，以描述文件中的文本为准
+  const channelInfo: Record<string, ChannelInfo> = Object.fromEntries(
+    descriptors.map((descriptor) => {
+      const text = descriptor.custom ? {} : channelTexts[descriptor.id] ?? {};
+      const style = channelStyles[descriptor.id] ?? defaultChannelStyle;
+      const info: ChannelInfo = {
+        ...style,
+        name: text.name ?? descriptor.name,
+        helpText: text.helpText,
+        fields: descriptor.fields.map((field) => {
+          const fieldText = text.fields?.[field.key];
+          return {
+            key: field.key,
+            label: fieldText?.label ?? field.label,
+            type: field.type,
+            placeholder: fieldText?.placeholder ?? field.placeholder,
+            options: field.options?.map((opt) => ({
+              value: opt.value,
+              label: fieldText?.options?.[opt.value] ?? opt.label,
+            })),
+            required: field.required,
+          };
+        }),
+      };
+      return [descriptor.id, info];
+    })
+  );
+
   const [channels, setChannels] = useState<ChannelConfig[]>([]);
+  // 最近一次读取渠道列表时的配置版本，修改时传回以拒绝覆盖外部修改
+  const revisionRef = useRef<string | undefined>(undefined);
+  // 表单载入时的配置版本：编辑期间配置被外部修改时保存会被拒绝
+  const [formRevision, setFormRevision] = useState<string | undefined>(undefined);
   const [loading, setLoading] = useState(true);
   const [selectedChannel, setSelectedChannel] = useState<string | null>(null);
   const [configForm, setConfigForm] = useState<Record<string, string>>({});
+  // 后端 schema 校验返回的字段级错误和警告（警告不阻止保存）
+  const [fieldErrors, setFieldErrors] = useState<Record<string, string>>({});
+  const [fieldWarnings, setFieldWarnings] = useState<Record<string, string>>({});
+  // 表单中没有输入框的字段的问题，以及保存失败的原因
+  const [otherIssues, setOtherIssues] = useState<ConfigIssue[]>([]);
+  const [saveError, setSaveError] = useState<string | null>(null);
   const [saving, setSaving] = useState(false);
   const [testing, setTesting] = useState(false);
   const [testResult, setTestResult] = useState<TestResult | null>(null);
   const [loginLoading, setLoginLoading] = useState(false);
   const [clearing, setClearing] = useState(false);
+  const [toggling, setToggling] = useState(false);
   const [showClearConfirm, setShowClearConfirm] = useState(false);
 
-  // 飞书插件状态
-  const [feishuPluginStatus, setFeishuPluginStatus] = useState<FeishuPluginStatus | null>(null);
-  const [feishuPluginLoading, setFeishuPluginLoading] = useState(false);
-  const [feishuPluginInstalling, setFeishuPluginInstalling] = useState(false);
+  // 渠道插件状态
+  const [pluginStatus, setPluginStatus] = useState<ChannelPluginStatus | null>(null);
+  const [pluginLoading, setPluginLoading] = useState(false);
+  const [pluginInstalling, setPluginInstalling] = useState(false);
 
   // 跟踪哪些密码字段显示明文
   const [visiblePasswords, setVisiblePasswords] = useState<Set<string>>(new Set());
@@ -364,32 +297,44 @@ export function Channels() {
     });
   };
 
-  // 检查飞书插件状态
-  const checkFeishuPlugin = async () => {
-    setFeishuPluginLoading(true);
+  // 检查渠道插件状态
+  const checkChannelPlugin = async (descriptor: ChannelDescriptor) => {
+    setPluginLoading(true);
     try {
-      const status = await invoke<FeishuPluginStatus>('check_feishu_plugin');
-      setFeishuPluginStatus(status);
+      const plugins = await invoke<PluginInfo[]>('list_plugins');
+      const plugin = plugins.find(
+        (p) => p.installed && (p.id === descriptor.id || p.channel === descriptor.id)
+      );
+      setPluginStatus({
+        channel: descriptor.id,
+        installed: !!plugin,
+        version: plugin?.version ?? null,
+        plugin_name: plugin ? plugin.package ?? plugin.name : null,
+      });
     } catch (e) {
-      console.error('检查飞书插件失败:', e);
-      setFeishuPluginStatus({ installed: false, version: null, plugin_name: null });
+      console.error('检查渠道插件失败:', e);
+      setPluginStatus({ channel: descriptor.id, installed: false, version: null, plugin_name: null });
     } finally {
-      setFeishuPluginLoading(false);
+      setPluginLoading(false);
     }
   };
 
-  // 安装飞书插件
-  const handleInstallFeishuPlugin = async () => {
-    setFeishuPluginInstalling(true);
+  // 安装渠道插件（按插件目录中的渠道 ID 安装）
+  const handleInstallChannelPlugin = async (descriptor: ChannelDescriptor) => {
+    setPluginInstalling(true);
     try {
-      const result = await invoke<string>('install_feishu_plugin');
+      const result = await invoke<string>('install_plugin', {
+        spec: descriptor.id,
+        expectedRevision: revisionRef.current,
+      });
       alert(result);
-      // 刷新插件状态
-      await checkFeishuPlugin();
+      // 安装会修改配置，刷新列表和表单版本，并刷新插件状态
+      await fetchChannels();
+      await checkChannelPlugin(descriptor);
     } catch (e) {
       alert(t('setup.installFailed', { error: e }));
     } finally {
-      setFeishuPluginInstalling(false);
+      setPluginInstalling(false);
     }
   };
 
@@ -409,7 +354,11 @@ export function Channels() {
     setShowClearConfirm(false);
     setClearing(true);
     try {
-      await invoke('clear_channel_config', { channelId: selectedChannel });
+      await invoke('clear_channel_config', {
+        channelId: channel?.channel_type ?? selectedChannel,
+        account: channel?.account,
+        expectedRevision: revisionRef.current,
+      });
       // 清空表单
       setConfigForm({});
       // 刷新列表
@@ -430,10 +379,50 @@ export function Channels() {
     }
   };
 
+  // 停用/启用渠道（保留配置）
+  const handleToggleEnabled = async () => {
+    const channel = channels.find((c) => c.id === selectedChannel);
+    if (!channel) return;
+
+    const enabled = channel.disabled;
+    const channelName = channelInfo[channel.channel_type]?.name || channel.channel_type;
+    setToggling(true);
+    try {
+      const result = await invoke<{ restart_required: boolean }>('set_channel_enabled', {
+        channelId: channel.channel_type,
+        account: channel.account,
+        enabled,
+        expectedRevision: revisionRef.current,
+      });
+      // Gateway 运行中时询问是否立即重启使修改生效
+      let restartPending = result.restart_required;
+      if (restartPending && confirm(t('channels.restartToApply'))) {
+        await invoke('restart_service');
+        restartPending = false;
+      }
+      await fetchChannels();
+      setTestResult({
+        success: true,
+        message: t(enabled ? 'channels.channelEnabled' : 'channels.channelDisabled', { name: channelName }),
+        error: restartPending ? t('channels.restartRequired') : null,
+      });
+    } catch (e) {
+      setTestResult({
+        success: false,
+        message: t('channels.toggleFailed'),
+        error: String(e),
+      });
+    } finally {
+      setToggling(false);
+    }
+  };
+
   // 快速测试
   const handleQuickTest = async () => {
     if (!selectedChannel) return;
 
+    const channel = channels.find((c) => c.id === selectedChannel);
+
     setTesting(true);
     setTestResult(null);
 
@@ -443,7 +432,10 @@ export function Channels() {
         channel: string;
         message: string;
         error: string | null;
-      }>('test_channel', { channelType: selectedChannel });
+      }>('test_channel', {
+        channelType: channel?.channel_type ?? selectedChannel,
+        account: channel?.account,
+      });
 
       setTestResult({
         success: result.success,
@@ -461,12 +453,14 @@ export function Channels() {
     }
   };
 
-  // WhatsApp 扫码登录
-  const handleWhatsAppLogin = async () => {
+  // 渠道登录（如扫码登录），渠道描述中声明了 login 的渠道可用
+  const handleChannelLogin = async (channel: ChannelConfig) => {
+    const channelType = channel.channel_type;
+    const name = channelInfo[channelType]?.name || channelType;
     setLoginLoading(true);
     try {
-      // 调用后端命令启动 WhatsApp 登录
-      await invoke('start_channel_login', { channelType: 'whatsapp' });
+      // 调用后端命令启动登录
+      await invoke('start_channel_login', { channelType });
 
       // 开始轮询检查登录状态
       const pollInterval = setInterval(async () => {
@@ -474,7 +468,7 @@ export function Channels() {
           const result = await invoke<{
             success: boolean;
             message: string;
-          }>('test_channel', { channelType: 'whatsapp' });
+          }>('test_channel', { channelType });
 
           if (result.success) {
             clearInterval(pollInterval);
@@ -483,7 +477,7 @@ export function Channels() {
             await fetchChannels();
             setTestResult({
               success: true,
-              message: t('channels.whatsapp.loginSuccess'),
+              message: t('channels.login.loginSuccess', { name }),
               error: null,
             });
           }
@@ -498,18 +492,23 @@ export function Channels() {
         setLoginLoading(false);
       }, 60000);
 
-      alert(t('channels.whatsapp.loginPrompt'));
+      alert(t('channels.login.loginPrompt'));
     } catch (e) {
-      alert(t('channels.whatsapp.loginFailed') + e);
+      alert(t('channels.login.loginFailed') + e);
       setLoginLoading(false);
     }
   };
 
-  const fetchChannels = async () => {
+  // syncForm 为 false 时只刷新列表，表单仍使用载入时的版本
+  const fetchChannels = async (syncForm = true) => {
     try {
-      const result = await invoke<ChannelConfig[]>('get_channels_config');
-      setChannels(result);
-      return result;
+      const result = await invoke<ChannelsOverview>('get_channels_config');
+      setChannels(result.channels);
+      revisionRef.current = result.revision;
+      if (syncForm) {
+        setFormRevision(result.revision);
+      }
+      return result.channels;
     } catch (e) {
       console.error('获取渠道配置失败:', e);
       return [];
@@ -519,7 +518,14 @@ export function Channels() {
   useEffect(() => {
     const init = async () => {
       try {
-        const result = await fetchChannels();
+        const [result, channelDescriptors] = await Promise.all([
+          fetchChannels(),
+          invoke<ChannelDescriptor[]>('get_channel_descriptors').catch((e) => {
+            console.error('获取渠道描述失败:', e);
+            return [];
+          }),
+        ]);
+        setDescriptors(channelDescriptors);
 
         // 自动选择第一个已配置的渠道
         const configured = result.find((c) => c.enabled);
@@ -533,6 +539,18 @@ export function Channels() {
     init();
   }, []);
 
+  // 配置文件被外部修改时刷新列表（不影响正在编辑的表单），保存时使用新版本
+  useEffect(() => {
+    const unlisten = onConfigFileChanged((change) => {
+      if (change.kind === 'config') {
+        fetchChannels(false);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const handleChannelSelect = (channelId: string, channelList?: ChannelConfig[]) => {
     setSelectedChannel(channelId);
     setTestResult(null); // 清除测试结果
@@ -540,9 +558,16 @@ export function Channels() {
     const list = channelList || channels;
     const channel = list.find((c) => c.id === channelId);
 
+    setFieldErrors({});
+    setFieldWarnings({});
+    setOtherIssues([]);
+    setSaveError(null);
+    setFormRevision(revisionRef.current);
     if (channel) {
       const form: Record<string, string> = {};
       Object.entries(channel.config).forEach(([key, value]) => {
+        // 数组、对象等字段不在表单中编辑，保存时原样带回
+        if (typeof value === 'object' && value !== null) return;
         // 处理布尔值
         if (typeof value === 'boolean') {
           form[key] = value ? 'true' : 'false';
@@ -551,11 +576,6 @@ export function Channels() {
         }
       });
       setConfigForm(form);
-
-      // 如果选择的是飞书渠道，检查插件状态
-      if (channel.channel_type === 'feishu') {
-        checkFeishuPlugin();
-      }
     } else {
       setConfigForm({});
     }
@@ -569,23 +589,48 @@ export function Channels() {
       const channel = channels.find((c) => c.id === selectedChannel);
       if (!channel) return;
 
-      // 转换表单值
-      const config: Record<string, unknown> = {};
+      // 转换表单值，保留表单中没有的数组、对象字段
+      const descriptor = descriptors.find((d) => d.id === channel.channel_type);
+      const config: Record<string, unknown> = Object.fromEntries(
+        Object.entries(channel.config).filter(([, value]) => typeof value === 'object' && value !== null)
+      );
       Object.entries(configForm).forEach(([key, value]) => {
-        if (value === 'true') {
-          config[key] = true;
-        } else if (value === 'false') {
-          config[key] = false;
-        } else if (value) {
-          config[key] = value;
+        if (value) {
+          config[key] = restoreFormValue(value, channel.config[key], schemaTypes(descriptor, key));
         }
       });
 
+      // 先按渠道 schema 校验，问题显示在对应输入框下；只有错误会阻止保存
+      setSaveError(null);
+      const issues = await invoke<ConfigIssue[]>('validate_channel_config', {
+        channel: { ...channel, config },
+      });
+      const fieldKeys = channelInfo[channel.channel_type]?.fields.map((f) => f.key) ?? [];
+      const errors: Record<string, string> = {};
+      const warnings: Record<string, string> = {};
+      const others: ConfigIssue[] = [];
+      issues.forEach((issue) => {
+        const key = issue.path.split(/[.[]/)[0];
+        if (!fieldKeys.includes(key)) {
+          others.push(issue);
+          return;
+        }
+        const target = issue.severity === 'error' ? errors : warnings;
+        target[key] = target[key] ? `${target[key]}; ${issue.message}` : issue.message;
+      });
+      setFieldErrors(errors);
+      setFieldWarnings(warnings);
+      setOtherIssues(others);
+      if (issues.some((issue) => issue.severity === 'error')) {
+        return;
+      }
+
       await invoke('save_channel_config', {
         channel: {
           ...channel,
           config,
         },
+        expectedRevision: formRevision,
       });
 
       // 刷新列表
@@ -594,7 +639,7 @@ export function Channels() {
       alert(t('channels.configSaved'));
     } catch (e) {
       console.error('保存失败:', e);
-      alert(t('channels.saveFailed', { error: e }));
+      setSaveError(t('channels.saveFailed', { error: e }));
     } finally {
       setSaving(false);
     }
@@ -602,6 +647,16 @@ export function Channels() {
 
   const currentChannel = channels.find((c) => c.id === selectedChannel);
   const currentInfo = currentChannel ? channelInfo[currentChannel.channel_type] : null;
+  const currentDescriptor = currentChannel
+    ? descriptors.find((d) => d.id === currentChannel.channel_type)
+    : undefined;
+
+  // 选中需要插件的渠道时检查插件状态
+  useEffect(() => {
+    if (currentDescriptor?.plugin_package) {
+      checkChannelPlugin(currentDescriptor);
+    }
+  }, [currentDescriptor?.id, descriptors]);
 
   // 检查渠道是否有有效配置
   const hasValidConfig = (channel: ChannelConfig) => {
@@ -637,9 +692,8 @@ export function Channels() {
             </h3>
             {channels.map((channel) => {
               const info = channelInfo[channel.channel_type] || {
+                ...defaultChannelStyle,
                 name: channel.channel_type,
-                icon: <MessageSquare size={20} />,
-                color: 'text-gray-400',
                 fields: [],
               };
               const isSelected = selectedChannel === channel.id;
@@ -672,9 +726,17 @@ export function Channels() {
                       )}
                     >
                       {info.name}
+                      {channel.account !== 'default' && (
+                        <span className="ml-1 text-xs text-gray-500">({channel.account})</span>
+                      )}
                     </p>
                     <div className="flex items-center gap-2 mt-1">
-                      {isConfigured ? (
+                      {isConfigured && channel.disabled ? (
+                        <>
+                          <Power size={12} className="text-yellow-500" />
+                          <span className="text-xs text-yellow-500">{t('channels.disabled')}</span>
+                        </>
+                      ) : isConfigured ? (
                         <>
                           <Check size={12} className="text-green-400" />
                           <span className="text-xs text-green-400">{t('channels.configured')}</span>
@@ -719,22 +781,22 @@ export function Channels() {
                   </div>
                 </div>
 
-                {/* 飞书插件状态提示 */}
-                {currentChannel.channel_type === 'feishu' && (
+                {/* 渠道插件状态提示 */}
+                {currentDescriptor?.plugin_package && (
                   <div className="mb-4">
-                    {feishuPluginLoading ? (
+                    {pluginLoading || pluginStatus?.channel !== currentDescriptor.id ? (
                       <div className="p-4 bg-dark-600 rounded-xl border border-dark-500 flex items-center gap-3">
                         <Loader2 size={20} className="animate-spin text-gray-400" />
-                        <span className="text-gray-400">{t('channels.feishu.checkingPlugin')}</span>
+                        <span className="text-gray-400">{t('channels.plugin.checking', { name: currentInfo.name })}</span>
                       </div>
-                    ) : feishuPluginStatus?.installed ? (
+                    ) : pluginStatus.installed ? (
                       <div className="p-4 bg-green-500/10 rounded-xl border border-green-500/30 flex items-center gap-3">
                         <Package size={20} className="text-green-400" />
                         <div className="flex-1">
-                          <p className="text-green-400 font-medium">{t('channels.feishu.pluginInstalled')}</p>
+                          <p className="text-green-400 font-medium">{t('channels.plugin.installed', { name: currentInfo.name })}</p>
                           <p className="text-xs text-gray-400 mt-0.5">
-                            {feishuPluginStatus.plugin_name || '@m1heng-clawd/feishu'}
-                            {feishuPluginStatus.version && ` v${feishuPluginStatus.version}`}
+                            {pluginStatus.plugin_name || currentDescriptor.plugin_package}
+                            {pluginStatus.version && ` v${pluginStatus.version}`}
                           </p>
                         </div>
                         <CheckCircle size={16} className="text-green-400" />
@@ -744,33 +806,33 @@ export function Channels() {
                         <div className="flex items-start gap-3">
                           <AlertTriangle size={20} className="text-amber-400 mt-0.5" />
                           <div className="flex-1">
-                            <p className="text-amber-400 font-medium">{t('channels.feishu.pluginNeeded')}</p>
+                            <p className="text-amber-400 font-medium">{t('channels.plugin.needed', { name: currentInfo.name })}</p>
                             <p className="text-xs text-gray-400 mt-1">
-                              {t('channels.feishu.pluginDesc')}
+                              {t('channels.plugin.desc', { name: currentInfo.name, package: currentDescriptor.plugin_package })}
                             </p>
                             <div className="mt-3 flex flex-wrap gap-2">
                               <button
-                                onClick={handleInstallFeishuPlugin}
-                                disabled={feishuPluginInstalling}
+                                onClick={() => handleInstallChannelPlugin(currentDescriptor)}
+                                disabled={pluginInstalling}
                                 className="btn-primary flex items-center gap-2 text-sm py-2"
                               >
-                                {feishuPluginInstalling ? (
+                                {pluginInstalling ? (
                                   <Loader2 size={14} className="animate-spin" />
                                 ) : (
                                   <Download size={14} />
                                 )}
-                                {feishuPluginInstalling ? t('channels.feishu.installing') : t('channels.feishu.installPlugin')}
+                                {pluginInstalling ? t('channels.plugin.installing') : t('channels.plugin.install')}
                               </button>
                               <button
-                                onClick={checkFeishuPlugin}
-                                disabled={feishuPluginLoading}
+                                onClick={() => checkChannelPlugin(currentDescriptor)}
+                                disabled={pluginLoading}
                                 className="btn-secondary flex items-center gap-2 text-sm py-2"
                               >
-                                {t('channels.feishu.refreshStatus')}
+                                {t('channels.plugin.refreshStatus')}
                               </button>
                             </div>
                             <p className="text-xs text-gray-500 mt-2">
-                              {t('channels.feishu.manualInstall')} <code className="px-1.5 py-0.5 bg-dark-600 rounded text-gray-400">openclaw plugins install @m1heng-clawd/feishu</code>
+                              {t('channels.plugin.manualInstall')} <code className="px-1.5 py-0.5 bg-dark-600 rounded text-gray-400">openclaw plugins install {currentDescriptor.plugin_package}</code>
                             </p>
                           </div>
                         </div>
@@ -840,22 +902,46 @@ export function Channels() {
                           className="input-base"
                         />
                       )}
+                      {fieldErrors[field.key] && (
+                        <p className="mt-1 text-xs text-red-400">{fieldErrors[field.key]}</p>
+                      )}
+                      {fieldWarnings[field.key] && (
+                        <p className="mt-1 text-xs text-yellow-500">{fieldWarnings[field.key]}</p>
+                      )}
                     </div>
                   ))}
 
-                  {/* WhatsApp 特殊处理：扫码登录按钮 */}
-                  {currentChannel.channel_type === 'whatsapp' && (
+                  {/* 表单外字段的校验问题和保存失败原因 */}
+                  {(otherIssues.length > 0 || saveError) && (
+                    <div className="p-4 bg-dark-600 rounded-xl border border-dark-500 space-y-1">
+                      {otherIssues.length > 0 && (
+                        <p className="text-sm text-gray-400 mb-1">{t('channels.otherFieldIssues')}</p>
+                      )}
+                      {otherIssues.map((issue) => (
+                        <p
+                          key={`${issue.path}-${issue.message}`}
+                          className={clsx('text-xs', issue.severity === 'error' ? 'text-red-400' : 'text-yellow-500')}
+                        >
+                          {issue.path}: {issue.message}
+                        </p>
+                      ))}
+                      {saveError && <p className="text-xs text-red-400 whitespace-pre-wrap">{saveError}</p>}
+                    </div>
+                  )}
+
+                  {/* 需要登录的渠道（如 WhatsApp 扫码）：登录按钮 */}
+                  {currentDescriptor?.login && (
                     <div className="p-4 bg-green-500/10 rounded-xl border border-green-500/30">
                       <div className="flex items-center gap-3 mb-3">
                         <QrCode size={24} className="text-green-400" />
                         <div>
-                          <p className="text-white font-medium">{t('channels.whatsapp.qrLogin')}</p>
-                          <p className="text-xs text-gray-400">{t('channels.whatsapp.qrLoginDesc')}</p>
+                          <p className="text-white font-medium">{t('channels.login.qrLogin')}</p>
+                          <p className="text-xs text-gray-400">{t('channels.login.qrLoginDesc', { name: currentInfo.name })}</p>
                         </div>
                       </div>
                       <div className="flex gap-2">
                         <button
-                          onClick={handleWhatsAppLogin}
+                          onClick={() => handleChannelLogin(currentChannel)}
                           disabled={loginLoading}
                           className="flex-1 btn-secondary flex items-center justify-center gap-2"
                         >
@@ -864,7 +950,7 @@ export function Channels() {
                           ) : (
                             <QrCode size={16} />
                           )}
-                          {loginLoading ? t('channels.whatsapp.waitingLogin') : t('channels.whatsapp.startLogin')}
+                          {loginLoading ? t('channels.login.waitingLogin') : t('channels.login.startLogin')}
                         </button>
                         <button
                           onClick={async () => {
@@ -873,7 +959,7 @@ export function Channels() {
                           }}
                           disabled={testing}
                           className="btn-secondary flex items-center justify-center gap-2 px-4"
-                          title={t('channels.feishu.refreshStatus')}
+                          title={t('channels.login.refreshStatus')}
                         >
                           {testing ? (
                             <Loader2 size={16} className="animate-spin" />
@@ -883,7 +969,7 @@ export function Channels() {
                         </button>
                       </div>
                       <p className="text-xs text-gray-500 mt-2 text-center">
-                        {t('channels.whatsapp.refreshHint')}
+                        {t('channels.login.refreshHint', { channel: currentChannel.channel_type })}
                       </p>
                     </div>
                   )}
@@ -917,6 +1003,22 @@ export function Channels() {
                       {t('channels.quickTest')}
                     </button>
 
+                    {/* 停用/启用按钮（保留配置） */}
+                    {hasValidConfig(currentChannel) && (
+                      <button
+                        onClick={handleToggleEnabled}
+                        disabled={toggling}
+                        className="btn-secondary flex items-center gap-2"
+                      >
+                        {toggling ? (
+                          <Loader2 size={16} className="animate-spin" />
+                        ) : (
+                          <Power size={16} />
+                        )}
+                        {currentChannel.disabled ? t('channels.enableChannel') : t('channels.disableChannel')}
+                      </button>
+                    )}
+
                     {/* 清空配置按钮 */}
                     {!showClearConfirm ? (
                       <button
@@ -6,11 +6,13 @@ import { StatusCard } from './StatusCard';
 import { QuickActions } from './QuickActions';
 import { SystemInfo } from './SystemInfo';
 import { Setup } from '../Setup';
-import { api, ServiceStatus, isTauri } from '../../lib/tauri';
+import { api, ServiceStatus, isTauri, onGatewayLog } from '../../lib/tauri';
 import { Terminal, RefreshCw, ChevronDown, ChevronUp } from 'lucide-react';
 import clsx from 'clsx';
 import { EnvironmentStatus } from '../../App';
 
+const MAX_LOG_LINES = 200;
+
 interface DashboardProps {
   envStatus: EnvironmentStatus | null;
   onSetupComplete: () => void;
@@ -53,15 +55,22 @@ export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
 
   useEffect(() => {
     fetchStatus();
-    fetchLogs();
     if (!isTauri()) return;
 
     const statusInterval = setInterval(fetchStatus, 3000);
-    const logsInterval = autoRefreshLogs ? setInterval(fetchLogs, 2000) : null;
+    return () => clearInterval(statusInterval);
+  }, []);
+
+  // 先加载已有日志，之后由后端推送新日志
+  useEffect(() => {
+    fetchLogs();
+    if (!isTauri() || !autoRefreshLogs) return;
 
+    const unlisten = onGatewayLog((entries) => {
+      setLogs((prev) => [...prev, ...entries.map((e) => e.line)].slice(-MAX_LOG_LINES));
+    });
     return () => {
-      clearInterval(statusInterval);
-      if (logsInterval) clearInterval(logsInterval);
+      unlisten.then((fn) => fn());
     };
   }, [autoRefreshLogs]);
 
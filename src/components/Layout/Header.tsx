@@ -4,6 +4,7 @@ import { PageType } from '../../App';
 import { RefreshCw, ExternalLink, Loader2 } from 'lucide-react';
 import { open } from '@tauri-apps/plugin-shell';
 import { invoke } from '@tauri-apps/api/core';
+import { useAppStore } from '../../stores/appStore';
 
 interface HeaderProps {
   currentPage: PageType;
@@ -14,6 +15,7 @@ export function Header({ currentPage }: HeaderProps) {
   const title = t(`header.${currentPage}.title`);
   const description = t(`header.${currentPage}.description`);
   const [opening, setOpening] = useState(false);
+  const serviceStatus = useAppStore((state) => state.serviceStatus);
 
   const handleOpenDashboard = async () => {
     setOpening(true);
@@ -22,7 +24,7 @@ export function Header({ currentPage }: HeaderProps) {
       await open(url);
     } catch (e) {
       console.error('打开 Dashboard 失败:', e);
-      window.open('http://localhost:18789', '_blank');
+      window.open(`http://localhost:${serviceStatus?.port ?? 18789}`, '_blank');
     } finally {
       setOpening(false);
     }
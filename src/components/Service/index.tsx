@@ -11,6 +11,9 @@ import {
 } from 'lucide-react';
 import clsx from 'clsx';
 import { serviceLogger } from '../../lib/logger';
+import { onGatewayLog } from '../../lib/tauri';
+
+const MAX_LOG_LINES = 1000;
 
 export function ServiceManager() {
   const [logs, setLogs] = useState<string[]>([]);
@@ -33,14 +36,16 @@ export function ServiceManager() {
   useEffect(() => {
     serviceLogger.info('ServiceManager 组件挂载');
     fetchLogs();
-    if (autoRefresh) {
-      serviceLogger.debug('启动日志自动刷新 (间隔: 2秒)');
-      const interval = setInterval(fetchLogs, 2000);
-      return () => {
-        serviceLogger.debug('停止日志自动刷新');
-        clearInterval(interval);
-      };
-    }
+    if (!autoRefresh) return;
+
+    serviceLogger.debug('订阅 gateway 实时日志');
+    const unlisten = onGatewayLog((entries) => {
+      setLogs((prev) => [...prev, ...entries.map((e) => e.line)].slice(-MAX_LOG_LINES));
+    });
+    return () => {
+      serviceLogger.debug('取消订阅 gateway 实时日志');
+      unlisten.then((fn) => fn());
+    };
   }, [autoRefresh]);
 
   useEffect(() => {
@@ -145,7 +150,7 @@ export function ServiceManager() {
               onChange={(e) => setAutoRefresh(e.target.checked)}
               className="w-4 h-4 rounded border-dark-500 bg-dark-600 text-claw-500 focus:ring-claw-500"
             />
-            自动刷新
+            实时跟踪
           </label>
 
           <button
@@ -163,7 +168,7 @@ export function ServiceManager() {
         <div className="flex items-center gap-2 px-4 py-2 bg-dark-700 border-b border-dark-600">
           <Terminal size={14} className="text-gray-500" />
           <span className="text-xs text-gray-400 font-medium">
-            /tmp/openclaw-gateway.log
+            gateway.log / gateway.err.log
           </span>
           <div className="flex-1" />
           <span className="text-xs text-gray-500">
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { apiLogger } from './logger';
 
 // 检查是否在 Tauri 环境中运行
@@ -32,6 +33,131 @@ export interface ServiceStatus {
   cpu_percent: number | null;
 }
 
+// Gateway 进程退出记录
+export interface GatewayExitRecord {
+  profile: string;
+  pid: number;
+  code: number | null;
+  signal: number | null;
+  exited_at: string;
+  uptime_seconds: number;
+  restart_scheduled: boolean;
+}
+
+// Gateway 实时日志行
+export interface GatewayLogLine {
+  source: string;
+  line: string;
+}
+
+// 订阅 gateway 实时日志（后端跟踪日志文件并推送 gateway-log 事件）
+export function onGatewayLog(handler: (lines: GatewayLogLine[]) => void): Promise<UnlistenFn> {
+  return listen<GatewayLogLine[]>('gateway-log', (event) => handler(event.payload));
+}
+
+// 配置文件被外部修改
+export interface ConfigFileChange {
+  kind: 'config' | 'env';
+  path: string;
+  revision: string;
+}
+
+// 监听配置文件外部修改事件
+export function onConfigFileChanged(handler: (change: ConfigFileChange) => void): Promise<UnlistenFn> {
+  return listen<ConfigFileChange>('config-file-changed', (event) => handler(event.payload));
+}
+
+// 完整配置及其版本
+export interface ConfigSnapshot {
+  config: unknown;
+  revision: string;
+}
+
+// 配置校验问题
+export interface ConfigIssue {
+  path: string;
+  message: string;
+  severity: 'error' | 'warning';
+}
+
+// openclaw.json 备份
+export interface ConfigBackup {
+  name: string;
+  path: string;
+  created_at: string;
+  size_bytes: number;
+}
+
+// 结构化日志
+export interface GatewayLogEntry {
+  timestamp: string | null;
+  level: string | null;
+  subsystem: string | null;
+  message: string;
+  source: string;
+  raw: string;
+}
+
+export interface LogQuery {
+  level?: string;
+  since?: string;
+  until?: string;
+  channel?: string;
+  search?: string;
+  limit?: number;
+}
+
+// 配置档案
+export interface Profile {
+  name: string;
+  configDir: string;
+  envFile?: string;
+  logDir?: string;
+  gatewayPort?: number;
+  tokenTtlHours?: number;
+}
+
+// Gateway Token 签发与过期信息
+export interface GatewayTokenInfo {
+  issued_at: string | null;
+  ttl_hours: number | null;
+  expires_at: string | null;
+}
+
+export interface ProfilesOverview {
+  active: string;
+  profiles: Profile[];
+}
+
+// 环境变量（值已脱敏）
+export interface EnvVarInfo {
+  key: string;
+  masked_value: string;
+  source: 'env' | 'vault';
+  is_secret: boolean;
+  used_by: string[];
+}
+
+// 环境变量批量修改项（value 为 null 表示删除）
+export interface EnvChange {
+  key: string;
+  value: string | null;
+}
+
+// 密钥库状态（不包含密钥值）
+export interface SecretsStatus {
+  backend: string;
+  key_source: 'machine-key' | 'passphrase';
+  location: string;
+  names: string[];
+}
+
+// 明文密钥迁移结果
+export interface SecretsMigration {
+  migrated: string[];
+  errors: string[];
+}
+
 // 系统信息
 export interface SystemInfo {
   os: string;
@@ -79,6 +205,7 @@ export interface SuggestedModel {
   context_window: number | null;
   max_tokens: number | null;
   recommended: boolean;
+  capabilities?: string[];
 }
 
 // 已配置的 Provider
@@ -103,8 +230,11 @@ export interface ConfiguredModel {
 // AI 配置概览
 export interface AIConfigOverview {
   primary_model: string | null;
+  fallback_models: string[];
   configured_providers: ConfiguredProvider[];
   available_models: string[];
+  /** 读取时的配置版本，修改时作为 expectedRevision 传回 */
+  revision: string;
 }
 
 // 模型配置
@@ -121,12 +251,67 @@ export interface ModelConfig {
 
 // 渠道配置
 export interface ChannelConfig {
+  /** 默认账号为渠道类型，其他账号为 <渠道类型>:<账号> */
   id: string;
   channel_type: string;
+  /** 账号名称，default 为默认账号 */
+  account: string;
   enabled: boolean;
+  /** 已停用（配置保留） */
+  disabled: boolean;
   config: Record<string, unknown>;
 }
 
+export interface ChannelsOverview {
+  channels: ChannelConfig[];
+  /** 读取时的配置版本，修改时作为 expectedRevision 传回 */
+  revision: string;
+}
+
+export interface ChannelToggleResult {
+  channel: string;
+  account: string;
+  enabled: boolean;
+  restarted: boolean;
+  restart_required: boolean;
+}
+
+// 插件
+export interface PluginInfo {
+  id: string;
+  name: string;
+  version: string | null;
+  source: string | null;
+  installed: boolean;
+  enabled: boolean;
+  allowed: boolean;
+  package: string | null;
+  channel: string | null;
+}
+
+export interface ChannelFieldDescriptor {
+  key: string;
+  label: string;
+  type: 'text' | 'password' | 'select';
+  secret: boolean;
+  required: boolean;
+  test_only: boolean;
+  placeholder?: string;
+  options?: { value: string; label: string }[];
+}
+
+export interface ChannelDescriptor {
+  id: string;
+  name: string;
+  fields: ChannelFieldDescriptor[];
+  test_target_field?: string;
+  login?: string;
+  plugin_package?: string;
+  renamed_fields?: Record<string, string>;
+  schema?: Record<string, unknown>;
+  custom: boolean;
+}
+
 // 诊断结果
 export interface DiagnosticResult {
   name: string;
@@ -145,6 +330,97 @@ export interface AITestResult {
   latency_ms: number | null;
 }
 
+export type ProviderErrorClass =
+  | 'auth'
+  | 'quota'
+  | 'rate_limited'
+  | 'model_not_found'
+  | 'invalid_request'
+  | 'server'
+  | 'timeout'
+  | 'network'
+  | 'unknown';
+
+export interface ModelTestResult {
+  success: boolean;
+  provider: string;
+  model: string;
+  api: string;
+  status: number | null;
+  error_class: ProviderErrorClass | null;
+  error: string | null;
+  response: string | null;
+  first_token_ms: number | null;
+  total_ms: number;
+}
+
+export interface ModelTestProgress {
+  completed: number;
+  total: number;
+  result: ModelTestResult;
+}
+
+// 监听批量模型测试进度
+export function onModelTestProgress(handler: (progress: ModelTestProgress) => void): Promise<UnlistenFn> {
+  return listen<ModelTestProgress>('model-test-progress', (event) => handler(event.payload));
+}
+
+export type UsagePeriod = 'day' | 'week' | 'month';
+
+export interface UsageTotals {
+  requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  cache_read_tokens: number;
+  cache_write_tokens: number;
+  cost: number;
+}
+
+export interface UsageBreakdown extends UsageTotals {
+  key: string;
+}
+
+export interface UsagePeriodReport {
+  start: string;
+  end: string;
+  totals: UsageTotals;
+  by_provider: UsageBreakdown[];
+  by_model: UsageBreakdown[];
+  by_channel: UsageBreakdown[];
+}
+
+export interface UsageReport {
+  period: UsagePeriod;
+  periods: UsagePeriodReport[];
+  totals: UsageTotals;
+  sessions_scanned: number;
+  unpriced_models: string[];
+}
+
+export type BudgetAction = 'notify' | 'switch_model' | 'stop_gateway';
+
+export interface BudgetSettings {
+  period: UsagePeriod;
+  globalLimit?: number;
+  providerLimits?: Record<string, number>;
+  alertThresholds: number[];
+  action: BudgetAction;
+  cheaperModel?: string;
+}
+
+export interface BudgetUsage {
+  scope: string;
+  limit: number;
+  spent: number;
+  percent: number;
+}
+
+export interface BudgetStatus {
+  period: UsagePeriod;
+  period_start: string;
+  budgets: BudgetUsage[];
+}
+
 // API 封装（带日志）
 export const api = {
   // 服务管理
@@ -153,6 +429,9 @@ export const api = {
   stopService: () => invokeWithLog<string>('stop_service'),
   restartService: () => invokeWithLog<string>('restart_service'),
   getLogs: (lines?: number) => invokeWithLog<string[]>('get_logs', { lines }),
+  queryLogs: (query: LogQuery) => invokeWithLog<GatewayLogEntry[]>('query_logs', { query }),
+  getGatewayExitHistory: () =>
+    invokeWithLog<GatewayExitRecord[]>('get_gateway_exit_history'),
 
   // 系统信息
   getSystemInfo: () => invokeWithLog<SystemInfo>('get_system_info'),
@@ -160,11 +439,43 @@ export const api = {
   getOpenclawVersion: () => invokeWithLog<string | null>('get_openclaw_version'),
 
   // 配置管理
-  getConfig: () => invokeWithLog<unknown>('get_config'),
-  saveConfig: (config: unknown) => invokeWithLog<string>('save_config', { config }),
+  getConfig: () => invokeWithLog<ConfigSnapshot>('get_config'),
+  saveConfig: (config: unknown, expectedRevision?: string) =>
+    invokeWithLog<string>('save_config', { config, expectedRevision }),
+  getConfigRevision: () => invokeWithLog<string>('get_config_revision'),
+  validateConfig: () => invokeWithLog<ConfigIssue[]>('validate_config'),
+  listConfigBackups: () => invokeWithLog<ConfigBackup[]>('list_config_backups'),
+  restoreConfigBackup: (name: string) => invokeWithLog<string>('restore_config_backup', { name }),
   getEnvValue: (key: string) => invokeWithLog<string | null>('get_env_value', { key }),
   saveEnvValue: (key: string, value: string) =>
     invokeWithLog<string>('save_env_value', { key, value }),
+  listEnvVars: () => invokeWithLog<EnvVarInfo[]>('list_env_vars'),
+  deleteEnvVar: (key: string) => invokeWithLog<string>('delete_env_var', { key }),
+  applyEnvChanges: (changes: EnvChange[]) =>
+    invokeWithLog<string>('apply_env_changes', { changes }),
+
+  // Gateway 端口
+  getGatewayPort: () => invokeWithLog<number>('get_gateway_port'),
+  setGatewayPort: (port: number | null) => invokeWithLog<string>('set_gateway_port', { port }),
+
+  // Gateway Token
+  getDashboardUrl: () => invokeWithLog<string>('get_dashboard_url'),
+  rotateGatewayToken: () => invokeWithLog<string>('rotate_gateway_token'),
+  getGatewayTokenInfo: () => invokeWithLog<GatewayTokenInfo>('get_gateway_token_info'),
+  setGatewayTokenTtl: (hours: number | null) =>
+    invokeWithLog<string>('set_gateway_token_ttl', { hours }),
+
+  // 配置档案
+  getProfiles: () => invokeWithLog<ProfilesOverview>('get_profiles'),
+  setActiveProfile: (name: string) => invokeWithLog<string>('set_active_profile', { name }),
+  saveProfile: (profile: Profile) => invokeWithLog<string>('save_profile', { profile }),
+  deleteProfile: (name: string) => invokeWithLog<string>('delete_profile', { name }),
+
+  // 密钥库
+  getSecretsStatus: () => invokeWithLog<SecretsStatus>('get_secrets_status'),
+  setSecret: (name: string, value: string) => invokeWithLog<string>('set_secret', { name, value }),
+  deleteSecret: (name: string) => invokeWithLog<string>('delete_secret', { name }),
+  migrateSecrets: () => invokeWithLog<SecretsMigration>('migrate_secrets'),
 
   // AI Provider（旧版兼容）
   getAIProviders: () => invokeWithLog<AIProviderOption[]>('get_ai_providers'),
@@ -177,7 +488,8 @@ export const api = {
     baseUrl: string,
     apiKey: string | null,
     apiType: string,
-    models: ModelConfig[]
+    models: ModelConfig[],
+    expectedRevision?: string
   ) =>
     invokeWithLog<string>('save_provider', {
       providerName,
@@ -185,24 +497,71 @@ export const api = {
       apiKey,
       apiType,
       models,
+      expectedRevision,
     }),
-  deleteProvider: (providerName: string) =>
-    invokeWithLog<string>('delete_provider', { providerName }),
-  setPrimaryModel: (modelId: string) =>
-    invokeWithLog<string>('set_primary_model', { modelId }),
-  addAvailableModel: (modelId: string) =>
-    invokeWithLog<string>('add_available_model', { modelId }),
-  removeAvailableModel: (modelId: string) =>
-    invokeWithLog<string>('remove_available_model', { modelId }),
+  deleteProvider: (providerName: string, expectedRevision?: string) =>
+    invokeWithLog<string>('delete_provider', { providerName, expectedRevision }),
+  setPrimaryModel: (modelId: string, expectedRevision?: string) =>
+    invokeWithLog<string>('set_primary_model', { modelId, expectedRevision }),
+  setFallbackModels: (modelIds: string[], expectedRevision?: string) =>
+    invokeWithLog<string>('set_fallback_models', { modelIds, expectedRevision }),
+  addAvailableModel: (modelId: string, expectedRevision?: string) =>
+    invokeWithLog<string>('add_available_model', { modelId, expectedRevision }),
+  removeAvailableModel: (modelId: string, expectedRevision?: string) =>
+    invokeWithLog<string>('remove_available_model', { modelId, expectedRevision }),
+  discoverModels: (provider: string) =>
+    invokeWithLog<SuggestedModel[]>('discover_models', { provider }),
+  testProviderModel: (provider: string, model: string) =>
+    invokeWithLog<ModelTestResult>('test_provider_model', { provider, model }),
+  testAllModels: (concurrency?: number) =>
+    invokeWithLog<ModelTestResult[]>('test_all_models', { concurrency }),
+
+  // 用量统计
+  getUsageReport: (period: UsagePeriod, count?: number) =>
+    invokeWithLog<UsageReport>('get_usage_report', { period, count }),
+  getBudgetSettings: () => invokeWithLog<BudgetSettings>('get_budget_settings'),
+  saveBudgetSettings: (budget: BudgetSettings) =>
+    invokeWithLog<string>('save_budget_settings', { budget }),
+  getBudgetStatus: () => invokeWithLog<BudgetStatus>('get_budget_status'),
 
   // 渠道
-  getChannelsConfig: () => invokeWithLog<ChannelConfig[]>('get_channels_config'),
-  saveChannelConfig: (channel: ChannelConfig) =>
-    invokeWithLog<string>('save_channel_config', { channel }),
+  getChannelDescriptors: () => invokeWithLog<ChannelDescriptor[]>('get_channel_descriptors'),
+  validateChannelConfig: (channel: ChannelConfig) =>
+    invokeWithLog<ConfigIssue[]>('validate_channel_config', { channel }),
+  getChannelsConfig: () => invokeWithLog<ChannelsOverview>('get_channels_config'),
+  saveChannelConfig: (channel: ChannelConfig, expectedRevision?: string) =>
+    invokeWithLog<string>('save_channel_config', { channel, expectedRevision }),
+  clearChannelConfig: (channelId: string, account?: string, expectedRevision?: string) =>
+    invokeWithLog<string>('clear_channel_config', { channelId, account, expectedRevision }),
+  setChannelEnabled: (
+    channelId: string,
+    enabled: boolean,
+    account?: string,
+    restartGateway?: boolean,
+    expectedRevision?: string
+  ) =>
+    invokeWithLog<ChannelToggleResult>('set_channel_enabled', {
+      channelId,
+      enabled,
+      account,
+      restartGateway,
+      expectedRevision,
+    }),
+
+  // 插件管理
+  listPlugins: () => invokeWithLog<PluginInfo[]>('list_plugins'),
+  installPlugin: (spec: string, expectedRevision?: string) =>
+    invokeWithLog<string>('install_plugin', { spec, expectedRevision }),
+  uninstallPlugin: (pluginId: string, expectedRevision?: string) =>
+    invokeWithLog<string>('uninstall_plugin', { pluginId, expectedRevision }),
+  setPluginEnabled: (pluginId: string, enabled: boolean, expectedRevision?: string) =>
+    invokeWithLog<string>('set_plugin_enabled', { pluginId, enabled, expectedRevision }),
+  updatePlugin: (pluginId: string, expectedRevision?: string) =>
+    invokeWithLog<string>('update_plugin', { pluginId, expectedRevision }),
 
   // 诊断测试
   runDoctor: () => invokeWithLog<DiagnosticResult[]>('run_doctor'),
   testAIConnection: () => invokeWithLog<AITestResult>('test_ai_connection'),
-  testChannel: (channelType: string) =>
-    invokeWithLog<unknown>('test_channel', { channelType }),
+  testChannel: (channelType: string, account?: string) =>
+    invokeWithLog<unknown>('test_channel', { channelType, account }),
 };